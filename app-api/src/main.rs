@@ -1,6 +1,7 @@
 use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
 use app_core::firebase::auth;
 use app_core::graphql;
+use app_core::mail;
 use app_core::misoca;
 use dotenv;
 use juniper_actix::{graphql_handler, playground_handler};
@@ -22,10 +23,12 @@ async fn main() -> std::io::Result<()> {
             env::var("MISOCA_SECRET").unwrap(),
             env::var("MISOCA_REDIRECT_URL").unwrap(),
         );
+        let mail_cli = mail::Client::from_env();
 
         App::new()
             .data(schema)
             .data(misoca_cli)
+            .data(mail_cli)
             .service(
                 web::resource("/graphql")
                     .route(web::post().to(graphql_route))
@@ -53,6 +56,7 @@ async fn graphql_route(
     payload: web::Payload,
     schema: web::Data<graphql::Schema>,
     misoca_cli: web::Data<misoca::Client>,
+    mail_cli: web::Data<Option<mail::Client>>,
 ) -> actix_web::Result<HttpResponse> {
     // 開発用
     let authenticated_user_id: Option<String> = match req.headers().get("x-user-id") {
@@ -64,7 +68,11 @@ async fn graphql_route(
         println!("login user id: {}", id);
     }
 
-    let context = graphql::Context::new(authenticated_user_id, misoca_cli.get_ref().clone());
+    let context = graphql::Context::new(
+        authenticated_user_id,
+        misoca_cli.get_ref().clone(),
+        mail_cli.get_ref().clone(),
+    );
 
     graphql_handler(&schema, &context, req, payload).await
 }
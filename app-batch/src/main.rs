@@ -1,3 +1,4 @@
+use app_core::mail;
 use app_core::misoca;
 use app_core::task;
 use app_core::CoreError;
@@ -23,7 +24,19 @@ async fn main() {
     let result = if command == "sync-invoice" {
         task::sync_invoice::exec(misoca_cli, now).await
     } else if command == "create-invoice" {
-        task::create_invoice::exec(misoca_cli, now).await
+        task::create_invoice::exec(misoca_cli, mail::Client::from_env(), now).await
+    } else if command == "detect-overdue" {
+        task::detect_overdue::exec(now).await
+    } else if command == "send-reminders" {
+        match mail::Client::from_env() {
+            Some(mail_cli) => task::send_reminders::exec(mail_cli, now).await,
+            None => Err(CoreError::Internal("SMTP_HOST is required".to_string())),
+        }
+    } else if command == "import-exchange-rates" {
+        match args.get(2) {
+            Some(path) => task::import_exchange_rates::exec(path.clone(), now).await,
+            None => Err(CoreError::Internal("csv path is required".to_string())),
+        }
     } else {
         Err(CoreError::Internal("unknown command".to_string()))
     };
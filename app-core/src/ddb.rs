@@ -9,12 +9,26 @@ use diesel::prelude::*;
 use crate::CoreResult;
 
 pub mod bank;
+pub mod exchange_rate;
+pub mod expense;
 pub mod invoice;
+pub mod invoice_delivery;
+pub mod invoice_number_sequence;
+pub mod invoice_reminder;
 pub mod pager;
+pub mod payment;
+pub mod retainer_usage;
 mod schema;
 pub mod sender;
 pub mod supplier;
+pub mod supplier_adjustment;
+pub mod supplier_item;
+pub mod supplier_milestone;
+pub mod supplier_price;
+pub mod supplier_suspension;
 pub mod user;
+pub mod voided_invoice_number;
+pub mod working_hours;
 
 pub fn establish_connection() -> MysqlConnection {
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
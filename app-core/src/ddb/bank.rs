@@ -64,20 +64,20 @@ impl Dao<domain::bank::Bank> {
             .filter(banks::user_id.eq(user_id))
             .order(banks::created_at.desc())
             .load::<Entity>(conn)
-            .map(|v: Vec<Entity>| {
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
                 v.into_iter()
-                    .map(|v| domain::bank::Bank::try_from(v).unwrap())
-                    .collect::<Vec<_>>()
-            })
-            .map_err(CoreError::from);
+                    .map(|v| domain::bank::Bank::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
     }
 
     pub fn get(&self, conn: &MysqlConnection, id: String) -> CoreResult<domain::bank::Bank> {
         banks::table
             .find(id)
             .first(conn)
-            .map(|v: Entity| domain::bank::Bank::try_from(v).unwrap())
             .map_err(CoreError::from)
+            .and_then(|v: Entity| domain::bank::Bank::try_from(v).map_err(CoreError::Internal))
     }
 
     pub fn insert(&self, conn: &MysqlConnection, item: &domain::bank::Bank) -> CoreResult<()> {
@@ -0,0 +1,134 @@
+use crate::ddb::schema::exchange_rates;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, AsChangeset)]
+#[primary_key(currency, ymd)]
+#[table_name = "exchange_rates"]
+pub struct Entity {
+    pub currency: i32,
+    pub ymd: String,
+    pub rate: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::currency::ExchangeRate {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::currency::ExchangeRate {
+            currency: domain::currency::Currency::from(e.currency),
+            ymd: domain::YMD::from_str(e.ymd.as_str())
+                .map_err(|_e| "parse ymd error".to_string())?,
+            rate: e.rate,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::currency::ExchangeRate> for Entity {
+    fn from(d: domain::currency::ExchangeRate) -> Entity {
+        Entity {
+            currency: d.currency.int(),
+            ymd: d.ymd.to_string(),
+            rate: d.rate,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::currency::ExchangeRate> {
+    pub fn get_all_by_currency(
+        &self,
+        conn: &MysqlConnection,
+        currency: &domain::currency::Currency,
+    ) -> CoreResult<Vec<domain::currency::ExchangeRate>> {
+        return exchange_rates::table
+            .filter(exchange_rates::currency.eq(currency.int()))
+            .order(exchange_rates::ymd.desc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::currency::ExchangeRate::try_from(v).map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    /// 指定日以前の仲値を全通貨分取得する。請求書ごとに発行日の仲値で円換算するときに使う
+    pub fn get_all_until(
+        &self,
+        conn: &MysqlConnection,
+        ymd: &domain::YMD,
+    ) -> CoreResult<Vec<domain::currency::ExchangeRate>> {
+        return exchange_rates::table
+            .filter(exchange_rates::ymd.le(ymd.to_string()))
+            .order(exchange_rates::ymd.desc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::currency::ExchangeRate::try_from(v).map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    /// 指定日以前で最も新しい仲値を取得する
+    pub fn get_latest_on(
+        &self,
+        conn: &MysqlConnection,
+        currency: &domain::currency::Currency,
+        ymd: &domain::YMD,
+    ) -> CoreResult<domain::currency::ExchangeRate> {
+        exchange_rates::table
+            .filter(exchange_rates::currency.eq(currency.int()))
+            .filter(exchange_rates::ymd.le(ymd.to_string()))
+            .order(exchange_rates::ymd.desc())
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::currency::ExchangeRate::try_from(v).map_err(CoreError::Internal)
+            })
+    }
+
+    /// 同じ日付の仲値が登録済みの場合は上書きする
+    pub fn upsert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::currency::ExchangeRate,
+    ) -> CoreResult<()> {
+        let mut e: Entity = item.clone().into();
+        let current = exchange_rates::table
+            .find((e.currency, e.ymd.clone()))
+            .first::<Entity>(conn)
+            .optional()
+            .map_err(CoreError::from)?;
+
+        let result = match current {
+            Some(current) => {
+                e.created_at = current.created_at;
+                diesel::update(exchange_rates::table.find((e.currency, e.ymd.clone())))
+                    .set(&e)
+                    .execute(conn)
+            }
+            None => diesel::insert_into(exchange_rates::table)
+                .values(e)
+                .execute(conn),
+        };
+        if let Err(e) = result.map_err(CoreError::from) {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use crate::ddb::schema::expenses;
+use crate::ddb::supplier;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use bigdecimal::BigDecimal;
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(supplier::Entity, foreign_key = "supplier_id")]
+#[table_name = "expenses"]
+pub struct Entity {
+    pub id: String,
+    pub supplier_id: String,
+    pub ymd: String,
+    pub description: String,
+    pub amount: BigDecimal,
+    pub tax_type: i32,
+    pub receipt_path: Option<String>,
+    pub invoice_id: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::expense::Expense {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::expense::Expense {
+            id: e.id,
+            supplier_id: e.supplier_id,
+            ymd: domain::YMD::from_str(e.ymd.as_str())
+                .map_err(|_e| "parse ymd error".to_string())?,
+            description: e.description,
+            amount: domain::money::Money::try_from(e.amount)?,
+            tax_type: domain::invoice::TaxType::from(e.tax_type),
+            receipt_path: e.receipt_path,
+            invoice_id: e.invoice_id,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::expense::Expense> for Entity {
+    fn from(d: domain::expense::Expense) -> Entity {
+        Entity {
+            id: d.id,
+            supplier_id: d.supplier_id,
+            ymd: d.ymd.to_string(),
+            description: d.description,
+            amount: d.amount.into(),
+            tax_type: d.tax_type.int(),
+            receipt_path: d.receipt_path,
+            invoice_id: d.invoice_id,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::expense::Expense> {
+    pub fn get_all_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<Vec<domain::expense::Expense>> {
+        return expenses::table
+            .filter(expenses::supplier_id.eq(supplier_id))
+            .order((expenses::ymd.desc(), expenses::created_at.desc()))
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| domain::expense::Expense::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    /// untilまでに支払った未請求の立替金を支払日順に取得する
+    pub fn get_pending_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+        until: &domain::YMD,
+    ) -> CoreResult<Vec<domain::expense::Expense>> {
+        return expenses::table
+            .filter(expenses::supplier_id.eq(supplier_id))
+            .filter(expenses::invoice_id.is_null())
+            .filter(expenses::ymd.le(until.to_string()))
+            .order((expenses::ymd.asc(), expenses::created_at.asc()))
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| domain::expense::Expense::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get(&self, conn: &MysqlConnection, id: String) -> CoreResult<domain::expense::Expense> {
+        expenses::table
+            .find(id)
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::expense::Expense::try_from(v).map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::expense::Expense,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(expenses::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn update(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::expense::Expense,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::update(expenses::table.find(e.id.clone()))
+            .set(&e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete(&self, conn: &MysqlConnection, id: String) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(expenses::table.find(id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(expenses::table)
+            .filter(expenses::supplier_id.eq(supplier_id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use crate::ddb::Dao;
 use crate::domain;
 use crate::{CoreError, CoreResult};
 use async_trait::async_trait;
+use bigdecimal::BigDecimal;
 use dataloader::{cached, BatchFn};
 use diesel::dsl::*;
 use diesel::prelude::*;
@@ -28,12 +29,29 @@ pub struct Entity {
     pub payment_due_on_at: Option<chrono::NaiveDateTime>,
     pub invoice_number: String,
     pub payment_status: i32,
+    pub remote_payment_status: i32,
     pub invoice_status: i32,
     pub recipient_name: String,
     pub subject: String,
-    pub total_amount: i32,
-    pub tax: i32,
+    pub billing_ym: String,
+    pub currency: i32,
+    pub total_amount: BigDecimal,
+    pub tax: BigDecimal,
+    pub withholding_tax: BigDecimal,
+    pub payment_amount: BigDecimal,
+    pub paid_amount: BigDecimal,
+    pub standard_taxable_amount: BigDecimal,
+    pub standard_tax: BigDecimal,
+    pub reduced_taxable_amount: BigDecimal,
+    pub reduced_tax: BigDecimal,
+    pub non_taxable_amount: BigDecimal,
+    pub remarks: String,
     pub pdf_path: Option<String>,
+    pub overdue_at: Option<chrono::NaiveDateTime>,
+    pub cancelled_at: Option<chrono::NaiveDateTime>,
+    pub cancel_reason: String,
+    pub credit_note_id: Option<String>,
+    pub original_invoice_id: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -51,12 +69,45 @@ impl TryFrom<Entity> for domain::invoice::Invoice {
                 .map_err(|_e| "parse ymd error".to_string())?,
             invoice_number: e.invoice_number,
             payment_status: domain::invoice::PaymentStatus::from(e.payment_status),
+            remote_payment_status: domain::invoice::PaymentStatus::from(e.remote_payment_status),
             invoice_status: domain::invoice::InvoiceStatus::from(e.invoice_status),
             recipient_name: e.recipient_name,
             subject: e.subject,
-            total_amount: e.total_amount,
-            tax: e.tax,
+            billing_ym: domain::YM::from_str(e.billing_ym.as_str())
+                .map_err(|_e| "parse ym error".to_string())?,
+            currency: domain::currency::Currency::from(e.currency),
+            total_amount: domain::money::Money::try_from(e.total_amount)?,
+            tax: domain::money::Money::try_from(e.tax)?,
+            withholding_tax: domain::money::Money::try_from(e.withholding_tax)?,
+            payment_amount: domain::money::Money::try_from(e.payment_amount)?,
+            paid_amount: domain::money::Money::try_from(e.paid_amount)?,
+            tax_summaries: vec![
+                domain::invoice::TaxSummary {
+                    tax_type: domain::invoice::TaxType::Standard10,
+                    taxable_amount: domain::money::Money::try_from(e.standard_taxable_amount)?,
+                    tax: domain::money::Money::try_from(e.standard_tax)?,
+                },
+                domain::invoice::TaxSummary {
+                    tax_type: domain::invoice::TaxType::Reduced8,
+                    taxable_amount: domain::money::Money::try_from(e.reduced_taxable_amount)?,
+                    tax: domain::money::Money::try_from(e.reduced_tax)?,
+                },
+                domain::invoice::TaxSummary {
+                    tax_type: domain::invoice::TaxType::NonTaxable,
+                    taxable_amount: domain::money::Money::try_from(e.non_taxable_amount)?,
+                    tax: domain::money::Money::zero(),
+                },
+            ]
+            .into_iter()
+            .filter(|v| !v.taxable_amount.is_zero() || !v.tax.is_zero())
+            .collect(),
+            remarks: e.remarks,
             pdf_path: e.pdf_path,
+            overdue_at: e.overdue_at,
+            cancelled_at: e.cancelled_at,
+            cancel_reason: e.cancel_reason,
+            credit_note_id: e.credit_note_id,
+            original_invoice_id: e.original_invoice_id,
             created_at: e.created_at,
             updated_at: e.updated_at,
         })
@@ -65,6 +116,10 @@ impl TryFrom<Entity> for domain::invoice::Invoice {
 
 impl From<domain::invoice::Invoice> for Entity {
     fn from(d: domain::invoice::Invoice) -> Entity {
+        let standard = summary_of(&d.tax_summaries, domain::invoice::TaxType::Standard10);
+        let reduced = summary_of(&d.tax_summaries, domain::invoice::TaxType::Reduced8);
+        let non_taxable = summary_of(&d.tax_summaries, domain::invoice::TaxType::NonTaxable);
+
         Entity {
             id: d.id,
             supplier_id: d.supplier_id,
@@ -74,18 +129,49 @@ impl From<domain::invoice::Invoice> for Entity {
             payment_due_on_at: d.payment_due_on_ymd.to_datetime(),
             invoice_number: d.invoice_number,
             payment_status: d.payment_status.int(),
+            remote_payment_status: d.remote_payment_status.int(),
             invoice_status: d.invoice_status.int(),
             recipient_name: d.recipient_name,
             subject: d.subject,
-            total_amount: d.total_amount,
-            tax: d.tax,
+            billing_ym: d.billing_ym.to_string(),
+            currency: d.currency.int(),
+            total_amount: d.total_amount.into(),
+            tax: d.tax.into(),
+            withholding_tax: d.withholding_tax.into(),
+            payment_amount: d.payment_amount.into(),
+            paid_amount: d.paid_amount.into(),
+            standard_taxable_amount: standard.0.into(),
+            standard_tax: standard.1.into(),
+            reduced_taxable_amount: reduced.0.into(),
+            reduced_tax: reduced.1.into(),
+            non_taxable_amount: non_taxable.0.into(),
+            remarks: d.remarks,
             pdf_path: d.pdf_path,
+            overdue_at: d.overdue_at,
+            cancelled_at: d.cancelled_at,
+            cancel_reason: d.cancel_reason,
+            credit_note_id: d.credit_note_id,
+            original_invoice_id: d.original_invoice_id,
             created_at: d.created_at,
             updated_at: d.updated_at,
         }
     }
 }
 
+/// 税率ごとの（税抜金額, 税額）
+fn summary_of(
+    tax_summaries: &[domain::invoice::TaxSummary],
+    tax_type: domain::invoice::TaxType,
+) -> (domain::money::Money, domain::money::Money) {
+    tax_summaries
+        .iter()
+        .find(|v| v.tax_type == tax_type)
+        .map_or(
+            (domain::money::Money::zero(), domain::money::Money::zero()),
+            |v| (v.taxable_amount.clone(), v.tax.clone()),
+        )
+}
+
 impl Dao<domain::invoice::Invoice> {
     pub fn get_all_by_supplier(
         &self,
@@ -99,12 +185,12 @@ impl Dao<domain::invoice::Invoice> {
             .limit(pager.get_limit())
             .offset(pager.get_offset())
             .load::<Entity>(conn)
-            .map(|v: Vec<Entity>| {
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
                 v.into_iter()
-                    .map(|v| domain::invoice::Invoice::try_from(v).unwrap())
-                    .collect::<Vec<_>>()
-            })
-            .map_err(CoreError::from);
+                    .map(|v| domain::invoice::Invoice::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
     }
 
     pub fn get_all_by_user(
@@ -120,25 +206,104 @@ impl Dao<domain::invoice::Invoice> {
             .limit(pager.get_limit())
             .offset(pager.get_offset())
             .load::<(Entity, supplier::Entity)>(conn)
-            .map(|v: Vec<(Entity, supplier::Entity)>| {
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<(Entity, supplier::Entity)>| {
                 v.into_iter()
-                    .map(|v| {
-                        (
-                            domain::invoice::Invoice::try_from(v.0).unwrap(),
-                            domain::supplier::Supplier::try_from(v.1).unwrap(),
-                        )
+                    .map(|v| -> CoreResult<_> {
+                        Ok((
+                            domain::invoice::Invoice::try_from(v.0)?,
+                            domain::supplier::Supplier::try_from(v.1)?,
+                        ))
                     })
-                    .collect::<Vec<_>>()
-            })
-            .map_err(CoreError::from);
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    /// 発行日が期間内の請求書と赤伝
+    pub fn get_all_issued_by_user(
+        &self,
+        conn: &MysqlConnection,
+        user_id: String,
+        from: &domain::YMD,
+        to: &domain::YMD,
+    ) -> CoreResult<Vec<domain::invoice::Invoice>> {
+        return invoices::table
+            .inner_join(suppliers::table)
+            .select(invoices::all_columns)
+            .filter(suppliers::user_id.eq(user_id))
+            .filter(invoices::issue_at.between(from.to_datetime(), to.to_datetime()))
+            .order(invoices::issue_at.asc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| domain::invoice::Invoice::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    /// 入金が揃っていない請求書を支払期限順に取得する。取り消した請求書と赤伝は除く
+    pub fn get_all_unpaid_by_user(
+        &self,
+        conn: &MysqlConnection,
+        user_id: String,
+    ) -> CoreResult<Vec<(domain::invoice::Invoice, domain::supplier::Supplier)>> {
+        return invoices::table
+            .inner_join(suppliers::table)
+            .filter(suppliers::user_id.eq(user_id))
+            .filter(invoices::payment_status.eq_any(vec![
+                domain::invoice::PaymentStatus::UnPaid.int(),
+                domain::invoice::PaymentStatus::PartiallyPaid.int(),
+            ]))
+            .filter(invoices::invoice_status.ne(domain::invoice::InvoiceStatus::Cancelled.int()))
+            .filter(invoices::original_invoice_id.is_null())
+            .order(invoices::payment_due_on_at.asc())
+            .load::<(Entity, supplier::Entity)>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<(Entity, supplier::Entity)>| {
+                v.into_iter()
+                    .map(|v| -> CoreResult<_> {
+                        Ok((
+                            domain::invoice::Invoice::try_from(v.0)?,
+                            domain::supplier::Supplier::try_from(v.1)?,
+                        ))
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    /// 支払期限が指定日より前で、まだ期限超過を記録していない未入金の請求書
+    pub fn get_all_overdue_candidates(
+        &self,
+        conn: &MysqlConnection,
+        ymd: &domain::YMD,
+    ) -> CoreResult<Vec<domain::invoice::Invoice>> {
+        return invoices::table
+            .filter(invoices::payment_status.eq_any(vec![
+                domain::invoice::PaymentStatus::UnPaid.int(),
+                domain::invoice::PaymentStatus::PartiallyPaid.int(),
+            ]))
+            .filter(invoices::invoice_status.ne(domain::invoice::InvoiceStatus::Cancelled.int()))
+            .filter(invoices::original_invoice_id.is_null())
+            .filter(invoices::overdue_at.is_null())
+            .filter(invoices::payment_due_on_at.lt(ymd.to_datetime()))
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| domain::invoice::Invoice::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
     }
 
     pub fn get(&self, conn: &MysqlConnection, id: String) -> CoreResult<domain::invoice::Invoice> {
         invoices::table
             .find(id)
             .first(conn)
-            .map(|v: Entity| domain::invoice::Invoice::try_from(v).unwrap())
             .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::invoice::Invoice::try_from(v).map_err(CoreError::Internal)
+            })
     }
 
     pub fn get_count_by_supplier(
@@ -162,18 +327,26 @@ impl Dao<domain::invoice::Invoice> {
             .map_err(CoreError::from)
     }
 
-    pub fn exist_by_subject(
+    /// ymを締め月とする期間の請求書を発行済みか。取り消した請求書も発行済みとして数え、バッチで発行し直さない。
+    /// 締め月を記録する前に発行した請求書は件名で判定する
+    pub fn exist_by_period(
         &self,
         conn: &MysqlConnection,
         supplier_id: String,
+        ym: &domain::YM,
         subject: String,
     ) -> CoreResult<bool> {
         select(exists(
-            invoices::table.filter(
-                invoices::subject
-                    .eq(subject)
-                    .and(invoices::supplier_id.eq(supplier_id)),
-            ),
+            invoices::table
+                .filter(invoices::supplier_id.eq(supplier_id))
+                .filter(invoices::original_invoice_id.is_null())
+                .filter(
+                    invoices::billing_ym
+                        .eq(ym.to_string())
+                        .or(invoices::billing_ym
+                            .eq("")
+                            .and(invoices::subject.eq(subject))),
+                ),
         ))
         .get_result(conn)
         .map_err(CoreError::from)
@@ -211,31 +384,6 @@ impl Dao<domain::invoice::Invoice> {
         Ok(())
     }
 
-    pub fn delete(&self, conn: &MysqlConnection, id: String) -> CoreResult<()> {
-        if let Err(e) = diesel::delete(invoices::table.find(id))
-            .execute(conn)
-            .map_err(CoreError::from)
-        {
-            return Err(e);
-        }
-        Ok(())
-    }
-
-    pub fn delete_by_supplier(
-        &self,
-        conn: &MysqlConnection,
-        supplier_id: String,
-    ) -> CoreResult<()> {
-        if let Err(e) = diesel::delete(invoices::table)
-            .filter(invoices::supplier_id.eq(supplier_id))
-            .execute(conn)
-            .map_err(CoreError::from)
-        {
-            return Err(e);
-        }
-        Ok(())
-    }
-
     fn batch_get_by_supplier(
         &self,
         conn: &MysqlConnection,
@@ -247,12 +395,12 @@ impl Dao<domain::invoice::Invoice> {
             .order(invoices::issue_at.desc())
             .limit(20)
             .load::<Entity>(conn)
-            .map(|v: Vec<Entity>| {
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
                 v.into_iter()
-                    .map(|v| domain::invoice::Invoice::try_from(v).unwrap())
-                    .collect::<Vec<_>>()
-            })
-            .map_err(CoreError::from);
+                    .map(|v| domain::invoice::Invoice::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
 
         if let Err(e) = result {
             for id in supplier_ids {
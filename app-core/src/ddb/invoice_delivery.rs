@@ -0,0 +1,93 @@
+use crate::ddb::invoice;
+use crate::ddb::schema::invoice_deliveries;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use diesel::prelude::*;
+use std::convert::TryFrom;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(invoice::Entity, foreign_key = "invoice_id")]
+#[table_name = "invoice_deliveries"]
+pub struct Entity {
+    pub id: String,
+    pub invoice_id: String,
+    pub sent_to: String,
+    pub cc: String,
+    pub bcc: String,
+    pub subject: String,
+    pub pdf_path: String,
+    pub sent_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::invoice_delivery::InvoiceDelivery {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::invoice_delivery::InvoiceDelivery {
+            id: e.id,
+            invoice_id: e.invoice_id,
+            sent_to: e.sent_to,
+            cc: domain::invoice_delivery::split_addresses(e.cc.as_str()),
+            bcc: domain::invoice_delivery::split_addresses(e.bcc.as_str()),
+            subject: e.subject,
+            pdf_path: e.pdf_path,
+            sent_at: e.sent_at,
+        })
+    }
+}
+
+impl From<domain::invoice_delivery::InvoiceDelivery> for Entity {
+    fn from(d: domain::invoice_delivery::InvoiceDelivery) -> Entity {
+        Entity {
+            id: d.id,
+            invoice_id: d.invoice_id,
+            sent_to: d.sent_to,
+            cc: d.cc.join(","),
+            bcc: d.bcc.join(","),
+            subject: d.subject,
+            pdf_path: d.pdf_path,
+            sent_at: d.sent_at,
+        }
+    }
+}
+
+impl Dao<domain::invoice_delivery::InvoiceDelivery> {
+    pub fn get_all_by_invoice(
+        &self,
+        conn: &MysqlConnection,
+        invoice_id: String,
+    ) -> CoreResult<Vec<domain::invoice_delivery::InvoiceDelivery>> {
+        return invoice_deliveries::table
+            .filter(invoice_deliveries::invoice_id.eq(invoice_id))
+            .order(invoice_deliveries::sent_at.asc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::invoice_delivery::InvoiceDelivery::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::invoice_delivery::InvoiceDelivery,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(invoice_deliveries::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::ddb::schema::invoice_number_sequences;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use std::convert::TryFrom;
+
+#[derive(Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, AsChangeset)]
+#[primary_key(user_id, year)]
+#[table_name = "invoice_number_sequences"]
+pub struct Entity {
+    pub user_id: String,
+    pub year: i32,
+    pub last_number: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::invoice_number::InvoiceNumberSequence {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::invoice_number::InvoiceNumberSequence {
+            user_id: e.user_id,
+            year: e.year as u32,
+            last_number: e.last_number,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::invoice_number::InvoiceNumberSequence> for Entity {
+    fn from(d: domain::invoice_number::InvoiceNumberSequence) -> Entity {
+        Entity {
+            user_id: d.user_id,
+            year: d.year as i32,
+            last_number: d.last_number,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::invoice_number::InvoiceNumberSequence> {
+    /// 行をロックして次の連番を採番する。外部APIの呼び出し中にロックを持ち続けず、取り消した番号を再利用しないよう、
+    /// 請求書の登録とは別の短いトランザクションで呼び出して確定させる。使わなかった番号は欠番として記録する
+    pub fn next(
+        &self,
+        conn: &MysqlConnection,
+        user_id: String,
+        year: u32,
+        now: DateTime<Utc>,
+    ) -> CoreResult<i32> {
+        let current = invoice_number_sequences::table
+            .find((user_id.clone(), year as i32))
+            .for_update()
+            .first::<Entity>(conn)
+            .optional()
+            .map_err(CoreError::from)?;
+
+        let mut sequence = match current {
+            Some(v) => domain::invoice_number::InvoiceNumberSequence::try_from(v)?,
+            None => {
+                let sequence =
+                    domain::invoice_number::InvoiceNumberSequence::new(user_id, year, now);
+                let e: Entity = sequence.clone().into();
+                diesel::insert_into(invoice_number_sequences::table)
+                    .values(e)
+                    .execute(conn)
+                    .map_err(CoreError::from)?;
+                sequence
+            }
+        };
+
+        let number = sequence.next(now);
+        let e: Entity = sequence.into();
+        if let Err(e) =
+            diesel::update(invoice_number_sequences::table.find((e.user_id.clone(), e.year)))
+                .set(&e)
+                .execute(conn)
+                .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(number)
+    }
+}
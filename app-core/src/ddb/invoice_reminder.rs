@@ -0,0 +1,82 @@
+use crate::ddb::invoice;
+use crate::ddb::schema::invoice_reminders;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use diesel::prelude::*;
+use std::convert::TryFrom;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(invoice::Entity, foreign_key = "invoice_id")]
+#[table_name = "invoice_reminders"]
+pub struct Entity {
+    pub id: String,
+    pub invoice_id: String,
+    pub offset_days: i32,
+    pub sent_to: String,
+    pub subject: String,
+    pub sent_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::reminder::InvoiceReminder {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::reminder::InvoiceReminder {
+            id: e.id,
+            invoice_id: e.invoice_id,
+            offset_days: e.offset_days as i64,
+            sent_to: e.sent_to,
+            subject: e.subject,
+            sent_at: e.sent_at,
+        })
+    }
+}
+
+impl From<domain::reminder::InvoiceReminder> for Entity {
+    fn from(d: domain::reminder::InvoiceReminder) -> Entity {
+        Entity {
+            id: d.id,
+            invoice_id: d.invoice_id,
+            offset_days: d.offset_days as i32,
+            sent_to: d.sent_to,
+            subject: d.subject,
+            sent_at: d.sent_at,
+        }
+    }
+}
+
+impl Dao<domain::reminder::InvoiceReminder> {
+    pub fn exists(
+        &self,
+        conn: &MysqlConnection,
+        invoice_id: String,
+        offset_days: i64,
+    ) -> CoreResult<bool> {
+        invoice_reminders::table
+            .filter(invoice_reminders::invoice_id.eq(invoice_id))
+            .filter(invoice_reminders::offset_days.eq(offset_days as i32))
+            .count()
+            .get_result::<i64>(conn)
+            .map(|v| v > 0)
+            .map_err(CoreError::from)
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::reminder::InvoiceReminder,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(invoice_reminders::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::ddb::invoice;
+use crate::ddb::schema::payments;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use bigdecimal::BigDecimal;
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(invoice::Entity, foreign_key = "invoice_id")]
+#[table_name = "payments"]
+pub struct Entity {
+    pub id: String,
+    pub invoice_id: String,
+    pub received_ymd: String,
+    pub amount: BigDecimal,
+    pub method: i32,
+    pub memo: String,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::payment::Payment {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::payment::Payment {
+            id: e.id,
+            invoice_id: e.invoice_id,
+            received_ymd: domain::YMD::from_str(e.received_ymd.as_str())
+                .map_err(|_e| "parse ymd error".to_string())?,
+            amount: domain::money::Money::try_from(e.amount)?,
+            method: domain::payment::PaymentMethod::from(e.method),
+            memo: e.memo,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::payment::Payment> for Entity {
+    fn from(d: domain::payment::Payment) -> Entity {
+        Entity {
+            id: d.id,
+            invoice_id: d.invoice_id,
+            received_ymd: d.received_ymd.to_string(),
+            amount: d.amount.into(),
+            method: d.method.int(),
+            memo: d.memo,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::payment::Payment> {
+    pub fn get_all_by_invoice(
+        &self,
+        conn: &MysqlConnection,
+        invoice_id: String,
+    ) -> CoreResult<Vec<domain::payment::Payment>> {
+        return payments::table
+            .filter(payments::invoice_id.eq(invoice_id))
+            .order((payments::received_ymd.asc(), payments::created_at.asc()))
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| domain::payment::Payment::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get(&self, conn: &MysqlConnection, id: String) -> CoreResult<domain::payment::Payment> {
+        payments::table
+            .find(id)
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::payment::Payment::try_from(v).map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::payment::Payment,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(payments::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete(&self, conn: &MysqlConnection, id: String) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(payments::table.find(id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
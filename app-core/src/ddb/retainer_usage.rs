@@ -0,0 +1,135 @@
+use crate::ddb::schema::retainer_usages;
+use crate::ddb::supplier;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(supplier::Entity, foreign_key = "supplier_id")]
+#[table_name = "retainer_usages"]
+pub struct Entity {
+    pub id: String,
+    pub supplier_id: String,
+    pub ym: String,
+    pub quantity: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::retainer_usage::RetainerUsage {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::retainer_usage::RetainerUsage {
+            id: e.id,
+            supplier_id: e.supplier_id,
+            ym: domain::YM::from_str(e.ym.as_str()).map_err(|_e| "parse ym error".to_string())?,
+            quantity: e.quantity,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::retainer_usage::RetainerUsage> for Entity {
+    fn from(d: domain::retainer_usage::RetainerUsage) -> Entity {
+        Entity {
+            id: d.id,
+            supplier_id: d.supplier_id,
+            ym: d.ym.to_string(),
+            quantity: d.quantity,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::retainer_usage::RetainerUsage> {
+    pub fn get_all_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<Vec<domain::retainer_usage::RetainerUsage>> {
+        return retainer_usages::table
+            .filter(retainer_usages::supplier_id.eq(supplier_id))
+            .order(retainer_usages::ym.desc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::retainer_usage::RetainerUsage::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get_by_supplier_and_ym(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+        ym: &domain::YM,
+    ) -> CoreResult<domain::retainer_usage::RetainerUsage> {
+        retainer_usages::table
+            .filter(retainer_usages::supplier_id.eq(supplier_id))
+            .filter(retainer_usages::ym.eq(ym.to_string()))
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::retainer_usage::RetainerUsage::try_from(v).map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::retainer_usage::RetainerUsage,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(retainer_usages::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn update(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::retainer_usage::RetainerUsage,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::update(retainer_usages::table.find(e.id.clone()))
+            .set(&e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(retainer_usages::table)
+            .filter(retainer_usages::supplier_id.eq(supplier_id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
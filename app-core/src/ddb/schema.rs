@@ -2,6 +2,12 @@ table! {
     users (id) {
         id -> Varchar,
         misoca_refresh_token -> Varchar,
+        invoice_number_format -> Varchar,
+        reminder_schedule -> Varchar,
+        reminder_subject_template -> Varchar,
+        reminder_body_template -> Text,
+        invoice_mail_subject_template -> Varchar,
+        invoice_mail_body_template -> Text,
         created_at -> Datetime,
         updated_at -> Datetime,
     }
@@ -14,11 +20,42 @@ table! {
         contact_id -> Varchar,
         contact_group_id -> Varchar,
         name -> Varchar,
-        billing_amount -> Integer,
+        billing_amount -> Numeric,
+        currency -> Integer,
         billing_type -> Integer,
         end_ym -> Varchar,
         subject -> Varchar,
         subject_template -> Varchar,
+        remarks_template -> Text,
+        invoice_number_prefix -> Varchar,
+        billing_email -> Varchar,
+        reminder_schedule -> Varchar,
+        billing_cc -> Varchar,
+        billing_bcc -> Varchar,
+        auto_send_invoice -> Bool,
+        closing_day -> Integer,
+        payment_term_type -> Integer,
+        payment_term_months -> Integer,
+        payment_term_day -> Integer,
+        business_day_adjustment -> Integer,
+        settlement_lower_hours -> Integer,
+        settlement_upper_hours -> Integer,
+        excess_hourly_rate -> Numeric,
+        deduction_hourly_rate -> Numeric,
+        retainer_included_quantity -> Integer,
+        retainer_unit -> Varchar,
+        retainer_overage_rate -> Numeric,
+        interval_months -> Integer,
+        start_ym -> Varchar,
+        contract_end_ym -> Varchar,
+        contract_start_date -> Varchar,
+        contract_end_date -> Varchar,
+        proration_basis -> Integer,
+        tax_type -> Integer,
+        tax_inclusive -> Bool,
+        tax_rounding -> Integer,
+        withholding_tax -> Bool,
+        archived -> Bool,
         created_at -> Datetime,
         updated_at -> Datetime,
     }
@@ -35,12 +72,29 @@ table! {
         payment_due_on_at -> Nullable<Datetime>,
         invoice_number -> Varchar,
         payment_status -> Integer,
+        remote_payment_status -> Integer,
         invoice_status -> Integer,
         recipient_name -> Varchar,
         subject -> Varchar,
-        total_amount -> Integer,
-        tax -> Integer,
+        billing_ym -> Varchar,
+        currency -> Integer,
+        total_amount -> Numeric,
+        tax -> Numeric,
+        withholding_tax -> Numeric,
+        payment_amount -> Numeric,
+        paid_amount -> Numeric,
+        standard_taxable_amount -> Numeric,
+        standard_tax -> Numeric,
+        reduced_taxable_amount -> Numeric,
+        reduced_tax -> Numeric,
+        non_taxable_amount -> Numeric,
+        remarks -> Text,
         pdf_path -> Nullable<Varchar>,
+        overdue_at -> Nullable<Datetime>,
+        cancelled_at -> Nullable<Datetime>,
+        cancel_reason -> Varchar,
+        credit_note_id -> Nullable<Varchar>,
+        original_invoice_id -> Nullable<Varchar>,
         created_at -> Datetime,
         updated_at -> Datetime,
     }
@@ -70,10 +124,215 @@ table! {
         tel -> Varchar,
         postal_code -> Varchar,
         address -> Varchar,
+        registration_number -> Varchar,
+        remarks_template -> Text,
         created_at -> Datetime,
         updated_at -> Datetime,
     }
 }
 joinable!(senders -> users (user_id));
 
-allow_tables_to_appear_in_same_query!(users, suppliers, invoices, banks, senders);
+table! {
+    working_hours (id) {
+        id -> Varchar,
+        supplier_id -> Varchar,
+        ym -> Varchar,
+        minutes -> Integer,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(working_hours -> suppliers (supplier_id));
+
+table! {
+    retainer_usages (id) {
+        id -> Varchar,
+        supplier_id -> Varchar,
+        ym -> Varchar,
+        quantity -> Integer,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(retainer_usages -> suppliers (supplier_id));
+
+table! {
+    supplier_items (id) {
+        id -> Varchar,
+        supplier_id -> Varchar,
+        name -> Varchar,
+        quantity -> Integer,
+        unit -> Varchar,
+        unit_price -> Numeric,
+        tax_type -> Integer,
+        withholding -> Bool,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(supplier_items -> suppliers (supplier_id));
+
+table! {
+    supplier_suspensions (id) {
+        id -> Varchar,
+        supplier_id -> Varchar,
+        from_ym -> Varchar,
+        to_ym -> Varchar,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(supplier_suspensions -> suppliers (supplier_id));
+
+table! {
+    supplier_prices (id) {
+        id -> Varchar,
+        supplier_id -> Varchar,
+        effective_from -> Varchar,
+        billing_amount -> Numeric,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(supplier_prices -> suppliers (supplier_id));
+
+table! {
+    supplier_milestones (id) {
+        id -> Varchar,
+        supplier_id -> Varchar,
+        name -> Varchar,
+        position -> Integer,
+        amount_type -> Integer,
+        amount_value -> Numeric,
+        target_ym -> Varchar,
+        invoice_id -> Nullable<Varchar>,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(supplier_milestones -> suppliers (supplier_id));
+
+table! {
+    expenses (id) {
+        id -> Varchar,
+        supplier_id -> Varchar,
+        ymd -> Varchar,
+        description -> Varchar,
+        amount -> Numeric,
+        tax_type -> Integer,
+        receipt_path -> Nullable<Varchar>,
+        invoice_id -> Nullable<Varchar>,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(expenses -> suppliers (supplier_id));
+
+table! {
+    supplier_adjustments (id) {
+        id -> Varchar,
+        supplier_id -> Varchar,
+        description -> Varchar,
+        amount -> Numeric,
+        tax_type -> Integer,
+        target_ym -> Varchar,
+        invoice_id -> Nullable<Varchar>,
+        cancelled -> Bool,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(supplier_adjustments -> suppliers (supplier_id));
+
+table! {
+    invoice_number_sequences (user_id, year) {
+        user_id -> Varchar,
+        year -> Integer,
+        last_number -> Integer,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(invoice_number_sequences -> users (user_id));
+
+table! {
+    voided_invoice_numbers (user_id, invoice_number) {
+        user_id -> Varchar,
+        invoice_number -> Varchar,
+        reason -> Text,
+        created_at -> Datetime,
+    }
+}
+joinable!(voided_invoice_numbers -> users (user_id));
+
+table! {
+    exchange_rates (currency, ymd) {
+        currency -> Integer,
+        ymd -> Varchar,
+        rate -> Integer,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+
+table! {
+    payments (id) {
+        id -> Varchar,
+        invoice_id -> Varchar,
+        received_ymd -> Varchar,
+        amount -> Numeric,
+        method -> Integer,
+        memo -> Varchar,
+        created_at -> Datetime,
+        updated_at -> Datetime,
+    }
+}
+joinable!(payments -> invoices (invoice_id));
+
+table! {
+    invoice_reminders (id) {
+        id -> Varchar,
+        invoice_id -> Varchar,
+        offset_days -> Integer,
+        sent_to -> Varchar,
+        subject -> Varchar,
+        sent_at -> Datetime,
+    }
+}
+joinable!(invoice_reminders -> invoices (invoice_id));
+
+table! {
+    invoice_deliveries (id) {
+        id -> Varchar,
+        invoice_id -> Varchar,
+        sent_to -> Varchar,
+        cc -> Varchar,
+        bcc -> Varchar,
+        subject -> Varchar,
+        pdf_path -> Varchar,
+        sent_at -> Datetime,
+    }
+}
+joinable!(invoice_deliveries -> invoices (invoice_id));
+
+allow_tables_to_appear_in_same_query!(
+    users,
+    suppliers,
+    invoices,
+    banks,
+    senders,
+    working_hours,
+    retainer_usages,
+    supplier_items,
+    supplier_suspensions,
+    supplier_prices,
+    supplier_milestones,
+    expenses,
+    supplier_adjustments,
+    invoice_number_sequences,
+    voided_invoice_numbers,
+    exchange_rates,
+    payments,
+    invoice_reminders,
+    invoice_deliveries
+);
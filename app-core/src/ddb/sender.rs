@@ -19,6 +19,8 @@ pub struct Entity {
     pub tel: String,
     pub postal_code: String,
     pub address: String,
+    pub registration_number: String,
+    pub remarks_template: String,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -35,6 +37,8 @@ impl TryFrom<Entity> for domain::sender::Sender {
             tel: e.tel,
             postal_code: e.postal_code,
             address: e.address,
+            registration_number: e.registration_number,
+            remarks_template: e.remarks_template,
             created_at: e.created_at,
             updated_at: e.updated_at,
         })
@@ -51,6 +55,8 @@ impl From<domain::sender::Sender> for Entity {
             tel: d.tel,
             postal_code: d.postal_code,
             address: d.address,
+            registration_number: d.registration_number,
+            remarks_template: d.remarks_template,
             created_at: d.created_at,
             updated_at: d.updated_at,
         }
@@ -67,20 +73,20 @@ impl Dao<domain::sender::Sender> {
             .filter(senders::user_id.eq(user_id))
             .order(senders::created_at.desc())
             .load::<Entity>(conn)
-            .map(|v: Vec<Entity>| {
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
                 v.into_iter()
-                    .map(|v| domain::sender::Sender::try_from(v).unwrap())
-                    .collect::<Vec<_>>()
-            })
-            .map_err(CoreError::from);
+                    .map(|v| domain::sender::Sender::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
     }
 
     pub fn get(&self, conn: &MysqlConnection, id: String) -> CoreResult<domain::sender::Sender> {
         senders::table
             .find(id)
             .first(conn)
-            .map(|v: Entity| domain::sender::Sender::try_from(v).unwrap())
             .map_err(CoreError::from)
+            .and_then(|v: Entity| domain::sender::Sender::try_from(v).map_err(CoreError::Internal))
     }
 
     pub fn insert(&self, conn: &MysqlConnection, item: &domain::sender::Sender) -> CoreResult<()> {
@@ -5,6 +5,7 @@ use crate::ddb::user;
 use crate::ddb::Dao;
 use crate::domain;
 use crate::{CoreError, CoreResult};
+use bigdecimal::BigDecimal;
 use diesel::prelude::*;
 use std::convert::TryFrom;
 use std::str::FromStr;
@@ -20,11 +21,42 @@ pub struct Entity {
     pub contact_id: String,
     pub contact_group_id: String,
     pub name: String,
-    pub billing_amount: i32,
+    pub billing_amount: BigDecimal,
+    pub currency: i32,
     pub billing_type: i32,
     pub end_ym: String,
     pub subject: String,
     pub subject_template: String,
+    pub remarks_template: String,
+    pub invoice_number_prefix: String,
+    pub billing_email: String,
+    pub reminder_schedule: String,
+    pub billing_cc: String,
+    pub billing_bcc: String,
+    pub auto_send_invoice: bool,
+    pub closing_day: i32,
+    pub payment_term_type: i32,
+    pub payment_term_months: i32,
+    pub payment_term_day: i32,
+    pub business_day_adjustment: i32,
+    pub settlement_lower_hours: i32,
+    pub settlement_upper_hours: i32,
+    pub excess_hourly_rate: BigDecimal,
+    pub deduction_hourly_rate: BigDecimal,
+    pub retainer_included_quantity: i32,
+    pub retainer_unit: String,
+    pub retainer_overage_rate: BigDecimal,
+    pub interval_months: i32,
+    pub start_ym: String,
+    pub contract_end_ym: String,
+    pub contract_start_date: String,
+    pub contract_end_date: String,
+    pub proration_basis: i32,
+    pub tax_type: i32,
+    pub tax_inclusive: bool,
+    pub tax_rounding: i32,
+    pub withholding_tax: bool,
+    pub archived: bool,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -39,12 +71,63 @@ impl TryFrom<Entity> for domain::supplier::Supplier {
             contact_id: e.contact_id,
             contact_group_id: e.contact_group_id,
             name: e.name,
-            billing_amount: e.billing_amount,
+            billing_amount: domain::money::Money::try_from(e.billing_amount)?,
+            currency: domain::currency::Currency::from(e.currency),
             billing_type: domain::supplier::BillingType::from(e.billing_type),
             end_ym: domain::YM::from_str(e.end_ym.as_str())
                 .map_err(|_e| "parse ym error".to_string())?,
             subject: e.subject,
             subject_template: e.subject_template,
+            remarks_template: e.remarks_template,
+            invoice_number_prefix: e.invoice_number_prefix,
+            billing_email: e.billing_email,
+            reminder_schedule: e.reminder_schedule,
+            delivery: domain::invoice_delivery::DeliverySetting {
+                cc: domain::invoice_delivery::split_addresses(e.billing_cc.as_str()),
+                bcc: domain::invoice_delivery::split_addresses(e.billing_bcc.as_str()),
+                auto_send: e.auto_send_invoice,
+            },
+            closing_day: domain::supplier::ClosingDay::from(e.closing_day),
+            payment_term: domain::supplier::PaymentTerm {
+                term_type: domain::supplier::PaymentTermType::from(e.payment_term_type),
+                months: e.payment_term_months as u32,
+                day: e.payment_term_day as u32,
+            },
+            business_day_adjustment: domain::calendar::BusinessDayAdjustment::from(
+                e.business_day_adjustment,
+            ),
+            settlement_range: domain::supplier::SettlementRange {
+                lower_hours: e.settlement_lower_hours,
+                upper_hours: e.settlement_upper_hours,
+                excess_rate: domain::money::Money::try_from(e.excess_hourly_rate)?,
+                deduction_rate: domain::money::Money::try_from(e.deduction_hourly_rate)?,
+            },
+            retainer: domain::supplier::RetainerAllowance {
+                included_quantity: e.retainer_included_quantity,
+                unit: e.retainer_unit,
+                overage_rate: domain::money::Money::try_from(e.retainer_overage_rate)?,
+            },
+            recurrence: domain::supplier::Recurrence {
+                interval_months: e.interval_months as u32,
+                start_ym: domain::YM::from_str(e.start_ym.as_str())
+                    .map_err(|_e| "parse ym error".to_string())?,
+                end_ym: domain::YM::from_str(e.contract_end_ym.as_str())
+                    .map_err(|_e| "parse ym error".to_string())?,
+            },
+            proration: domain::supplier::Proration {
+                basis: domain::supplier::ProrationBasis::from(e.proration_basis),
+                start_date: domain::YMD::from_str(e.contract_start_date.as_str())
+                    .map_err(|_e| "parse ymd error".to_string())?,
+                end_date: domain::YMD::from_str(e.contract_end_date.as_str())
+                    .map_err(|_e| "parse ymd error".to_string())?,
+            },
+            tax_setting: domain::invoice::TaxSetting {
+                tax_type: domain::invoice::TaxType::from(e.tax_type),
+                inclusive: e.tax_inclusive,
+                rounding: domain::invoice::TaxRounding::from(e.tax_rounding),
+            },
+            withholding_tax: e.withholding_tax,
+            archived: e.archived,
             created_at: e.created_at,
             updated_at: e.updated_at,
         })
@@ -59,11 +142,42 @@ impl From<domain::supplier::Supplier> for Entity {
             contact_id: d.contact_id,
             contact_group_id: d.contact_group_id,
             name: d.name,
-            billing_amount: d.billing_amount,
+            billing_amount: d.billing_amount.into(),
+            currency: d.currency.int(),
             billing_type: d.billing_type.int(),
             end_ym: d.end_ym.to_string(),
             subject: d.subject,
             subject_template: d.subject_template,
+            remarks_template: d.remarks_template,
+            invoice_number_prefix: d.invoice_number_prefix,
+            billing_email: d.billing_email,
+            reminder_schedule: d.reminder_schedule,
+            billing_cc: d.delivery.cc.join(","),
+            billing_bcc: d.delivery.bcc.join(","),
+            auto_send_invoice: d.delivery.auto_send,
+            closing_day: d.closing_day.int(),
+            payment_term_type: d.payment_term.term_type.int(),
+            payment_term_months: d.payment_term.months as i32,
+            payment_term_day: d.payment_term.day as i32,
+            business_day_adjustment: d.business_day_adjustment.int(),
+            settlement_lower_hours: d.settlement_range.lower_hours,
+            settlement_upper_hours: d.settlement_range.upper_hours,
+            excess_hourly_rate: d.settlement_range.excess_rate.into(),
+            deduction_hourly_rate: d.settlement_range.deduction_rate.into(),
+            retainer_included_quantity: d.retainer.included_quantity,
+            retainer_unit: d.retainer.unit,
+            retainer_overage_rate: d.retainer.overage_rate.into(),
+            interval_months: d.recurrence.interval_months as i32,
+            start_ym: d.recurrence.start_ym.to_string(),
+            contract_end_ym: d.recurrence.end_ym.to_string(),
+            contract_start_date: d.proration.start_date.to_string(),
+            contract_end_date: d.proration.end_date.to_string(),
+            proration_basis: d.proration.basis.int(),
+            tax_type: d.tax_setting.tax_type.int(),
+            tax_inclusive: d.tax_setting.inclusive,
+            tax_rounding: d.tax_setting.rounding.int(),
+            withholding_tax: d.withholding_tax,
+            archived: d.archived,
             created_at: d.created_at,
             updated_at: d.updated_at,
         }
@@ -80,12 +194,12 @@ impl Dao<domain::supplier::Supplier> {
             .filter(suppliers::user_id.eq(user_id))
             .order(suppliers::created_at.desc())
             .load::<Entity>(conn)
-            .map(|v: Vec<Entity>| {
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
                 v.into_iter()
-                    .map(|v| domain::supplier::Supplier::try_from(v).unwrap())
-                    .collect::<Vec<_>>()
-            })
-            .map_err(CoreError::from);
+                    .map(|v| domain::supplier::Supplier::try_from(v).map_err(CoreError::Internal))
+                    .collect::<CoreResult<Vec<_>>>()
+            });
     }
 
     pub fn get_all_by_user_with_invoices(
@@ -112,19 +226,19 @@ impl Dao<domain::supplier::Supplier> {
                 .zip(invoice_entities.grouped_by(&supplier_entities))
                 .collect::<Vec<_>>();
 
-        Ok(supplier_entities_with_invoices
+        supplier_entities_with_invoices
             .into_iter()
-            .map(
-                |v: (Entity, Vec<invoice::Entity>)| domain::supplier::SupplierWithInvoices {
-                    supplier: domain::supplier::Supplier::try_from(v.0).unwrap(),
+            .map(|v: (Entity, Vec<invoice::Entity>)| -> CoreResult<_> {
+                Ok(domain::supplier::SupplierWithInvoices {
+                    supplier: domain::supplier::Supplier::try_from(v.0)?,
                     invoices: v
                         .1
                         .into_iter()
-                        .map(|v| domain::invoice::Invoice::try_from(v).unwrap())
-                        .collect::<Vec<_>>(),
-                },
-            )
-            .collect::<Vec<_>>())
+                        .map(|v| domain::invoice::Invoice::try_from(v).map_err(CoreError::Internal))
+                        .collect::<CoreResult<Vec<_>>>()?,
+                })
+            })
+            .collect::<CoreResult<Vec<_>>>()
     }
 
     pub fn get(
@@ -135,8 +249,10 @@ impl Dao<domain::supplier::Supplier> {
         suppliers::table
             .find(id)
             .first(conn)
-            .map(|v: Entity| domain::supplier::Supplier::try_from(v).unwrap())
             .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::supplier::Supplier::try_from(v).map_err(CoreError::Internal)
+            })
     }
 
     pub fn insert(
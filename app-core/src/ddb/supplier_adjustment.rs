@@ -0,0 +1,177 @@
+use crate::ddb::schema::supplier_adjustments;
+use crate::ddb::supplier;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use bigdecimal::BigDecimal;
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(supplier::Entity, foreign_key = "supplier_id")]
+#[table_name = "supplier_adjustments"]
+pub struct Entity {
+    pub id: String,
+    pub supplier_id: String,
+    pub description: String,
+    pub amount: BigDecimal,
+    pub tax_type: i32,
+    pub target_ym: String,
+    pub invoice_id: Option<String>,
+    pub cancelled: bool,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::supplier_adjustment::SupplierAdjustment {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::supplier_adjustment::SupplierAdjustment {
+            id: e.id,
+            supplier_id: e.supplier_id,
+            description: e.description,
+            amount: domain::money::Money::try_from(e.amount)?,
+            tax_type: domain::invoice::TaxType::from(e.tax_type),
+            target_ym: domain::YM::from_str(e.target_ym.as_str())
+                .map_err(|_e| "parse ym error".to_string())?,
+            invoice_id: e.invoice_id,
+            cancelled: e.cancelled,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::supplier_adjustment::SupplierAdjustment> for Entity {
+    fn from(d: domain::supplier_adjustment::SupplierAdjustment) -> Entity {
+        Entity {
+            id: d.id,
+            supplier_id: d.supplier_id,
+            description: d.description,
+            amount: d.amount.into(),
+            tax_type: d.tax_type.int(),
+            target_ym: d.target_ym.to_string(),
+            invoice_id: d.invoice_id,
+            cancelled: d.cancelled,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::supplier_adjustment::SupplierAdjustment> {
+    pub fn get_all_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<Vec<domain::supplier_adjustment::SupplierAdjustment>> {
+        return supplier_adjustments::table
+            .filter(supplier_adjustments::supplier_id.eq(supplier_id))
+            .order(supplier_adjustments::created_at.desc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::supplier_adjustment::SupplierAdjustment::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    /// ymを締め月とする請求で適用する、未適用かつ取り消されていない調整を登録順に取得する
+    pub fn get_pending_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+        ym: &domain::YM,
+    ) -> CoreResult<Vec<domain::supplier_adjustment::SupplierAdjustment>> {
+        return supplier_adjustments::table
+            .filter(supplier_adjustments::supplier_id.eq(supplier_id))
+            .filter(supplier_adjustments::invoice_id.is_null())
+            .filter(supplier_adjustments::cancelled.eq(false))
+            .filter(
+                supplier_adjustments::target_ym
+                    .eq("")
+                    .or(supplier_adjustments::target_ym.le(ym.to_string())),
+            )
+            .order(supplier_adjustments::created_at.asc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::supplier_adjustment::SupplierAdjustment::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get(
+        &self,
+        conn: &MysqlConnection,
+        id: String,
+    ) -> CoreResult<domain::supplier_adjustment::SupplierAdjustment> {
+        supplier_adjustments::table
+            .find(id)
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::supplier_adjustment::SupplierAdjustment::try_from(v)
+                    .map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_adjustment::SupplierAdjustment,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(supplier_adjustments::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn update(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_adjustment::SupplierAdjustment,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::update(supplier_adjustments::table.find(e.id.clone()))
+            .set(&e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_adjustments::table)
+            .filter(supplier_adjustments::supplier_id.eq(supplier_id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use crate::ddb::schema::supplier_items;
+use crate::ddb::supplier;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use bigdecimal::BigDecimal;
+use diesel::prelude::*;
+use std::convert::TryFrom;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(supplier::Entity, foreign_key = "supplier_id")]
+#[table_name = "supplier_items"]
+pub struct Entity {
+    pub id: String,
+    pub supplier_id: String,
+    pub name: String,
+    pub quantity: i32,
+    pub unit: String,
+    pub unit_price: BigDecimal,
+    pub tax_type: i32,
+    pub withholding: bool,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::supplier_item::SupplierItem {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::supplier_item::SupplierItem {
+            id: e.id,
+            supplier_id: e.supplier_id,
+            name: e.name,
+            quantity: e.quantity,
+            unit: e.unit,
+            unit_price: domain::money::Money::try_from(e.unit_price)?,
+            tax_type: domain::invoice::TaxType::from(e.tax_type),
+            withholding: e.withholding,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::supplier_item::SupplierItem> for Entity {
+    fn from(d: domain::supplier_item::SupplierItem) -> Entity {
+        Entity {
+            id: d.id,
+            supplier_id: d.supplier_id,
+            name: d.name,
+            quantity: d.quantity,
+            unit: d.unit,
+            unit_price: d.unit_price.into(),
+            tax_type: d.tax_type.int(),
+            withholding: d.withholding,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::supplier_item::SupplierItem> {
+    pub fn get_all_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<Vec<domain::supplier_item::SupplierItem>> {
+        return supplier_items::table
+            .filter(supplier_items::supplier_id.eq(supplier_id))
+            .order(supplier_items::created_at.asc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::supplier_item::SupplierItem::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get(
+        &self,
+        conn: &MysqlConnection,
+        id: String,
+    ) -> CoreResult<domain::supplier_item::SupplierItem> {
+        supplier_items::table
+            .find(id)
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::supplier_item::SupplierItem::try_from(v).map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_item::SupplierItem,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(supplier_items::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn update(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_item::SupplierItem,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::update(supplier_items::table.find(e.id.clone()))
+            .set(&e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete(&self, conn: &MysqlConnection, id: String) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_items::table.find(id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_items::table)
+            .filter(supplier_items::supplier_id.eq(supplier_id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::ddb::schema::supplier_milestones;
+use crate::ddb::supplier;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use bigdecimal::BigDecimal;
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(supplier::Entity, foreign_key = "supplier_id")]
+#[table_name = "supplier_milestones"]
+pub struct Entity {
+    pub id: String,
+    pub supplier_id: String,
+    pub name: String,
+    pub position: i32,
+    pub amount_type: i32,
+    pub amount_value: BigDecimal,
+    pub target_ym: String,
+    pub invoice_id: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::supplier_milestone::SupplierMilestone {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::supplier_milestone::SupplierMilestone {
+            id: e.id,
+            supplier_id: e.supplier_id,
+            name: e.name,
+            position: e.position,
+            amount_type: domain::supplier_milestone::MilestoneAmountType::from(e.amount_type),
+            amount_value: domain::money::Money::try_from(e.amount_value)?,
+            target_ym: domain::YM::from_str(e.target_ym.as_str())
+                .map_err(|_e| "parse ym error".to_string())?,
+            invoice_id: e.invoice_id,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::supplier_milestone::SupplierMilestone> for Entity {
+    fn from(d: domain::supplier_milestone::SupplierMilestone) -> Entity {
+        Entity {
+            id: d.id,
+            supplier_id: d.supplier_id,
+            name: d.name,
+            position: d.position,
+            amount_type: d.amount_type.int(),
+            amount_value: d.amount_value.into(),
+            target_ym: d.target_ym.to_string(),
+            invoice_id: d.invoice_id,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::supplier_milestone::SupplierMilestone> {
+    pub fn get_all_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<Vec<domain::supplier_milestone::SupplierMilestone>> {
+        return supplier_milestones::table
+            .filter(supplier_milestones::supplier_id.eq(supplier_id))
+            .order((
+                supplier_milestones::position.asc(),
+                supplier_milestones::created_at.asc(),
+            ))
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::supplier_milestone::SupplierMilestone::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get(
+        &self,
+        conn: &MysqlConnection,
+        id: String,
+    ) -> CoreResult<domain::supplier_milestone::SupplierMilestone> {
+        supplier_milestones::table
+            .find(id)
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::supplier_milestone::SupplierMilestone::try_from(v)
+                    .map_err(CoreError::Internal)
+            })
+    }
+
+    /// 行をロックして読み込む。同じマイルストーンを同時に請求しないよう、トランザクションの中で請求済みか確かめるのに使う
+    pub fn get_for_update(
+        &self,
+        conn: &MysqlConnection,
+        id: String,
+    ) -> CoreResult<domain::supplier_milestone::SupplierMilestone> {
+        supplier_milestones::table
+            .find(id)
+            .for_update()
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::supplier_milestone::SupplierMilestone::try_from(v)
+                    .map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_milestone::SupplierMilestone,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(supplier_milestones::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn update(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_milestone::SupplierMilestone,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::update(supplier_milestones::table.find(e.id.clone()))
+            .set(&e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete(&self, conn: &MysqlConnection, id: String) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_milestones::table.find(id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_milestones::table)
+            .filter(supplier_milestones::supplier_id.eq(supplier_id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
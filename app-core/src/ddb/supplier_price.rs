@@ -0,0 +1,161 @@
+use crate::ddb::schema::supplier_prices;
+use crate::ddb::supplier;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use bigdecimal::BigDecimal;
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(supplier::Entity, foreign_key = "supplier_id")]
+#[table_name = "supplier_prices"]
+pub struct Entity {
+    pub id: String,
+    pub supplier_id: String,
+    pub effective_from: String,
+    pub billing_amount: BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::supplier_price::SupplierPrice {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::supplier_price::SupplierPrice {
+            id: e.id,
+            supplier_id: e.supplier_id,
+            effective_from: domain::YM::from_str(e.effective_from.as_str())
+                .map_err(|_e| "parse ym error".to_string())?,
+            billing_amount: domain::money::Money::try_from(e.billing_amount)?,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::supplier_price::SupplierPrice> for Entity {
+    fn from(d: domain::supplier_price::SupplierPrice) -> Entity {
+        Entity {
+            id: d.id,
+            supplier_id: d.supplier_id,
+            effective_from: d.effective_from.to_string(),
+            billing_amount: d.billing_amount.into(),
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::supplier_price::SupplierPrice> {
+    pub fn get_all_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<Vec<domain::supplier_price::SupplierPrice>> {
+        return supplier_prices::table
+            .filter(supplier_prices::supplier_id.eq(supplier_id))
+            .order(supplier_prices::effective_from.asc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::supplier_price::SupplierPrice::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get(
+        &self,
+        conn: &MysqlConnection,
+        id: String,
+    ) -> CoreResult<domain::supplier_price::SupplierPrice> {
+        supplier_prices::table
+            .find(id)
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::supplier_price::SupplierPrice::try_from(v).map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn get_by_supplier_and_effective_from(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+        effective_from: &domain::YM,
+    ) -> CoreResult<domain::supplier_price::SupplierPrice> {
+        supplier_prices::table
+            .filter(supplier_prices::supplier_id.eq(supplier_id))
+            .filter(supplier_prices::effective_from.eq(effective_from.to_string()))
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::supplier_price::SupplierPrice::try_from(v).map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_price::SupplierPrice,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(supplier_prices::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn update(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_price::SupplierPrice,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::update(supplier_prices::table.find(e.id.clone()))
+            .set(&e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete(&self, conn: &MysqlConnection, id: String) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_prices::table.find(id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_prices::table)
+            .filter(supplier_prices::supplier_id.eq(supplier_id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
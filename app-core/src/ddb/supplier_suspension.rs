@@ -0,0 +1,130 @@
+use crate::ddb::schema::supplier_suspensions;
+use crate::ddb::supplier;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(supplier::Entity, foreign_key = "supplier_id")]
+#[table_name = "supplier_suspensions"]
+pub struct Entity {
+    pub id: String,
+    pub supplier_id: String,
+    pub from_ym: String,
+    pub to_ym: String,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::supplier_suspension::SupplierSuspension {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::supplier_suspension::SupplierSuspension {
+            id: e.id,
+            supplier_id: e.supplier_id,
+            from_ym: domain::YM::from_str(e.from_ym.as_str())
+                .map_err(|_e| "parse ym error".to_string())?,
+            to_ym: domain::YM::from_str(e.to_ym.as_str())
+                .map_err(|_e| "parse ym error".to_string())?,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::supplier_suspension::SupplierSuspension> for Entity {
+    fn from(d: domain::supplier_suspension::SupplierSuspension) -> Entity {
+        Entity {
+            id: d.id,
+            supplier_id: d.supplier_id,
+            from_ym: d.from_ym.to_string(),
+            to_ym: d.to_ym.to_string(),
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::supplier_suspension::SupplierSuspension> {
+    pub fn get_all_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<Vec<domain::supplier_suspension::SupplierSuspension>> {
+        return supplier_suspensions::table
+            .filter(supplier_suspensions::supplier_id.eq(supplier_id))
+            .order(supplier_suspensions::from_ym.asc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::supplier_suspension::SupplierSuspension::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get(
+        &self,
+        conn: &MysqlConnection,
+        id: String,
+    ) -> CoreResult<domain::supplier_suspension::SupplierSuspension> {
+        supplier_suspensions::table
+            .find(id)
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::supplier_suspension::SupplierSuspension::try_from(v)
+                    .map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::supplier_suspension::SupplierSuspension,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(supplier_suspensions::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete(&self, conn: &MysqlConnection, id: String) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_suspensions::table.find(id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(supplier_suspensions::table)
+            .filter(supplier_suspensions::supplier_id.eq(supplier_id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
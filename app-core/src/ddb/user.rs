@@ -12,6 +12,12 @@ use std::convert::TryFrom;
 pub struct Entity {
     pub id: String,
     pub misoca_refresh_token: String,
+    pub invoice_number_format: String,
+    pub reminder_schedule: String,
+    pub reminder_subject_template: String,
+    pub reminder_body_template: String,
+    pub invoice_mail_subject_template: String,
+    pub invoice_mail_body_template: String,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -23,6 +29,12 @@ impl TryFrom<Entity> for domain::user::User {
         Ok(domain::user::User {
             id: e.id.to_string(),
             misoca_refresh_token: e.misoca_refresh_token,
+            invoice_number_format: e.invoice_number_format,
+            reminder_schedule: e.reminder_schedule,
+            reminder_subject_template: e.reminder_subject_template,
+            reminder_body_template: e.reminder_body_template,
+            invoice_mail_subject_template: e.invoice_mail_subject_template,
+            invoice_mail_body_template: e.invoice_mail_body_template,
             created_at: e.created_at,
             updated_at: e.updated_at,
         })
@@ -34,6 +46,12 @@ impl From<domain::user::User> for Entity {
         Entity {
             id: d.id,
             misoca_refresh_token: d.misoca_refresh_token,
+            invoice_number_format: d.invoice_number_format,
+            reminder_schedule: d.reminder_schedule,
+            reminder_subject_template: d.reminder_subject_template,
+            reminder_body_template: d.reminder_body_template,
+            invoice_mail_subject_template: d.invoice_mail_subject_template,
+            invoice_mail_body_template: d.invoice_mail_body_template,
             created_at: d.created_at,
             updated_at: d.updated_at,
         }
@@ -59,15 +77,17 @@ impl Dao<domain::user::User> {
         let zipped = user_entities
             .into_iter()
             .zip(supplier_entities)
-            .map(|v: (Entity, Vec<supplier::Entity>)| {
-                (
-                    domain::user::User::try_from(v.0).unwrap(),
+            .map(|v: (Entity, Vec<supplier::Entity>)| -> CoreResult<_> {
+                Ok((
+                    domain::user::User::try_from(v.0)?,
                     v.1.into_iter()
-                        .map(|v| domain::supplier::Supplier::try_from(v).unwrap())
-                        .collect::<Vec<_>>(),
-                )
+                        .map(|v| {
+                            domain::supplier::Supplier::try_from(v).map_err(CoreError::Internal)
+                        })
+                        .collect::<CoreResult<Vec<_>>>()?,
+                ))
             })
-            .collect::<Vec<_>>();
+            .collect::<CoreResult<Vec<_>>>()?;
 
         Ok(zipped)
     }
@@ -76,8 +96,8 @@ impl Dao<domain::user::User> {
         users::table
             .find(id)
             .first(conn)
-            .map(|v: Entity| domain::user::User::try_from(v).unwrap())
             .map_err(CoreError::from)
+            .and_then(|v: Entity| domain::user::User::try_from(v).map_err(CoreError::Internal))
     }
 
     pub fn insert(&self, conn: &MysqlConnection, item: &domain::user::User) -> CoreResult<()> {
@@ -0,0 +1,58 @@
+use crate::ddb::schema::voided_invoice_numbers;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use diesel::prelude::*;
+use std::convert::TryFrom;
+
+#[derive(Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable)]
+#[primary_key(user_id, invoice_number)]
+#[table_name = "voided_invoice_numbers"]
+pub struct Entity {
+    pub user_id: String,
+    pub invoice_number: String,
+    pub reason: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::invoice_number::VoidedInvoiceNumber {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::invoice_number::VoidedInvoiceNumber {
+            user_id: e.user_id,
+            invoice_number: e.invoice_number,
+            reason: e.reason,
+            created_at: e.created_at,
+        })
+    }
+}
+
+impl From<domain::invoice_number::VoidedInvoiceNumber> for Entity {
+    fn from(d: domain::invoice_number::VoidedInvoiceNumber) -> Entity {
+        Entity {
+            user_id: d.user_id,
+            invoice_number: d.invoice_number,
+            reason: d.reason,
+            created_at: d.created_at,
+        }
+    }
+}
+
+impl Dao<domain::invoice_number::VoidedInvoiceNumber> {
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::invoice_number::VoidedInvoiceNumber,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(voided_invoice_numbers::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use crate::ddb::schema::working_hours;
+use crate::ddb::supplier;
+use crate::ddb::Dao;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use diesel::prelude::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(
+    Queryable, Insertable, Debug, Clone, Eq, PartialEq, Identifiable, Associations, AsChangeset,
+)]
+#[belongs_to(supplier::Entity, foreign_key = "supplier_id")]
+#[table_name = "working_hours"]
+pub struct Entity {
+    pub id: String,
+    pub supplier_id: String,
+    pub ym: String,
+    pub minutes: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<Entity> for domain::working_hours::WorkingHours {
+    type Error = String;
+
+    fn try_from(e: Entity) -> Result<Self, Self::Error> {
+        Ok(domain::working_hours::WorkingHours {
+            id: e.id,
+            supplier_id: e.supplier_id,
+            ym: domain::YM::from_str(e.ym.as_str()).map_err(|_e| "parse ym error".to_string())?,
+            minutes: e.minutes,
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        })
+    }
+}
+
+impl From<domain::working_hours::WorkingHours> for Entity {
+    fn from(d: domain::working_hours::WorkingHours) -> Entity {
+        Entity {
+            id: d.id,
+            supplier_id: d.supplier_id,
+            ym: d.ym.to_string(),
+            minutes: d.minutes,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
+impl Dao<domain::working_hours::WorkingHours> {
+    pub fn get_all_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<Vec<domain::working_hours::WorkingHours>> {
+        return working_hours::table
+            .filter(working_hours::supplier_id.eq(supplier_id))
+            .order(working_hours::ym.desc())
+            .load::<Entity>(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Vec<Entity>| {
+                v.into_iter()
+                    .map(|v| {
+                        domain::working_hours::WorkingHours::try_from(v)
+                            .map_err(CoreError::Internal)
+                    })
+                    .collect::<CoreResult<Vec<_>>>()
+            });
+    }
+
+    pub fn get_by_supplier_and_ym(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+        ym: &domain::YM,
+    ) -> CoreResult<domain::working_hours::WorkingHours> {
+        working_hours::table
+            .filter(working_hours::supplier_id.eq(supplier_id))
+            .filter(working_hours::ym.eq(ym.to_string()))
+            .first(conn)
+            .map_err(CoreError::from)
+            .and_then(|v: Entity| {
+                domain::working_hours::WorkingHours::try_from(v).map_err(CoreError::Internal)
+            })
+    }
+
+    pub fn insert(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::working_hours::WorkingHours,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::insert_into(working_hours::table)
+            .values(e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn update(
+        &self,
+        conn: &MysqlConnection,
+        item: &domain::working_hours::WorkingHours,
+    ) -> CoreResult<()> {
+        let e: Entity = item.clone().into();
+        if let Err(e) = diesel::update(working_hours::table.find(e.id.clone()))
+            .set(&e)
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn delete_by_supplier(
+        &self,
+        conn: &MysqlConnection,
+        supplier_id: String,
+    ) -> CoreResult<()> {
+        if let Err(e) = diesel::delete(working_hours::table)
+            .filter(working_hours::supplier_id.eq(supplier_id))
+            .execute(conn)
+            .map_err(CoreError::from)
+        {
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,27 @@
 pub mod bank;
+pub mod calendar;
+pub mod currency;
+pub mod expense;
 pub mod invoice;
+pub mod invoice_delivery;
+pub mod invoice_number;
+pub mod mail_template;
+pub mod money;
+pub mod payment;
+pub mod reminder;
+pub mod retainer_usage;
 pub mod sender;
+pub mod subject_template;
 pub mod supplier;
+pub mod supplier_adjustment;
+pub mod supplier_item;
+pub mod supplier_milestone;
+pub mod supplier_price;
+pub mod supplier_suspension;
 pub mod user;
+pub mod working_hours;
 
+use chrono::{Datelike, Duration, NaiveDate};
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -49,10 +67,17 @@ impl FromStr for YMD {
 }
 
 impl YMD {
-    fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.year == 0 || self.month == 0 || self.day == 0
     }
 
+    pub fn to_date(&self) -> Option<NaiveDate> {
+        if self.is_empty() {
+            return None;
+        }
+        NaiveDate::from_ymd_opt(self.year as i32, self.month, self.day)
+    }
+
     pub fn to_datetime(&self) -> Option<chrono::NaiveDateTime> {
         if self.is_empty() {
             return None;
@@ -70,7 +95,17 @@ impl YMD {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+impl From<NaiveDate> for YMD {
+    fn from(d: NaiveDate) -> Self {
+        YMD {
+            year: d.year() as u32,
+            month: d.month(),
+            day: d.day(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct YM {
     pub year: u32,
     pub month: u32,
@@ -110,4 +145,34 @@ impl YM {
     pub fn is_empty(&self) -> bool {
         self.year == 0 || self.month == 0
     }
+
+    pub fn add_months(&self, months: i32) -> YM {
+        let index = self.year as i32 * 12 + self.month as i32 - 1 + months;
+        YM {
+            year: index.div_euclid(12) as u32,
+            month: index.rem_euclid(12) as u32 + 1,
+        }
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd(self.year as i32, self.month, 1)
+    }
+
+    pub fn last_day(&self) -> NaiveDate {
+        self.add_months(1).first_day() - Duration::days(1)
+    }
+
+    /// selfからotherまでの月数
+    pub fn months_until(&self, other: &YM) -> i32 {
+        (other.year as i32 - self.year as i32) * 12 + other.month as i32 - self.month as i32
+    }
+}
+
+impl From<NaiveDate> for YM {
+    fn from(d: NaiveDate) -> Self {
+        YM {
+            year: d.year() as u32,
+            month: d.month(),
+        }
+    }
 }
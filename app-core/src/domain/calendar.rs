@@ -0,0 +1,287 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// 2007年以降の祝日法（振替休日・国民の休日を含む）に年末年始の銀行休業日を加えたもの
+pub fn holidays(year: i32) -> Vec<Holiday> {
+    let mut items = national_holidays(year);
+
+    let mut date = NaiveDate::from_ymd(year, 1, 2);
+    while date.year() == year {
+        let prev = date - Duration::days(1);
+        let next = date + Duration::days(1);
+        if date.weekday() != Weekday::Sun
+            && !contains(&items, date)
+            && contains(&items, prev)
+            && contains(&items, next)
+        {
+            items.push(Holiday {
+                date,
+                name: "国民の休日".to_string(),
+            });
+        }
+        date = next;
+    }
+
+    let sundays = items
+        .iter()
+        .filter(|v| v.date.weekday() == Weekday::Sun)
+        .map(|v| v.date)
+        .collect::<Vec<_>>();
+    for sunday in sundays {
+        let mut date = sunday + Duration::days(1);
+        while contains(&items, date) {
+            date = date + Duration::days(1);
+        }
+        items.push(Holiday {
+            date,
+            name: "振替休日".to_string(),
+        });
+    }
+
+    for (month, day) in vec![(1, 2), (1, 3), (12, 31)] {
+        items.push(Holiday {
+            date: NaiveDate::from_ymd(year, month, day),
+            name: "銀行休業日".to_string(),
+        });
+    }
+
+    items.sort_by_key(|v| v.date);
+    items
+}
+
+pub fn is_holiday(date: NaiveDate) -> bool {
+    contains(&holidays(date.year()), date)
+}
+
+pub fn is_business_day(date: NaiveDate) -> bool {
+    date.weekday() != Weekday::Sat && date.weekday() != Weekday::Sun && !is_holiday(date)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BusinessDayAdjustment {
+    Unadjusted,
+    Previous,
+    Next,
+}
+
+impl BusinessDayAdjustment {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::Unadjusted => 0,
+            Self::Previous => 1,
+            Self::Next => 2,
+        }
+    }
+
+    pub fn adjust(&self, date: NaiveDate) -> NaiveDate {
+        let step = match self {
+            Self::Unadjusted => return date,
+            Self::Previous => Duration::days(-1),
+            Self::Next => Duration::days(1),
+        };
+        let mut adjusted = date;
+        while !is_business_day(adjusted) {
+            adjusted = adjusted + step;
+        }
+        adjusted
+    }
+
+    /// 発行日は請求する期間が締まる前にならないよう、前営業日の設定でも翌営業日に寄せる
+    pub fn forward(&self) -> BusinessDayAdjustment {
+        match self {
+            Self::Previous => Self::Next,
+            _ => self.clone(),
+        }
+    }
+}
+
+impl Default for BusinessDayAdjustment {
+    fn default() -> Self {
+        Self::Unadjusted
+    }
+}
+
+impl From<i32> for BusinessDayAdjustment {
+    fn from(v: i32) -> BusinessDayAdjustment {
+        match v {
+            0 => Self::Unadjusted,
+            1 => Self::Previous,
+            2 => Self::Next,
+            _ => Self::default(),
+        }
+    }
+}
+
+fn contains(items: &[Holiday], date: NaiveDate) -> bool {
+    items.iter().any(|v| v.date == date)
+}
+
+fn nth_monday(year: i32, month: u32, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month(year, month, Weekday::Mon, n)
+}
+
+/// 1980〜2099年の範囲で使える春分日・秋分日の近似式
+fn equinox_day(year: i32, base: i64) -> u32 {
+    let y = (year - 1980) as i64;
+    ((base + 242194 * y) / 1000000 - y / 4) as u32
+}
+
+fn national_holidays(year: i32) -> Vec<Holiday> {
+    let mut items: Vec<(NaiveDate, &str)> = vec![
+        (NaiveDate::from_ymd(year, 1, 1), "元日"),
+        (nth_monday(year, 1, 2), "成人の日"),
+        (NaiveDate::from_ymd(year, 2, 11), "建国記念の日"),
+        (
+            NaiveDate::from_ymd(year, 3, equinox_day(year, 20843100)),
+            "春分の日",
+        ),
+        (NaiveDate::from_ymd(year, 4, 29), "昭和の日"),
+        (NaiveDate::from_ymd(year, 5, 3), "憲法記念日"),
+        (NaiveDate::from_ymd(year, 5, 4), "みどりの日"),
+        (NaiveDate::from_ymd(year, 5, 5), "こどもの日"),
+        (nth_monday(year, 9, 3), "敬老の日"),
+        (
+            NaiveDate::from_ymd(year, 9, equinox_day(year, 23248800)),
+            "秋分の日",
+        ),
+        (NaiveDate::from_ymd(year, 11, 3), "文化の日"),
+        (NaiveDate::from_ymd(year, 11, 23), "勤労感謝の日"),
+    ];
+
+    match year {
+        2020 => {
+            items.push((NaiveDate::from_ymd(year, 7, 23), "海の日"));
+            items.push((NaiveDate::from_ymd(year, 7, 24), "スポーツの日"));
+            items.push((NaiveDate::from_ymd(year, 8, 10), "山の日"));
+        }
+        2021 => {
+            items.push((NaiveDate::from_ymd(year, 7, 22), "海の日"));
+            items.push((NaiveDate::from_ymd(year, 7, 23), "スポーツの日"));
+            items.push((NaiveDate::from_ymd(year, 8, 8), "山の日"));
+        }
+        _ => {
+            items.push((nth_monday(year, 7, 3), "海の日"));
+            // 2019年までは体育の日
+            items.push((
+                nth_monday(year, 10, 2),
+                if year >= 2020 {
+                    "スポーツの日"
+                } else {
+                    "体育の日"
+                },
+            ));
+            if year >= 2016 {
+                items.push((NaiveDate::from_ymd(year, 8, 11), "山の日"));
+            }
+        }
+    }
+
+    if year <= 2018 {
+        items.push((NaiveDate::from_ymd(year, 12, 23), "天皇誕生日"));
+    } else if year >= 2020 {
+        items.push((NaiveDate::from_ymd(year, 2, 23), "天皇誕生日"));
+    }
+
+    if year == 2019 {
+        items.push((NaiveDate::from_ymd(year, 5, 1), "即位の日"));
+        items.push((NaiveDate::from_ymd(year, 10, 22), "即位礼正殿の儀"));
+    }
+
+    items
+        .into_iter()
+        .map(|(date, name)| Holiday {
+            date,
+            name: name.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod calendar_tests {
+    use crate::domain::calendar::{holidays, is_business_day, BusinessDayAdjustment};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn holidays_in_2024() {
+        let items = holidays(2024)
+            .into_iter()
+            .map(|v| (v.date.format("%m-%d").to_string(), v.name))
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            ("01-01", "元日"),
+            ("01-02", "銀行休業日"),
+            ("01-03", "銀行休業日"),
+            ("01-08", "成人の日"),
+            ("02-11", "建国記念の日"),
+            ("02-12", "振替休日"),
+            ("02-23", "天皇誕生日"),
+            ("03-20", "春分の日"),
+            ("04-29", "昭和の日"),
+            ("05-03", "憲法記念日"),
+            ("05-04", "みどりの日"),
+            ("05-05", "こどもの日"),
+            ("05-06", "振替休日"),
+            ("07-15", "海の日"),
+            ("08-11", "山の日"),
+            ("08-12", "振替休日"),
+            ("09-16", "敬老の日"),
+            ("09-22", "秋分の日"),
+            ("09-23", "振替休日"),
+            ("10-14", "スポーツの日"),
+            ("11-03", "文化の日"),
+            ("11-04", "振替休日"),
+            ("11-23", "勤労感謝の日"),
+            ("12-31", "銀行休業日"),
+        ]
+        .into_iter()
+        .map(|(date, name)| (date.to_string(), name.to_string()))
+        .collect::<Vec<_>>();
+
+        assert_eq!(items, expected);
+    }
+
+    #[test]
+    fn sandwiched_holidays() {
+        let items = holidays(2019);
+        for (month, day) in vec![(4, 30), (5, 2), (5, 6)] {
+            let date = NaiveDate::from_ymd(2019, month, day);
+            assert!(items.iter().any(|v| v.date == date), "{}", date);
+        }
+
+        assert!(items
+            .iter()
+            .any(|v| v.date == NaiveDate::from_ymd(2019, 10, 14) && v.name == "体育の日"));
+
+        let items = holidays(2026);
+        let date = NaiveDate::from_ymd(2026, 9, 22);
+        assert!(items
+            .iter()
+            .any(|v| v.date == date && v.name == "国民の休日"));
+    }
+
+    #[test]
+    fn adjust_business_day() {
+        // 2024-12-31(火)〜2025-01-05(日)は年末年始と土日で休み
+        let date = NaiveDate::from_ymd(2024, 12, 31);
+        assert!(!is_business_day(date));
+        assert_eq!(BusinessDayAdjustment::Unadjusted.adjust(date), date);
+        assert_eq!(
+            BusinessDayAdjustment::Previous.adjust(date),
+            NaiveDate::from_ymd(2024, 12, 30)
+        );
+        assert_eq!(
+            BusinessDayAdjustment::Next.adjust(date),
+            NaiveDate::from_ymd(2025, 1, 6)
+        );
+        assert_eq!(
+            BusinessDayAdjustment::Previous.forward().adjust(date),
+            NaiveDate::from_ymd(2025, 1, 6)
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::domain::invoice::TaxRounding;
+use crate::domain::money::Money;
+use crate::domain::YMD;
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+/// 請求に使う通貨。金額はすべてこの通貨の最小単位（円、セント）の整数で持つ
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Currency {
+    JPY,
+    USD,
+    EUR,
+}
+
+impl Currency {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::JPY => 0,
+            Self::USD => 1,
+            Self::EUR => 2,
+        }
+    }
+
+    pub fn code(&self) -> String {
+        match self {
+            Self::JPY => "JPY".to_string(),
+            Self::USD => "USD".to_string(),
+            Self::EUR => "EUR".to_string(),
+        }
+    }
+
+    /// 最小単位の桁数。円は0、ドル・ユーロはセント単位なので2
+    pub fn minor_unit_digits(&self) -> u32 {
+        match self {
+            Self::JPY => 0,
+            Self::USD | Self::EUR => 2,
+        }
+    }
+
+    fn minor_unit_scale(&self) -> i64 {
+        10i64.pow(self.minor_unit_digits())
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Self::JPY
+    }
+}
+
+impl From<i32> for Currency {
+    fn from(v: i32) -> Currency {
+        match v {
+            0 => Self::JPY,
+            1 => Self::USD,
+            2 => Self::EUR,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "JPY" => Ok(Self::JPY),
+            "USD" => Ok(Self::USD),
+            "EUR" => Ok(Self::EUR),
+            _ => Err(format!("{}は対応していない通貨です", s)),
+        }
+    }
+}
+
+/// 仲値（TTM）。1通貨単位あたりの円を銭単位（100倍）の整数で持つ
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExchangeRate {
+    pub currency: Currency,
+    pub ymd: YMD,
+    /// 1ドル = 149.52円の場合は14952
+    pub rate: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl ExchangeRate {
+    pub fn new(
+        currency: Currency,
+        ymd: YMD,
+        rate: i32,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        if currency == Currency::JPY {
+            return Err("円の為替レートは登録できません".to_string());
+        }
+        if rate <= 0 {
+            return Err("為替レートは正の値を入力してください".to_string());
+        }
+        Ok(ExchangeRate {
+            currency,
+            ymd,
+            rate,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        })
+    }
+
+    /// 最小単位の金額を円に換算する。1円未満は四捨五入
+    pub fn to_jpy(&self, minor: &Money) -> Money {
+        minor.ratio(
+            self.rate as i64,
+            100 * self.currency.minor_unit_scale(),
+            &TaxRounding::Round,
+        )
+    }
+}
+
+/// 指定日の仲値。土日祝日は公表されないため、指定日以前で最も新しいものを使う
+pub fn rate_on<'a>(
+    rates: &'a [ExchangeRate],
+    currency: &Currency,
+    ymd: &YMD,
+) -> Option<&'a ExchangeRate> {
+    rates
+        .iter()
+        .filter(|v| &v.currency == currency && v.ymd.to_string() <= ymd.to_string())
+        .max_by_key(|v| v.ymd.to_string())
+}
+
+/// 「日付,通貨,仲値」のCSV（例: `2021-08-02,USD,109.35`）を読み込む。1行目が見出しの場合は読み飛ばす
+pub fn parse_ttm_csv(csv: &str, now: DateTime<Utc>) -> Result<Vec<ExchangeRate>, String> {
+    let mut rates = vec![];
+
+    for (i, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let columns = line.split(',').map(|v| v.trim()).collect::<Vec<_>>();
+        if i == 0 && !columns[0].starts_with(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+        if columns.len() != 3 {
+            return Err(format!("{}行目: 列の数が正しくありません", i + 1));
+        }
+
+        let ymd = parse_ymd(columns[0]).ok_or(format!("{}行目: 日付が正しくありません", i + 1))?;
+        let currency =
+            Currency::from_str(columns[1]).map_err(|e| format!("{}行目: {}", i + 1, e))?;
+        let rate =
+            parse_rate(columns[2]).ok_or(format!("{}行目: 仲値が正しくありません", i + 1))?;
+
+        rates.push(
+            ExchangeRate::new(currency, ymd, rate, now)
+                .map_err(|e| format!("{}行目: {}", i + 1, e))?,
+        );
+    }
+
+    Ok(rates)
+}
+
+fn parse_ymd(s: &str) -> Option<YMD> {
+    let tmp = s.split('-').collect::<Vec<_>>();
+    if s.len() != 10 || tmp.len() != 3 {
+        return None;
+    }
+    let ymd = YMD {
+        year: tmp[0].parse().ok()?,
+        month: tmp[1].parse().ok()?,
+        day: tmp[2].parse().ok()?,
+    };
+    chrono::NaiveDate::from_ymd_opt(ymd.year as i32, ymd.month, ymd.day)?;
+    Some(ymd)
+}
+
+/// 小数第2位までの仲値を銭単位の整数にする。符号や桁あふれする値は受け付けない
+fn parse_rate(s: &str) -> Option<i32> {
+    let mut parts = s.splitn(2, '.');
+    let integer = parts.next()?;
+    let fraction = parts.next().unwrap_or("");
+    if integer.is_empty()
+        || !integer.chars().all(|c| c.is_ascii_digit())
+        || fraction.len() > 2
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let integer: i32 = integer.parse().ok()?;
+    let fraction: i32 = format!("{:0<2}", fraction).parse().ok()?;
+    integer.checked_mul(100)?.checked_add(fraction)
+}
+
+#[cfg(test)]
+mod currency_tests {
+    use crate::domain::currency::{parse_ttm_csv, rate_on, Currency};
+    use crate::domain::money::Money;
+    use crate::domain::YMD;
+    use chrono::{TimeZone, Utc};
+    use std::str::FromStr;
+
+    #[test]
+    fn convert_to_jpy() {
+        let now = Utc.ymd(2021, 8, 10).and_hms(0, 0, 0);
+        let ymd = |s: &str| YMD::from_str(s).unwrap();
+
+        let rates = parse_ttm_csv(
+            "date,currency,ttm\n2021-08-02,USD,109.35\n2021-08-06,USD,109.8\n2021-08-06,EUR,130.12\n",
+            now,
+        )
+        .unwrap();
+        assert_eq!(rates.len(), 3);
+        assert_eq!(rates[1].rate, 10980);
+
+        // 土日は直前の営業日の仲値を使う
+        let rate = rate_on(&rates, &Currency::USD, &ymd("2021-08-08")).unwrap();
+        assert_eq!(rate.ymd, ymd("2021-08-06"));
+        assert_eq!(rate.to_jpy(&Money::from(123456)), Money::from(135555));
+        assert_eq!(rate.to_jpy(&Money::from(-123456)), Money::from(-135555));
+        assert!(rate_on(&rates, &Currency::USD, &ymd("2021-08-01")).is_none());
+
+        assert!(parse_ttm_csv("2021-08-02,JPY,1.00", now).is_err());
+        assert!(parse_ttm_csv("2021-08-02,USD,109.355", now).is_err());
+        assert!(parse_ttm_csv("2021-08-32,USD,109.35", now).is_err());
+        assert!(parse_ttm_csv("2021-08-02,USD,-0.50", now).is_err());
+        assert!(parse_ttm_csv("2021-08-02,USD,+109.35", now).is_err());
+        assert!(parse_ttm_csv("2021-08-02,USD,2000000000.00", now).is_err());
+    }
+}
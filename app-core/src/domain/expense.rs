@@ -0,0 +1,177 @@
+use crate::domain::invoice::{InvoiceItem, TaxType};
+use crate::domain::money::Money;
+use crate::domain::YMD;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 請求先に代わって支払った交通費・ライセンス費用などの立替金。未請求のものは次に発行する請求書に含める
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Expense {
+    pub id: String,
+    pub supplier_id: String,
+    /// 支払日
+    pub ymd: YMD,
+    pub description: String,
+    pub amount: Money,
+    /// 立替金は通常は不課税
+    pub tax_type: TaxType,
+    /// 領収書のストレージ上のパス
+    pub receipt_path: Option<String>,
+    /// 請求済みの場合は発行した請求書
+    pub invoice_id: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl Expense {
+    pub fn new(
+        supplier_id: String,
+        ymd: YMD,
+        description: String,
+        amount: Money,
+        tax_type: TaxType,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        validate(&ymd, &description, &amount)?;
+        Ok(Expense {
+            id: Uuid::new_v4().to_string(),
+            supplier_id,
+            ymd,
+            description,
+            amount,
+            tax_type,
+            receipt_path: None,
+            invoice_id: None,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        })
+    }
+
+    pub fn update(
+        &mut self,
+        ymd: YMD,
+        description: String,
+        amount: Money,
+        tax_type: TaxType,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        if self.is_billed() {
+            return Err("請求済みの立替金は変更できません".to_string());
+        }
+        validate(&ymd, &description, &amount)?;
+        self.ymd = ymd;
+        self.description = description;
+        self.amount = amount;
+        self.tax_type = tax_type;
+        self.updated_at = now.naive_utc();
+        Ok(())
+    }
+
+    pub fn is_billed(&self) -> bool {
+        self.invoice_id.is_some()
+    }
+
+    pub fn receipt_path(&self, file_name: &str, now: DateTime<Utc>) -> String {
+        format!(
+            "expense/{}/{}_{}",
+            self.id,
+            now.format("%Y%m%d%H%M%S"),
+            file_name
+        )
+    }
+
+    pub fn attach_receipt(&mut self, path: String, now: DateTime<Utc>) {
+        self.receipt_path = Some(path);
+        self.updated_at = now.naive_utc();
+    }
+
+    pub fn mark_billed(&mut self, invoice_id: String, now: DateTime<Utc>) {
+        self.invoice_id = Some(invoice_id);
+        self.updated_at = now.naive_utc();
+    }
+
+    /// 立替金は源泉徴収の対象外
+    pub fn invoice_item(&self) -> InvoiceItem {
+        InvoiceItem {
+            name: format!(
+                "立替金 {}/{} {}",
+                self.ymd.month, self.ymd.day, self.description
+            ),
+            quantity: 1,
+            unit: "式".to_string(),
+            unit_price: self.amount.clone(),
+            tax_type: self.tax_type.clone(),
+            withholding: false,
+        }
+    }
+}
+
+fn validate(ymd: &YMD, description: &str, amount: &Money) -> Result<(), String> {
+    if ymd.is_empty() {
+        return Err("支払日を入力してください".to_string());
+    }
+    if description.is_empty() {
+        return Err("内容を入力してください".to_string());
+    }
+    if !amount.is_positive() {
+        return Err("金額は1円以上で指定してください".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod expense_tests {
+    use crate::domain::currency::Currency;
+    use crate::domain::expense::Expense;
+    use crate::domain::invoice::{withholding_tax, TaxType};
+    use crate::domain::money::Money;
+    use crate::domain::YMD;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn expense_invoice_item() {
+        let now = Utc.ymd(2021, 4, 10).and_hms(0, 0, 0);
+        let ymd = YMD {
+            year: 2021,
+            month: 4,
+            day: 3,
+        };
+
+        assert!(Expense::new(
+            "".to_string(),
+            ymd.clone(),
+            "".to_string(),
+            Money::from(1000),
+            TaxType::NonTaxable,
+            now
+        )
+        .is_err());
+
+        let mut expense = Expense::new(
+            "".to_string(),
+            ymd.clone(),
+            "新幹線 東京-大阪".to_string(),
+            Money::from(14720),
+            TaxType::NonTaxable,
+            now,
+        )
+        .unwrap();
+
+        let item = expense.invoice_item();
+        assert_eq!(item.name, "立替金 4/3 新幹線 東京-大阪");
+        assert_eq!(item.amount(), Money::from(14720));
+        assert_eq!(item.tax_type, TaxType::NonTaxable);
+        assert_eq!(withholding_tax(&[item], &Currency::JPY), Money::zero());
+
+        expense.mark_billed("1".to_string(), now);
+        assert!(expense
+            .update(
+                ymd,
+                "新幹線 東京-大阪".to_string(),
+                Money::from(1000),
+                TaxType::NonTaxable,
+                now
+            )
+            .is_err());
+    }
+}
@@ -1,4 +1,12 @@
-use crate::domain::YMD;
+use crate::domain::currency::{rate_on, Currency, ExchangeRate};
+use crate::domain::money::Money;
+use crate::domain::payment::Payment;
+use crate::domain::{YM, YMD};
+use bigdecimal::{BigDecimal, Signed};
+use chrono::{DateTime, Utc};
+use std::cmp;
+
+const WITHHOLDING_THRESHOLD: i32 = 1_000_000;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Invoice {
@@ -8,12 +16,37 @@ pub struct Invoice {
     pub payment_due_on_ymd: YMD,
     pub invoice_number: String,
     pub payment_status: PaymentStatus,
+    /// misocaで最後に確認した入金状況
+    pub remote_payment_status: PaymentStatus,
     pub invoice_status: InvoiceStatus,
     pub recipient_name: String,
     pub subject: String,
-    pub total_amount: i32,
-    pub tax: i32,
+    /// 定期請求で請求した期間の締め月。手動やマイルストーンの請求は空
+    pub billing_ym: YM,
+    /// 金額はこの通貨の最小単位
+    pub currency: Currency,
+    pub total_amount: Money,
+    pub tax: Money,
+    /// 源泉徴収税額
+    pub withholding_tax: Money,
+    /// 請求金額から源泉徴収税額を差し引いた支払額
+    pub payment_amount: Money,
+    /// 登録した入金の合計
+    pub paid_amount: Money,
+    /// 税率ごとの合計（適格請求書の記載事項）
+    pub tax_summaries: Vec<TaxSummary>,
+    /// 備考
+    pub remarks: String,
     pub pdf_path: Option<String>,
+    /// 期限超過を検知した日時
+    pub overdue_at: Option<chrono::NaiveDateTime>,
+    /// 取消した日時と理由
+    pub cancelled_at: Option<chrono::NaiveDateTime>,
+    pub cancel_reason: String,
+    /// 取消の際に発行した赤伝
+    pub credit_note_id: Option<String>,
+    /// 赤伝の場合は打ち消す元の請求書
+    pub original_invoice_id: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -23,15 +56,623 @@ impl Invoice {
         self.pdf_path = Some(path);
     }
 
+    /// misocaから取得したPDFの保存先。misoca側で請求書が更新されると変わる
+    pub fn pdf_object_path(&self) -> String {
+        format!(
+            "invoice/{}_{}.pdf",
+            self.id.clone(),
+            self.updated_at.format("%Y%m%d%H%M%S")
+        )
+    }
+
+    /// 保存済みのPDFが請求書の最新の内容のものか
+    pub fn has_current_pdf(&self) -> bool {
+        self.pdf_path.as_deref() == Some(self.pdf_object_path().as_str())
+    }
+
+    /// メールで送付した請求書を送付済みにする。PDFの保存先が変わらないよう updated_at はmisocaの値のままにする
+    pub fn mark_submitted(&mut self) {
+        self.invoice_status = InvoiceStatus::Submitted;
+    }
+
     pub fn should_update(&self, other: &Invoice) -> bool {
         self.updated_at != other.updated_at
     }
+
+    pub fn apply_withholding_tax(&mut self, withholding_tax: Money) {
+        self.payment_amount = self.total_amount.clone() - withholding_tax.clone();
+        self.withholding_tax = withholding_tax;
+    }
+
+    pub fn apply_tax_summaries(&mut self, tax_summaries: Vec<TaxSummary>) {
+        self.tax_summaries = tax_summaries;
+    }
+
+    pub fn apply_billing_ym(&mut self, ym: YM) {
+        self.billing_ym = ym;
+    }
+
+    /// 登録済みの入金を合計し、支払額との差から入金状況を決める
+    pub fn apply_payments(&mut self, payments: &[Payment]) {
+        self.paid_amount = payments.iter().map(|v| v.amount.clone()).sum();
+        self.refresh_payment_status();
+    }
+
+    /// 入金を登録していなければmisocaの入金状況に従う。misocaで入金済みのものは一部入金や未入金に戻さない
+    fn refresh_payment_status(&mut self) {
+        let local = if self.paid_amount.is_zero() {
+            self.remote_payment_status.clone()
+        } else if self.paid_amount < self.payment_amount {
+            PaymentStatus::PartiallyPaid
+        } else if self.paid_amount == self.payment_amount {
+            PaymentStatus::Paid
+        } else {
+            PaymentStatus::Overpaid
+        };
+        self.payment_status = match local {
+            PaymentStatus::UnPaid | PaymentStatus::PartiallyPaid
+                if self.remote_payment_status == PaymentStatus::Paid =>
+            {
+                PaymentStatus::Paid
+            }
+            v => v,
+        };
+    }
+
+    /// 未回収の残高。過入金の場合は負になる。misocaで入金済みになったものは0
+    pub fn outstanding_amount(&self) -> Money {
+        if self.payment_status == PaymentStatus::Paid {
+            return Money::zero();
+        }
+        self.payment_amount.clone() - self.paid_amount.clone()
+    }
+
+    /// 発行日の仲値で円に換算する。円の請求書はそのまま、仲値が登録されていなければNone
+    pub fn to_jpy(&self, amount: &Money, rates: &[ExchangeRate]) -> Option<Money> {
+        if self.currency == Currency::JPY {
+            return Some(amount.clone());
+        }
+        rate_on(rates, &self.currency, &self.issue_ymd).map(|v| v.to_jpy(amount))
+    }
+
+    /// 回収が必要か。取消した請求書と赤伝は回収しない
+    pub fn is_unpaid(&self) -> bool {
+        if self.is_cancelled() || self.is_credit_note() {
+            return false;
+        }
+        self.payment_status == PaymentStatus::UnPaid
+            || self.payment_status == PaymentStatus::PartiallyPaid
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.invoice_status == InvoiceStatus::Cancelled
+    }
+
+    pub fn is_credit_note(&self) -> bool {
+        self.original_invoice_id.is_some()
+    }
+
+    pub fn validate_for_payment(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            return Err("取消した請求書には入金を登録できません".to_string());
+        }
+        if self.is_credit_note() {
+            return Err("赤伝には入金を登録できません".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn validate_for_delivery(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            return Err("取消した請求書は送付できません".to_string());
+        }
+        Ok(())
+    }
+
+    /// 請求書を取り消す。帳簿から消さないよう、請求書番号も金額もそのまま残す
+    pub fn cancel(&mut self, reason: String, now: DateTime<Utc>) -> Result<(), String> {
+        if self.is_cancelled() {
+            return Err("この請求書はすでに取り消されています".to_string());
+        }
+        if self.is_credit_note() {
+            return Err("赤伝は取り消せません".to_string());
+        }
+        if reason.trim().is_empty() {
+            return Err("取消理由を入力してください".to_string());
+        }
+        self.invoice_status = InvoiceStatus::Cancelled;
+        self.cancel_reason = reason.trim().to_string();
+        self.cancelled_at = Some(now.naive_utc());
+        Ok(())
+    }
+
+    /// 赤伝の請求書番号。採番しない場合は元の請求書番号に `-C` を付け、元の請求書にも番号がなければmisocaで採番する
+    pub fn credit_note_number(&self, invoice_number: Option<String>) -> Option<String> {
+        invoice_number.or_else(|| {
+            if self.invoice_number.is_empty() {
+                None
+            } else {
+                Some(format!("{}-C", self.invoice_number))
+            }
+        })
+    }
+
+    /// 取り消した請求書を打ち消す赤伝としてmisocaに作る明細。税率ごとに元の請求書の税抜金額をマイナスで載せる
+    pub fn credit_note_items(&self) -> Result<Vec<InvoiceItem>, String> {
+        if !self.is_cancelled() {
+            return Err("赤伝は取り消した請求書にのみ発行できます".to_string());
+        }
+        if self.credit_note_id.is_some() {
+            return Err("この請求書の赤伝はすでに発行されています".to_string());
+        }
+        Ok(self
+            .tax_summaries
+            .iter()
+            .filter(|v| !v.taxable_amount.is_zero())
+            .map(|v| InvoiceItem {
+                name: format!(
+                    "請求書番号{}の取消（{}）",
+                    self.invoice_number,
+                    v.tax_type.label()
+                ),
+                quantity: 1,
+                unit: "式".to_string(),
+                unit_price: -v.taxable_amount.clone(),
+                tax_type: v.tax_type.clone(),
+                withholding: false,
+            })
+            .collect())
+    }
+
+    pub fn credit_note_subject(&self) -> String {
+        format!("【赤伝】{}", self.subject)
+    }
+
+    /// misocaで作った赤伝を元の請求書に紐付ける。源泉徴収税額はmisocaの明細では打ち消さないため、
+    /// 税率ごとの合計と合わせて元の請求書の符号を反転したものをこちらで記録する
+    pub fn issue_credit_note(&mut self, mut credit_note: Invoice) -> Invoice {
+        credit_note.apply_withholding_tax(-self.withholding_tax.clone());
+        credit_note.apply_tax_summaries(
+            self.tax_summaries
+                .iter()
+                .map(|v| TaxSummary {
+                    tax_type: v.tax_type.clone(),
+                    taxable_amount: -v.taxable_amount.clone(),
+                    tax: -v.tax.clone(),
+                })
+                .collect(),
+        );
+        credit_note.remarks = format!(
+            "請求書番号{}の請求を取り消します。取消理由: {}",
+            self.invoice_number, self.cancel_reason
+        );
+        credit_note.original_invoice_id = Some(self.id.clone());
+        self.credit_note_id = Some(credit_note.id.clone());
+        credit_note
+    }
+
+    /// 支払期限を過ぎてからの日数。期限内または入金済みの場合は0
+    pub fn days_overdue(&self, now: DateTime<Utc>) -> i64 {
+        let due = match self.payment_due_on_ymd.to_date() {
+            Some(due) => due,
+            None => return 0,
+        };
+        if !self.is_unpaid() {
+            return 0;
+        }
+        cmp::max((now.naive_utc().date() - due).num_days(), 0)
+    }
+
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.days_overdue(now) > 0
+    }
+
+    /// 期限超過になったことを記録する。記録済みの場合は最初に検知した日時のまま
+    pub fn mark_overdue(&mut self, now: DateTime<Utc>) -> bool {
+        if self.overdue_at.is_some() || !self.is_overdue(now) {
+            return false;
+        }
+        self.overdue_at = Some(now.naive_utc());
+        true
+    }
+
+    /// misocaから取得した内容で更新する際に、こちらでのみ管理している項目を引き継ぐ
+    pub fn merge_remote(&self, remote: Invoice) -> Invoice {
+        let mut invoice = remote;
+        invoice.apply_withholding_tax(self.withholding_tax.clone());
+        invoice.apply_tax_summaries(self.tax_summaries.clone());
+        invoice.remarks = self.remarks.clone();
+        invoice.billing_ym = self.billing_ym.clone();
+        invoice.paid_amount = self.paid_amount.clone();
+        invoice.overdue_at = self.overdue_at;
+        invoice.cancelled_at = self.cancelled_at;
+        invoice.cancel_reason = self.cancel_reason.clone();
+        invoice.credit_note_id = self.credit_note_id.clone();
+        invoice.original_invoice_id = self.original_invoice_id.clone();
+        if self.invoice_status != InvoiceStatus::UnSubmitted {
+            invoice.invoice_status = self.invoice_status.clone();
+        }
+        invoice.refresh_payment_status();
+        if !self.invoice_number.is_empty() {
+            invoice.invoice_number = self.invoice_number.clone();
+        }
+        invoice
+    }
+}
+
+/// 請求先ごとの未回収残高
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct OutstandingSummary {
+    pub outstanding_amount: Money,
+    /// うち支払期限を過ぎているもの
+    pub overdue_amount: Money,
+    pub overdue_count: i32,
+    /// 請求書ごとに発行日の仲値で円に換算した合計。仲値のない請求書がある場合はNone
+    pub outstanding_amount_jpy: Option<Money>,
+    pub overdue_amount_jpy: Option<Money>,
+}
+
+impl OutstandingSummary {
+    pub fn of(
+        invoices: &[Invoice],
+        rates: &[ExchangeRate],
+        now: DateTime<Utc>,
+    ) -> OutstandingSummary {
+        let zero = OutstandingSummary {
+            outstanding_amount_jpy: Some(Money::zero()),
+            overdue_amount_jpy: Some(Money::zero()),
+            ..OutstandingSummary::default()
+        };
+        invoices
+            .iter()
+            .filter(|v| v.is_unpaid())
+            .fold(zero, |acc, v| {
+                let overdue = v.is_overdue(now);
+                let jpy = v.to_jpy(&v.outstanding_amount(), rates);
+                OutstandingSummary {
+                    outstanding_amount: acc.outstanding_amount + v.outstanding_amount(),
+                    overdue_amount: if overdue {
+                        acc.overdue_amount + v.outstanding_amount()
+                    } else {
+                        acc.overdue_amount
+                    },
+                    overdue_count: acc.overdue_count + overdue as i32,
+                    outstanding_amount_jpy: sum_jpy(acc.outstanding_amount_jpy, jpy.clone()),
+                    overdue_amount_jpy: if overdue {
+                        sum_jpy(acc.overdue_amount_jpy, jpy)
+                    } else {
+                        acc.overdue_amount_jpy
+                    },
+                }
+            })
+    }
+}
+
+/// 円換算額の合計。どちらかが換算できなければNone
+fn sum_jpy(a: Option<Money>, b: Option<Money>) -> Option<Money> {
+    Some(a? + b?)
+}
+
+/// 発行月・通貨ごとの請求額（税込）。取消と赤伝を差し引いた純額を出す
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BillingSummary {
+    pub ym: YM,
+    pub currency: Currency,
+    /// 発行した請求書の合計。取り消したものも含む
+    pub billed_amount: Money,
+    /// うち赤伝を発行せずに取り消したもの
+    pub cancelled_amount: Money,
+    /// この月に発行した赤伝で打ち消した額
+    pub credited_amount: Money,
+    /// 請求書ごとに発行日の仲値で円に換算した合計。仲値のない請求書がある場合はNone
+    pub billed_amount_jpy: Option<Money>,
+    pub cancelled_amount_jpy: Option<Money>,
+    pub credited_amount_jpy: Option<Money>,
+}
+
+impl BillingSummary {
+    pub fn net_amount(&self) -> Money {
+        self.billed_amount.clone() - self.cancelled_amount.clone() - self.credited_amount.clone()
+    }
+
+    pub fn net_amount_jpy(&self) -> Option<Money> {
+        Some(
+            self.billed_amount_jpy.clone()?
+                - self.cancelled_amount_jpy.clone()?
+                - self.credited_amount_jpy.clone()?,
+        )
+    }
+
+    /// 赤伝を発行した請求書は元の月に計上したまま赤伝の月で打ち消し、赤伝のない取消は元の月で打ち消す
+    pub fn of(invoices: &[Invoice], rates: &[ExchangeRate]) -> Vec<BillingSummary> {
+        let mut summaries: Vec<BillingSummary> = vec![];
+        for invoice in invoices {
+            let ym = match invoice.issue_ymd.to_date() {
+                Some(date) => YM::from(date),
+                None => continue,
+            };
+            let index = match summaries
+                .iter()
+                .position(|v| v.ym == ym && v.currency == invoice.currency)
+            {
+                Some(index) => index,
+                None => {
+                    summaries.push(BillingSummary {
+                        ym,
+                        currency: invoice.currency.clone(),
+                        billed_amount: Money::zero(),
+                        cancelled_amount: Money::zero(),
+                        credited_amount: Money::zero(),
+                        billed_amount_jpy: Some(Money::zero()),
+                        cancelled_amount_jpy: Some(Money::zero()),
+                        credited_amount_jpy: Some(Money::zero()),
+                    });
+                    summaries.len() - 1
+                }
+            };
+
+            let summary = &mut summaries[index];
+            let jpy = invoice.to_jpy(&invoice.total_amount, rates);
+            if invoice.is_credit_note() {
+                summary.credited_amount =
+                    summary.credited_amount.clone() - invoice.total_amount.clone();
+                summary.credited_amount_jpy =
+                    sum_jpy(summary.credited_amount_jpy.clone(), jpy.map(|v| -v));
+                continue;
+            }
+            summary.billed_amount = summary.billed_amount.clone() + invoice.total_amount.clone();
+            summary.billed_amount_jpy = sum_jpy(summary.billed_amount_jpy.clone(), jpy.clone());
+            if invoice.is_cancelled() && invoice.credit_note_id.is_none() {
+                summary.cancelled_amount =
+                    summary.cancelled_amount.clone() + invoice.total_amount.clone();
+                summary.cancelled_amount_jpy = sum_jpy(summary.cancelled_amount_jpy.clone(), jpy);
+            }
+        }
+        summaries.sort_by(|a, b| {
+            a.ym.cmp(&b.ym)
+                .then(a.currency.int().cmp(&b.currency.int()))
+        });
+        summaries
+    }
+}
+
+/// 調整で値引きしすぎて請求金額がマイナスになる明細は発行しない。税率ごとに見るのは、税率ごとの消費税額がマイナスにならないようにするため
+pub fn validate_items(items: &[InvoiceItem]) -> Result<(), String> {
+    for tax_type in vec![TaxType::Standard10, TaxType::Reduced8, TaxType::NonTaxable] {
+        let amount: Money = items
+            .iter()
+            .filter(|v| v.tax_type == tax_type)
+            .map(|v| v.amount())
+            .sum();
+        if amount < Money::zero() {
+            return Err(format!(
+                "調整後の{}の請求金額がマイナスになります。値引きの調整を見直してください",
+                tax_type.label()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 源泉徴収税額。対象額が100万円以下は10.21%、100万円を超える部分は20.42%で、1円未満は切り捨て。
+/// 税率の区分が円建てなので、円以外の請求書では源泉徴収しない
+pub fn withholding_tax(items: &[InvoiceItem], currency: &Currency) -> Money {
+    if currency != &Currency::JPY {
+        return Money::zero();
+    }
+    let base: Money = items
+        .iter()
+        .filter(|v| v.withholding)
+        .map(|v| v.amount())
+        .sum();
+    if !base.is_positive() {
+        return Money::zero();
+    }
+    let threshold = Money::from(WITHHOLDING_THRESHOLD);
+    if base <= threshold {
+        base.ratio(1021, 10000, &TaxRounding::Floor)
+    } else {
+        threshold.ratio(1021, 10000, &TaxRounding::Floor)
+            + (base - threshold).ratio(2042, 10000, &TaxRounding::Floor)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvoiceItem {
+    pub name: String,
+    pub quantity: i32,
+    pub unit: String,
+    pub unit_price: Money,
+    pub tax_type: TaxType,
+    pub withholding: bool,
+}
+
+impl InvoiceItem {
+    pub fn amount(&self) -> Money {
+        self.unit_price.times(self.quantity)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TaxType {
+    Standard10,
+    Reduced8,
+    NonTaxable,
+}
+
+impl TaxType {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::Standard10 => 0,
+            Self::Reduced8 => 1,
+            Self::NonTaxable => 2,
+        }
+    }
+
+    /// 税率（%）
+    pub fn rate(&self) -> i64 {
+        match self {
+            Self::Standard10 => 10,
+            Self::Reduced8 => 8,
+            Self::NonTaxable => 0,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Standard10 => "10%対象",
+            Self::Reduced8 => "8%対象",
+            Self::NonTaxable => "非課税",
+        }
+    }
+}
+
+impl Default for TaxType {
+    fn default() -> Self {
+        Self::Standard10
+    }
+}
+
+impl From<i32> for TaxType {
+    fn from(v: i32) -> TaxType {
+        match v {
+            0 => Self::Standard10,
+            1 => Self::Reduced8,
+            2 => Self::NonTaxable,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TaxRounding {
+    Floor,
+    Round,
+    Ceil,
+}
+
+impl TaxRounding {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::Floor => 0,
+            Self::Round => 1,
+            Self::Ceil => 2,
+        }
+    }
+
+    /// 小数点以下を丸める。負の値は絶対値を丸めてから符号を戻す
+    pub fn round(&self, v: &BigDecimal) -> BigDecimal {
+        let abs = v.abs();
+        let truncated = abs.with_scale(0);
+        let rounded = match self {
+            Self::Floor => truncated,
+            Self::Round => (abs + BigDecimal::new(5.into(), 1)).with_scale(0),
+            Self::Ceil if abs.is_integer() => truncated,
+            Self::Ceil => truncated + BigDecimal::from(1),
+        };
+        if v.is_negative() {
+            -rounded
+        } else {
+            rounded
+        }
+    }
+}
+
+impl Default for TaxRounding {
+    fn default() -> Self {
+        Self::Floor
+    }
+}
+
+impl From<i32> for TaxRounding {
+    fn from(v: i32) -> TaxRounding {
+        match v {
+            0 => Self::Floor,
+            1 => Self::Round,
+            2 => Self::Ceil,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// 請求先ごとの消費税の設定。tax_type は明細を登録していない場合の既定の税率
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct TaxSetting {
+    pub tax_type: TaxType,
+    /// 単価を税込で扱うか
+    pub inclusive: bool,
+    pub rounding: TaxRounding,
+}
+
+impl TaxSetting {
+    pub fn tax_of(&self, amount: &Money, tax_type: &TaxType) -> Money {
+        let rate = tax_type.rate();
+        if self.inclusive {
+            amount.ratio(rate, 100 + rate, &self.rounding)
+        } else {
+            amount.ratio(rate, 100, &self.rounding)
+        }
+    }
+
+    pub fn amount_include_tax(&self, amount: &Money) -> Money {
+        if self.inclusive {
+            amount.clone()
+        } else {
+            amount.clone() + self.tax_of(amount, &self.tax_type)
+        }
+    }
+
+    pub fn amount_exclude_tax(&self, amount: &Money) -> Money {
+        if self.inclusive {
+            amount.clone() - self.tax_of(amount, &self.tax_type)
+        } else {
+            amount.clone()
+        }
+    }
+
+    /// 税率ごとに明細を合計し、税率ごとに1回だけ端数処理した税額を求める
+    pub fn summaries(&self, items: &[InvoiceItem]) -> Vec<TaxSummary> {
+        vec![TaxType::Standard10, TaxType::Reduced8, TaxType::NonTaxable]
+            .into_iter()
+            .filter(|tax_type| items.iter().any(|v| &v.tax_type == tax_type))
+            .map(|tax_type| {
+                let amount: Money = items
+                    .iter()
+                    .filter(|v| v.tax_type == tax_type)
+                    .map(|v| v.amount())
+                    .sum();
+                let tax = self.tax_of(&amount, &tax_type);
+                let taxable_amount = if self.inclusive {
+                    amount - tax.clone()
+                } else {
+                    amount
+                };
+                TaxSummary {
+                    tax_type,
+                    taxable_amount,
+                    tax,
+                }
+            })
+            .collect()
+    }
+}
+
+/// 税率ごとの合計。taxable_amount は税抜金額
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TaxSummary {
+    pub tax_type: TaxType,
+    pub taxable_amount: Money,
+    pub tax: Money,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum PaymentStatus {
     UnPaid,
     Paid,
+    /// 一部入金
+    PartiallyPaid,
+    /// 過入金
+    Overpaid,
 }
 
 impl PaymentStatus {
@@ -39,6 +680,8 @@ impl PaymentStatus {
         match self {
             Self::UnPaid => 0,
             Self::Paid => 1,
+            Self::PartiallyPaid => 2,
+            Self::Overpaid => 3,
         }
     }
 }
@@ -54,6 +697,8 @@ impl From<i32> for PaymentStatus {
         match v {
             0 => Self::UnPaid,
             1 => Self::Paid,
+            2 => Self::PartiallyPaid,
+            3 => Self::Overpaid,
             _ => Self::default(),
         }
     }
@@ -63,6 +708,8 @@ impl From<i32> for PaymentStatus {
 pub enum InvoiceStatus {
     UnSubmitted,
     Submitted,
+    /// 取消済み
+    Cancelled,
 }
 
 impl InvoiceStatus {
@@ -70,6 +717,7 @@ impl InvoiceStatus {
         match self {
             Self::UnSubmitted => 0,
             Self::Submitted => 1,
+            Self::Cancelled => 2,
         }
     }
 }
@@ -85,7 +733,469 @@ impl From<i32> for InvoiceStatus {
         match v {
             0 => Self::UnSubmitted,
             1 => Self::Submitted,
+            2 => Self::Cancelled,
             _ => Self::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod invoice_tests {
+    use crate::domain::currency::{parse_ttm_csv, Currency};
+    use crate::domain::invoice::{
+        validate_items, withholding_tax, BillingSummary, Invoice, InvoiceItem, InvoiceStatus,
+        OutstandingSummary, PaymentStatus, TaxRounding, TaxSetting, TaxSummary, TaxType,
+    };
+    use crate::domain::money::Money;
+    use crate::domain::payment::{Payment, PaymentMethod};
+    use crate::domain::{YM, YMD};
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::str::FromStr;
+
+    fn item(unit_price: i32, withholding: bool) -> InvoiceItem {
+        InvoiceItem {
+            name: "".to_string(),
+            quantity: 1,
+            unit: "式".to_string(),
+            unit_price: Money::from(unit_price),
+            tax_type: TaxType::Standard10,
+            withholding,
+        }
+    }
+
+    #[test]
+    fn tax_summaries() {
+        let items = vec![
+            InvoiceItem {
+                quantity: 3,
+                unit_price: Money::from(3333),
+                ..item(0, false)
+            },
+            InvoiceItem {
+                tax_type: TaxType::Reduced8,
+                ..item(1005, false)
+            },
+            InvoiceItem {
+                tax_type: TaxType::NonTaxable,
+                ..item(500, false)
+            },
+        ];
+
+        let exclusive = TaxSetting::default();
+        assert_eq!(
+            exclusive.summaries(&items),
+            vec![
+                TaxSummary {
+                    tax_type: TaxType::Standard10,
+                    taxable_amount: Money::from(9999),
+                    tax: Money::from(999),
+                },
+                TaxSummary {
+                    tax_type: TaxType::Reduced8,
+                    taxable_amount: Money::from(1005),
+                    tax: Money::from(80),
+                },
+                TaxSummary {
+                    tax_type: TaxType::NonTaxable,
+                    taxable_amount: Money::from(500),
+                    tax: Money::from(0),
+                },
+            ]
+        );
+
+        let inclusive = TaxSetting {
+            inclusive: true,
+            rounding: TaxRounding::Round,
+            ..TaxSetting::default()
+        };
+        let summaries = inclusive.summaries(&items[..1]);
+        assert_eq!(summaries[0].tax, Money::from(909));
+        assert_eq!(summaries[0].taxable_amount, Money::from(9090));
+    }
+
+    #[test]
+    fn amount_include_tax() {
+        let setting = TaxSetting {
+            tax_type: TaxType::Reduced8,
+            rounding: TaxRounding::Ceil,
+            ..TaxSetting::default()
+        };
+        assert_eq!(
+            setting.amount_include_tax(&Money::from(1001)),
+            Money::from(1082)
+        );
+        assert_eq!(
+            setting.amount_exclude_tax(&Money::from(1001)),
+            Money::from(1001)
+        );
+
+        let setting = TaxSetting {
+            inclusive: true,
+            ..TaxSetting::default()
+        };
+        assert_eq!(
+            setting.amount_include_tax(&Money::from(11000)),
+            Money::from(11000)
+        );
+        assert_eq!(
+            setting.amount_exclude_tax(&Money::from(11000)),
+            Money::from(10000)
+        );
+    }
+
+    #[test]
+    fn withholding_tax_amount() {
+        let jpy = Currency::JPY;
+        assert_eq!(withholding_tax(&[], &jpy), Money::from(0));
+        assert_eq!(
+            withholding_tax(&[item(100000, false)], &jpy),
+            Money::from(0)
+        );
+        assert_eq!(
+            withholding_tax(&[item(100000, true)], &jpy),
+            Money::from(10210)
+        );
+        assert_eq!(
+            withholding_tax(&[item(33333, true)], &jpy),
+            Money::from(3403)
+        );
+        assert_eq!(
+            withholding_tax(&[item(1000000, true)], &jpy),
+            Money::from(102100)
+        );
+        assert_eq!(
+            withholding_tax(
+                &[item(1000000, true), item(500000, true), item(300000, false)],
+                &jpy
+            ),
+            Money::from(204200)
+        );
+        assert_eq!(
+            withholding_tax(&[item(100000, true)], &Currency::USD),
+            Money::from(0)
+        );
+    }
+
+    #[test]
+    fn validate_items_rejects_negative_total() {
+        assert!(validate_items(&[]).is_ok());
+        assert!(validate_items(&[item(100000, false), item(-100000, false)]).is_ok());
+        assert!(validate_items(&[item(100000, false), item(-100001, false)]).is_err());
+
+        // 他の税率の明細で相殺しても、税率ごとにマイナスなら発行しない
+        let mut reduced = item(-50000, false);
+        reduced.tax_type = TaxType::Reduced8;
+        assert!(validate_items(&[item(100000, false), reduced]).is_err());
+    }
+
+    fn invoice(now: DateTime<Utc>) -> Invoice {
+        Invoice {
+            id: "1".to_string(),
+            supplier_id: "1".to_string(),
+            issue_ymd: YMD::from_str("2021-04-30").unwrap(),
+            payment_due_on_ymd: YMD::from_str("2021-05-31").unwrap(),
+            invoice_number: "".to_string(),
+            payment_status: PaymentStatus::UnPaid,
+            remote_payment_status: PaymentStatus::UnPaid,
+            invoice_status: InvoiceStatus::Submitted,
+            recipient_name: "".to_string(),
+            subject: "".to_string(),
+            billing_ym: YM { year: 0, month: 0 },
+            currency: Currency::JPY,
+            total_amount: Money::from(110000),
+            tax: Money::from(10000),
+            withholding_tax: Money::zero(),
+            payment_amount: Money::from(110000),
+            paid_amount: Money::zero(),
+            tax_summaries: vec![],
+            remarks: "".to_string(),
+            pdf_path: None,
+            overdue_at: None,
+            cancelled_at: None,
+            cancel_reason: "".to_string(),
+            credit_note_id: None,
+            original_invoice_id: None,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        }
+    }
+
+    #[test]
+    fn payment_status_with_payments() {
+        let now = Utc.ymd(2021, 5, 10).and_hms(0, 0, 0);
+        let payment = |amount: i32| {
+            Payment::new(
+                "1".to_string(),
+                YMD::from_str("2021-05-10").unwrap(),
+                Money::from(amount),
+                PaymentMethod::BankTransfer,
+                "".to_string(),
+                now,
+            )
+            .unwrap()
+        };
+        let mut invoice = invoice(now);
+
+        invoice.apply_payments(&[payment(50000)]);
+        assert_eq!(invoice.payment_status, PaymentStatus::PartiallyPaid);
+        assert_eq!(invoice.outstanding_amount(), Money::from(60000));
+
+        invoice.apply_payments(&[payment(50000), payment(60000)]);
+        assert_eq!(invoice.payment_status, PaymentStatus::Paid);
+        assert!(invoice.outstanding_amount().is_zero());
+
+        invoice.apply_payments(&[payment(50000), payment(61000)]);
+        assert_eq!(invoice.payment_status, PaymentStatus::Overpaid);
+        assert_eq!(invoice.outstanding_amount(), Money::from(-1000));
+
+        // misocaの同期で上書きされない
+        let remote = Invoice {
+            payment_status: PaymentStatus::UnPaid,
+            ..invoice.clone()
+        };
+        assert_eq!(
+            invoice.merge_remote(remote).payment_status,
+            PaymentStatus::Overpaid
+        );
+
+        invoice.apply_payments(&[]);
+        assert_eq!(invoice.payment_status, PaymentStatus::UnPaid);
+
+        // misocaで入金済みのものは、一部入金を登録しても入金を削除しても入金済みのまま
+        let remote = Invoice {
+            payment_status: PaymentStatus::Paid,
+            remote_payment_status: PaymentStatus::Paid,
+            ..invoice.clone()
+        };
+        let mut invoice = invoice.merge_remote(remote);
+        assert_eq!(invoice.payment_status, PaymentStatus::Paid);
+        invoice.apply_payments(&[payment(50000)]);
+        assert_eq!(invoice.payment_status, PaymentStatus::Paid);
+        assert_eq!(
+            invoice.merge_remote(invoice.clone()).payment_status,
+            PaymentStatus::Paid
+        );
+        invoice.apply_payments(&[]);
+        assert_eq!(invoice.payment_status, PaymentStatus::Paid);
+        invoice.apply_payments(&[payment(50000), payment(61000)]);
+        assert_eq!(invoice.payment_status, PaymentStatus::Overpaid);
+        assert!(Payment::new(
+            "1".to_string(),
+            YMD::from_str("2021-05-10").unwrap(),
+            Money::zero(),
+            PaymentMethod::Cash,
+            "".to_string(),
+            now,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn overdue_invoices() {
+        let issued = Utc.ymd(2021, 4, 30).and_hms(0, 0, 0);
+        let now = Utc.ymd(2021, 6, 3).and_hms(12, 0, 0);
+
+        let mut overdue = invoice(issued);
+        assert!(!overdue.is_overdue(Utc.ymd(2021, 5, 31).and_hms(23, 0, 0)));
+        assert_eq!(overdue.days_overdue(now), 3);
+        assert!(overdue.mark_overdue(now));
+        assert_eq!(overdue.overdue_at, Some(now.naive_utc()));
+        // 2回目以降は最初に検知した日時のまま
+        assert!(!overdue.mark_overdue(Utc.ymd(2021, 6, 4).and_hms(0, 0, 0)));
+        assert_eq!(overdue.overdue_at, Some(now.naive_utc()));
+
+        let partially_paid = Invoice {
+            id: "2".to_string(),
+            payment_status: PaymentStatus::PartiallyPaid,
+            paid_amount: Money::from(10000),
+            ..invoice(issued)
+        };
+        let paid = Invoice {
+            id: "3".to_string(),
+            payment_status: PaymentStatus::Paid,
+            ..invoice(issued)
+        };
+        let not_due = Invoice {
+            id: "4".to_string(),
+            payment_due_on_ymd: YMD::from_str("2021-06-30").unwrap(),
+            ..invoice(issued)
+        };
+        assert_eq!(paid.days_overdue(now), 0);
+        assert!(!paid.clone().mark_overdue(now));
+
+        assert_eq!(
+            OutstandingSummary::of(&[overdue, partially_paid, paid, not_due], &[], now),
+            OutstandingSummary {
+                outstanding_amount: Money::from(320000),
+                overdue_amount: Money::from(210000),
+                overdue_count: 2,
+                outstanding_amount_jpy: Some(Money::from(320000)),
+                overdue_amount_jpy: Some(Money::from(210000)),
+            }
+        );
+
+        // 外貨の請求書は発行日の仲値で円に換算する。仲値がなければ換算しない
+        let usd = Invoice {
+            currency: Currency::USD,
+            payment_amount: Money::from(10000),
+            ..invoice(issued)
+        };
+        let rates = parse_ttm_csv("2021-04-30,USD,110.00", now).unwrap();
+        let summary = OutstandingSummary::of(&[usd.clone()], &rates, now);
+        assert_eq!(summary.outstanding_amount, Money::from(10000));
+        assert_eq!(summary.overdue_amount_jpy, Some(Money::from(11000)));
+        assert_eq!(
+            OutstandingSummary::of(&[usd], &[], now).outstanding_amount_jpy,
+            None
+        );
+    }
+
+    #[test]
+    fn submitted_by_mail() {
+        let now = Utc.ymd(2021, 5, 1).and_hms(9, 30, 0);
+        let mut current = Invoice {
+            invoice_status: InvoiceStatus::UnSubmitted,
+            ..invoice(now)
+        };
+        assert_eq!(current.pdf_object_path(), "invoice/1_20210501093000.pdf");
+        assert!(!current.has_current_pdf());
+        current.update_pdf_path(current.pdf_object_path());
+        assert!(current.has_current_pdf());
+
+        current.mark_submitted();
+        let remote = Invoice {
+            invoice_status: InvoiceStatus::UnSubmitted,
+            updated_at: Utc.ymd(2021, 5, 2).and_hms(0, 0, 0).naive_utc(),
+            ..invoice(now)
+        };
+        let merged = current.merge_remote(remote);
+        assert_eq!(merged.invoice_status, InvoiceStatus::Submitted);
+        // misoca側で更新されたらPDFを取り直す
+        assert!(!merged.has_current_pdf());
+    }
+
+    #[test]
+    fn cancel_with_credit_note() {
+        let now = Utc.ymd(2021, 6, 10).and_hms(0, 0, 0);
+        let mut current = Invoice {
+            invoice_number: "INV-0001".to_string(),
+            tax_summaries: vec![TaxSummary {
+                tax_type: TaxType::Standard10,
+                taxable_amount: Money::from(100000),
+                tax: Money::from(10000),
+            }],
+            ..invoice(now)
+        };
+
+        assert!(current.credit_note_items().is_err());
+        assert!(current.cancel(" ".to_string(), now).is_err());
+        current.cancel("金額の誤り".to_string(), now).unwrap();
+        assert_eq!(current.invoice_status, InvoiceStatus::Cancelled);
+        assert_eq!(current.invoice_number, "INV-0001");
+        assert_eq!(current.cancelled_at, Some(now.naive_utc()));
+        assert!(!current.is_unpaid());
+        assert!(current.validate_for_payment().is_err());
+        assert!(current.cancel("二重".to_string(), now).is_err());
+
+        assert_eq!(
+            current.credit_note_number(None),
+            Some("INV-0001-C".to_string())
+        );
+        assert_eq!(
+            current.credit_note_number(Some("INV-0002".to_string())),
+            Some("INV-0002".to_string())
+        );
+        let items = current.credit_note_items().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "請求書番号INV-0001の取消（10%対象）");
+        assert_eq!(items[0].unit_price, Money::from(-100000));
+        assert_eq!(items[0].tax_type, TaxType::Standard10);
+
+        // misocaで作った赤伝を紐付ける
+        let remote = Invoice {
+            id: "2".to_string(),
+            invoice_number: "INV-0001-C".to_string(),
+            total_amount: Money::from(-110000),
+            tax: Money::from(-10000),
+            payment_amount: Money::from(-110000),
+            ..invoice(now)
+        };
+        let mut credit_note = current.issue_credit_note(remote.clone());
+        assert_eq!(current.credit_note_id, Some("2".to_string()));
+        assert_eq!(credit_note.original_invoice_id, Some(current.id.clone()));
+        assert_eq!(credit_note.payment_amount, Money::from(-110000));
+        assert_eq!(
+            credit_note.tax_summaries[0].taxable_amount,
+            Money::from(-100000)
+        );
+        assert!(!credit_note.is_unpaid());
+        assert!(credit_note.validate_for_delivery().is_ok());
+        assert!(credit_note.cancel("取消".to_string(), now).is_err());
+        assert!(current.credit_note_items().is_err());
+        assert_eq!(
+            credit_note.merge_remote(remote).original_invoice_id,
+            Some(current.id.clone())
+        );
+
+        // misocaと同期しても取消は残る
+        let merged = current.merge_remote(invoice(now));
+        assert_eq!(merged.invoice_status, InvoiceStatus::Cancelled);
+        assert_eq!(merged.cancel_reason, "金額の誤り");
+        assert_eq!(merged.credit_note_id, current.credit_note_id);
+    }
+
+    #[test]
+    fn billing_summary() {
+        let now = Utc.ymd(2021, 6, 10).and_hms(0, 0, 0);
+        let issued = |ymd: &str, amount: i32| Invoice {
+            issue_ymd: YMD::from_str(ymd).unwrap(),
+            total_amount: Money::from(amount),
+            ..invoice(now)
+        };
+
+        let active = issued("2021-04-30", 110000);
+        let mut cancelled = issued("2021-04-30", 55000);
+        cancelled.cancel("重複".to_string(), now).unwrap();
+        let mut credited = issued("2021-05-31", 220000);
+        credited.cancel("契約解除".to_string(), now).unwrap();
+        let credit_note = credited.issue_credit_note(Invoice {
+            id: "2".to_string(),
+            ..issued("2021-06-10", -220000)
+        });
+        let usd = Invoice {
+            currency: Currency::USD,
+            ..issued("2021-05-31", 1000)
+        };
+
+        let rates = parse_ttm_csv("2021-05-28,USD,110.00", now).unwrap();
+        let summaries = BillingSummary::of(
+            &[credit_note, usd.clone(), credited, cancelled, active],
+            &rates,
+        );
+        let ym_of = |v: &BillingSummary| (v.ym.to_string(), v.currency.clone());
+        assert_eq!(
+            summaries.iter().map(ym_of).collect::<Vec<_>>(),
+            vec![
+                ("2021-04".to_string(), Currency::JPY),
+                ("2021-05".to_string(), Currency::JPY),
+                ("2021-05".to_string(), Currency::USD),
+                ("2021-06".to_string(), Currency::JPY),
+            ]
+        );
+        // 赤伝のない取消は発行月で打ち消す
+        assert_eq!(summaries[0].billed_amount, Money::from(165000));
+        assert_eq!(summaries[0].cancelled_amount, Money::from(55000));
+        assert_eq!(summaries[0].net_amount(), Money::from(110000));
+        // 赤伝を発行した請求書は発行月に残し、赤伝の月で打ち消す
+        assert_eq!(summaries[1].net_amount(), Money::from(220000));
+        assert_eq!(summaries[2].net_amount(), Money::from(1000));
+        assert_eq!(summaries[3].credited_amount, Money::from(220000));
+        assert_eq!(summaries[3].net_amount(), Money::from(-220000));
+        // 円換算は請求書ごとに発行日の仲値で行う
+        assert_eq!(summaries[0].net_amount_jpy(), Some(Money::from(110000)));
+        assert_eq!(summaries[2].billed_amount_jpy, Some(Money::from(1100)));
+        assert_eq!(summaries[3].credited_amount_jpy, Some(Money::from(220000)));
+        assert_eq!(summaries[3].net_amount_jpy(), Some(Money::from(-220000)));
+        assert_eq!(BillingSummary::of(&[usd], &[])[0].net_amount_jpy(), None);
+    }
+}
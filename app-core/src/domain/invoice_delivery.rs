@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub const DEFAULT_SUBJECT_TEMPLATE: &str = "【請求書送付】{subject}（{invoice_number}）";
+pub const DEFAULT_BODY_TEMPLATE: &str = "{supplier} ご担当者様
+
+いつもお世話になっております。{sender}です。
+
+下記の通り請求書をお送りいたします。添付のPDFをご確認ください。
+
+件名: {subject}
+請求書番号: {invoice_number}
+発行日: {issue}
+ご請求金額: {amount}
+お支払い期限: {due}
+
+よろしくお願いいたします。
+";
+
+/// 請求書をメールで送る際の CC・BCC と、発行時に自動で送るか
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct DeliverySetting {
+    pub cc: Vec<String>,
+    pub bcc: Vec<String>,
+    /// 請求書の発行バッチで、発行した請求書をそのまま送付する
+    pub auto_send: bool,
+}
+
+impl DeliverySetting {
+    pub fn new(cc: Vec<String>, bcc: Vec<String>, auto_send: bool) -> Result<Self, String> {
+        let cc = normalize_addresses(cc)?;
+        let bcc = normalize_addresses(bcc)?;
+        Ok(DeliverySetting { cc, bcc, auto_send })
+    }
+}
+
+/// 前後の空白と空の行を除き、メールアドレスの形式を確かめる
+pub fn normalize_addresses(addresses: Vec<String>) -> Result<Vec<String>, String> {
+    addresses
+        .into_iter()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            validate_address(v.as_str())?;
+            Ok(v)
+        })
+        .collect()
+}
+
+/// カンマ区切りで保存したメールアドレスを分ける
+pub fn split_addresses(s: &str) -> Vec<String> {
+    s.split(',')
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+        .collect()
+}
+
+/// 送信に使えるかだけを見る簡易な検証。`local@domain` の形で、空白や山括弧を含まない
+pub fn validate_address(address: &str) -> Result<(), String> {
+    let valid = match address.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.is_empty()
+                && !domain.contains('@')
+                && !address
+                    .contains(|c: char| c.is_whitespace() || c == '<' || c == '>' || c == ',')
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(format!("{}はメールアドレスの形式ではありません", address));
+    }
+    Ok(())
+}
+
+/// 請求書をメールで送付した記録
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvoiceDelivery {
+    pub id: String,
+    pub invoice_id: String,
+    pub sent_to: String,
+    pub cc: Vec<String>,
+    pub bcc: Vec<String>,
+    pub subject: String,
+    /// 添付したPDF
+    pub pdf_path: String,
+    pub sent_at: chrono::NaiveDateTime,
+}
+
+impl InvoiceDelivery {
+    pub fn new(
+        invoice_id: String,
+        sent_to: String,
+        setting: &DeliverySetting,
+        subject: String,
+        pdf_path: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        InvoiceDelivery {
+            id: Uuid::new_v4().to_string(),
+            invoice_id,
+            sent_to,
+            cc: setting.cc.clone(),
+            bcc: setting.bcc.clone(),
+            subject,
+            pdf_path,
+            sent_at: now.naive_utc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod invoice_delivery_tests {
+    use crate::domain::invoice_delivery::{validate_address, DeliverySetting};
+
+    #[test]
+    fn delivery_setting() {
+        let setting = DeliverySetting::new(
+            vec![" keiri@example.com ".to_string(), "".to_string()],
+            vec!["me@example.com".to_string()],
+            true,
+        )
+        .unwrap();
+        assert_eq!(setting.cc, vec!["keiri@example.com".to_string()]);
+        assert_eq!(setting.bcc, vec!["me@example.com".to_string()]);
+
+        assert!(DeliverySetting::new(vec!["keiri".to_string()], vec![], false).is_err());
+        assert!(validate_address("a@b@example.com").is_err());
+        assert!(validate_address("taro <taro@example.com>").is_err());
+        assert!(validate_address("@example.com").is_err());
+    }
+}
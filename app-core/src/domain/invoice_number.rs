@@ -0,0 +1,177 @@
+use crate::domain::subject_template::{pad, tokenize, Token};
+use crate::domain::YMD;
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+/// 請求書番号の書式。`{year}` `{month}` `{prefix}`（請求先ごとの接頭辞） `{seq}`（年ごとの連番）を埋め込み、
+/// 数値は `{seq:04}` のようにゼロ埋めできる。連番は年ごとに振り直すため `{year}` も必須。空の場合はmisocaの採番に任せる
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct InvoiceNumberFormat {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder, Option<usize>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Placeholder {
+    Year,
+    Month,
+    Prefix,
+    Seq,
+}
+
+impl FromStr for InvoiceNumberFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments = tokenize(s)?
+            .into_iter()
+            .map(|token| match token {
+                Token::Text(text) => Ok(Segment::Text(text)),
+                Token::Placeholder(name, width) => {
+                    let placeholder = match name.as_str() {
+                        "year" => Placeholder::Year,
+                        "month" => Placeholder::Month,
+                        "prefix" => Placeholder::Prefix,
+                        "seq" => Placeholder::Seq,
+                        _ => return Err(format!("{{{}}}は使用できないプレースホルダーです", name)),
+                    };
+                    if width.is_some() && placeholder == Placeholder::Prefix {
+                        return Err(format!("{{{}}}はゼロ埋めできません", name));
+                    }
+                    Ok(Segment::Placeholder(placeholder, width))
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let format = InvoiceNumberFormat { segments };
+        if format.is_empty() {
+            return Ok(format);
+        }
+        if !format.contains(Placeholder::Year) || !format.contains(Placeholder::Seq) {
+            return Err("請求書番号の書式には{year}と{seq}が必要です".to_string());
+        }
+        Ok(format)
+    }
+}
+
+impl InvoiceNumberFormat {
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    fn contains(&self, placeholder: Placeholder) -> bool {
+        self.segments
+            .iter()
+            .any(|v| matches!(v, Segment::Placeholder(p, _) if *p == placeholder))
+    }
+
+    pub fn render(&self, issue_ymd: &YMD, prefix: &str, seq: i32) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Placeholder(placeholder, width) => {
+                    let value = match placeholder {
+                        Placeholder::Year => issue_ymd.year.to_string(),
+                        Placeholder::Month => issue_ymd.month.to_string(),
+                        Placeholder::Prefix => prefix.to_string(),
+                        Placeholder::Seq => seq.to_string(),
+                    };
+                    pad(value, width)
+                }
+            })
+            .collect()
+    }
+}
+
+/// ユーザーごと・発行年ごとに最後に採番した連番。年が変わると1から振り直す
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvoiceNumberSequence {
+    pub user_id: String,
+    pub year: u32,
+    pub last_number: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl InvoiceNumberSequence {
+    pub fn new(user_id: String, year: u32, now: DateTime<Utc>) -> Self {
+        InvoiceNumberSequence {
+            user_id,
+            year,
+            last_number: 0,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        }
+    }
+
+    pub fn next(&mut self, now: DateTime<Utc>) -> i32 {
+        self.last_number += 1;
+        self.updated_at = now.naive_utc();
+        self.last_number
+    }
+}
+
+/// 採番したが請求書の発行に使わなかった番号。連番は巻き戻さずに欠番として残し、理由を記録する
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VoidedInvoiceNumber {
+    pub user_id: String,
+    pub invoice_number: String,
+    pub reason: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl VoidedInvoiceNumber {
+    pub fn new(
+        user_id: String,
+        invoice_number: String,
+        reason: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        VoidedInvoiceNumber {
+            user_id,
+            invoice_number,
+            reason,
+            created_at: now.naive_utc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod invoice_number_tests {
+    use crate::domain::invoice_number::{InvoiceNumberFormat, InvoiceNumberSequence};
+    use crate::domain::YMD;
+    use chrono::{TimeZone, Utc};
+    use std::str::FromStr;
+
+    #[test]
+    fn render_invoice_number() {
+        let now = Utc.ymd(2026, 1, 5).and_hms(0, 0, 0);
+        let issue_ymd = YMD {
+            year: 2026,
+            month: 1,
+            day: 5,
+        };
+
+        let mut sequence = InvoiceNumberSequence::new("".to_string(), 2026, now);
+        assert_eq!(sequence.next(now), 1);
+        assert_eq!(sequence.next(now), 2);
+
+        let format = InvoiceNumberFormat::from_str("{year}-{seq:04}").unwrap();
+        assert_eq!(format.render(&issue_ymd, "", 42), "2026-0042");
+
+        let format = InvoiceNumberFormat::from_str("{prefix}-{year}{month:02}-{seq:03}").unwrap();
+        assert_eq!(format.render(&issue_ymd, "ABC", 7), "ABC-202601-007");
+
+        assert!(InvoiceNumberFormat::from_str("").unwrap().is_empty());
+        assert!(InvoiceNumberFormat::from_str("{year}").is_err());
+        assert!(InvoiceNumberFormat::from_str("{prefix}-{seq}").is_err());
+        assert!(InvoiceNumberFormat::from_str("{year}{seq}-{day}").is_err());
+        assert!(InvoiceNumberFormat::from_str("{prefix:03}-{year}{seq}").is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use crate::domain::currency::Currency;
+use crate::domain::money::Money;
+use crate::domain::subject_template::{pad, tokenize, Token};
+use chrono::{Datelike, NaiveDate};
+use std::str::FromStr;
+
+/// 請求書の送付・督促メールの件名と本文のテンプレート。請求書の件名と同じく `{名前}` で値を埋め込む
+///
+/// | 名前 | 内容 |
+/// | --- | --- |
+/// | `supplier` | 請求先名 |
+/// | `sender` | 差出人名 |
+/// | `subject` | 請求書の件名 |
+/// | `invoice_number` | 請求書番号 |
+/// | `issue` `due` | 発行日・支払期限（2021年8月31日） |
+/// | `amount` | 請求額、督促では未回収の残高（110,000円） |
+/// | `days_overdue` | 支払期限を過ぎてからの日数。期限前は0 |
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MailTemplate {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder, Option<usize>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Placeholder {
+    Supplier,
+    Sender,
+    Subject,
+    InvoiceNumber,
+    Issue,
+    Due,
+    Amount,
+    DaysOverdue,
+}
+
+impl Placeholder {
+    fn parse(name: &str) -> Option<Placeholder> {
+        match name {
+            "supplier" => Some(Self::Supplier),
+            "sender" => Some(Self::Sender),
+            "subject" => Some(Self::Subject),
+            "invoice_number" => Some(Self::InvoiceNumber),
+            "issue" => Some(Self::Issue),
+            "due" => Some(Self::Due),
+            "amount" => Some(Self::Amount),
+            "days_overdue" => Some(Self::DaysOverdue),
+            _ => None,
+        }
+    }
+}
+
+/// テンプレートに埋め込む値
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MailContext {
+    pub supplier_name: String,
+    pub sender_name: String,
+    pub subject: String,
+    pub invoice_number: String,
+    pub issue: Option<NaiveDate>,
+    pub due: NaiveDate,
+    pub amount: Money,
+    pub currency: Currency,
+    pub days_overdue: i64,
+}
+
+impl FromStr for MailTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments = tokenize(s)?
+            .into_iter()
+            .map(|token| match token {
+                Token::Text(text) => Ok(Segment::Text(text)),
+                Token::Placeholder(name, width) => {
+                    let placeholder = Placeholder::parse(name.as_str())
+                        .ok_or(format!("{{{}}}は使用できないプレースホルダーです", name))?;
+                    if width.is_some() && placeholder != Placeholder::DaysOverdue {
+                        return Err(format!("{{{}}}はゼロ埋めできません", name));
+                    }
+                    Ok(Segment::Placeholder(placeholder, width))
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(MailTemplate { segments })
+    }
+}
+
+impl MailTemplate {
+    /// 空の場合は既定のテンプレートを使う
+    pub fn or_default(template: &str, default: &str) -> Result<Self, String> {
+        if template.is_empty() {
+            MailTemplate::from_str(default)
+        } else {
+            MailTemplate::from_str(template)
+        }
+    }
+
+    pub fn render(&self, ctx: &MailContext) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Placeholder(placeholder, width) => pad(value_of(placeholder, ctx), width),
+            })
+            .collect()
+    }
+}
+
+fn value_of(placeholder: &Placeholder, ctx: &MailContext) -> String {
+    match placeholder {
+        Placeholder::Supplier => ctx.supplier_name.clone(),
+        Placeholder::Sender => ctx.sender_name.clone(),
+        Placeholder::Subject => ctx.subject.clone(),
+        Placeholder::InvoiceNumber => ctx.invoice_number.clone(),
+        Placeholder::Issue => ctx.issue.map(|v| format_date(&v)).unwrap_or_default(),
+        Placeholder::Due => format_date(&ctx.due),
+        Placeholder::Amount => format_amount(&ctx.amount, &ctx.currency),
+        Placeholder::DaysOverdue => ctx.days_overdue.to_string(),
+    }
+}
+
+fn format_date(date: &NaiveDate) -> String {
+    format!("{}年{}月{}日", date.year(), date.month(), date.day())
+}
+
+/// 3桁区切りにして、円は「円」、外貨は通貨コードを付ける
+fn format_amount(amount: &Money, currency: &Currency) -> String {
+    let major = amount.to_major_string(currency);
+    let (sign, digits) = match major.strip_prefix('-') {
+        Some(v) => ("-", v),
+        None => ("", major.as_str()),
+    };
+    let mut parts = digits.splitn(2, '.');
+    let integer = parts.next().unwrap_or("");
+    let grouped = integer
+        .chars()
+        .rev()
+        .collect::<Vec<_>>()
+        .chunks(3)
+        .map(|v| v.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(",")
+        .chars()
+        .rev()
+        .collect::<String>();
+    let number = match parts.next() {
+        Some(fraction) => format!("{}{}.{}", sign, grouped, fraction),
+        None => format!("{}{}", sign, grouped),
+    };
+
+    match currency {
+        Currency::JPY => format!("{}円", number),
+        _ => format!("{} {}", number, currency.code()),
+    }
+}
+
+#[cfg(test)]
+mod mail_template_tests {
+    use crate::domain::currency::Currency;
+    use crate::domain::mail_template::{MailContext, MailTemplate};
+    use crate::domain::money::Money;
+    use crate::domain::reminder::DEFAULT_BODY_TEMPLATE;
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    #[test]
+    fn render_template() {
+        let ctx = MailContext {
+            supplier_name: "株式会社サンプル".to_string(),
+            sender_name: "山田太郎".to_string(),
+            subject: "8月分 保守".to_string(),
+            invoice_number: "INV-0001".to_string(),
+            issue: Some(NaiveDate::from_ymd(2021, 8, 1)),
+            due: NaiveDate::from_ymd(2021, 8, 31),
+            amount: Money::from(1234567),
+            currency: Currency::JPY,
+            days_overdue: 7,
+        };
+
+        let render = |s: &str| MailTemplate::from_str(s).unwrap().render(&ctx);
+        assert_eq!(
+            render("{supplier}様 {invoice_number} {amount} 期限{due}から{days_overdue:03}日"),
+            "株式会社サンプル様 INV-0001 1,234,567円 期限2021年8月31日から007日"
+        );
+        assert_eq!(
+            MailTemplate::from_str("{amount}")
+                .unwrap()
+                .render(&MailContext {
+                    amount: Money::from(-123456),
+                    currency: Currency::USD,
+                    ..ctx.clone()
+                }),
+            "-1,234.56 USD"
+        );
+        assert!(MailTemplate::or_default("", DEFAULT_BODY_TEMPLATE)
+            .unwrap()
+            .render(&ctx)
+            .contains("ご請求残高: 1,234,567円"));
+
+        assert!(MailTemplate::from_str("{year}").is_err());
+        assert!(MailTemplate::from_str("{amount:05}").is_err());
+    }
+}
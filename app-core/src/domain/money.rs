@@ -0,0 +1,202 @@
+use crate::domain::currency::Currency;
+use crate::domain::invoice::TaxRounding;
+use bigdecimal::{BigDecimal, Signed, Zero};
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+/// 金額。通貨の最小単位（円、セント）の整数を桁あふれしないように BigDecimal で持つ
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Money(BigDecimal);
+
+impl Money {
+    fn new(v: BigDecimal) -> Money {
+        Money(v.with_scale(0))
+    }
+
+    pub fn zero() -> Money {
+        Money(BigDecimal::zero())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.0.is_positive()
+    }
+
+    pub fn times(&self, quantity: i32) -> Money {
+        Money::new(&self.0 * BigDecimal::from(quantity))
+    }
+
+    /// self × numerator / denominator を丸める
+    pub fn ratio(&self, numerator: i64, denominator: i64, rounding: &TaxRounding) -> Money {
+        let v = &self.0 * BigDecimal::from(numerator) / BigDecimal::from(denominator);
+        Money::new(rounding.round(&v))
+    }
+
+    /// self × percent / 100 を丸める
+    pub fn percent(&self, percent: &Money, rounding: &TaxRounding) -> Money {
+        let v = &self.0 * &percent.0 / BigDecimal::from(100);
+        Money::new(rounding.round(&v))
+    }
+
+    /// 通貨単位の金額（"12.50"ドル）を最小単位（1250セント）にする。最小単位未満の端数はエラー
+    pub fn from_major(s: &str, currency: &Currency) -> Result<Money, String> {
+        let major = BigDecimal::from_str(s.trim()).map_err(|e| format!("{}: {}", s, e))?;
+        let minor = major * BigDecimal::from(10i64.pow(currency.minor_unit_digits()));
+        if !minor.is_integer() {
+            return Err(format!(
+                "{}は{}の最小単位未満の端数を含んでいます",
+                s,
+                currency.code()
+            ));
+        }
+        Ok(Money::new(minor))
+    }
+
+    /// 最小単位の金額を通貨単位の文字列（1250セント → "12.50"）にする
+    pub fn to_major_string(&self, currency: &Currency) -> String {
+        let (int_val, _) = self.0.with_scale(0).as_bigint_and_exponent();
+        BigDecimal::new(int_val, currency.minor_unit_digits() as i64).to_string()
+    }
+}
+
+impl From<i32> for Money {
+    fn from(v: i32) -> Money {
+        Money(BigDecimal::from(v))
+    }
+}
+
+impl From<i64> for Money {
+    fn from(v: i64) -> Money {
+        Money(BigDecimal::from(v))
+    }
+}
+
+impl TryFrom<BigDecimal> for Money {
+    type Error = String;
+
+    fn try_from(v: BigDecimal) -> Result<Self, Self::Error> {
+        if !v.is_integer() {
+            return Err(format!("{}は整数ではありません", v));
+        }
+        Ok(Money::new(v))
+    }
+}
+
+impl From<Money> for BigDecimal {
+    fn from(v: Money) -> BigDecimal {
+        v.0
+    }
+}
+
+impl FromStr for Money {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v = BigDecimal::from_str(s.trim()).map_err(|e| format!("{}: {}", s, e))?;
+        Money::try_from(v)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money::new(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money::new(self.0 - other.0)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money::new(-self.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::zero(), |acc, v| acc + v)
+    }
+}
+
+#[cfg(test)]
+mod money_tests {
+    use crate::domain::currency::Currency;
+    use crate::domain::invoice::TaxRounding;
+    use crate::domain::money::Money;
+    use std::str::FromStr;
+
+    #[test]
+    fn parse_major_amount() {
+        assert_eq!(
+            Money::from_major("1000.0", &Currency::JPY),
+            Ok(Money::from(1000))
+        );
+        assert_eq!(
+            Money::from_major("12.5", &Currency::USD),
+            Ok(Money::from(1250))
+        );
+        assert!(Money::from_major("12.345", &Currency::USD).is_err());
+        assert!(Money::from_major("1000.5", &Currency::JPY).is_err());
+        assert!(Money::from_major("abc", &Currency::JPY).is_err());
+
+        assert_eq!(Money::from(1250).to_major_string(&Currency::USD), "12.50");
+        assert_eq!(Money::from(-5).to_major_string(&Currency::EUR), "-0.05");
+        assert_eq!(Money::from(1000).to_major_string(&Currency::JPY), "1000");
+
+        // i32の上限（約21億円）を超えても桁あふれしない
+        let large = Money::from_str("3000000000").unwrap();
+        assert_eq!(large.times(3).to_string(), "9000000000");
+        assert_eq!(
+            vec![large.clone(), large.clone()]
+                .into_iter()
+                .sum::<Money>(),
+            Money::from(6_000_000_000i64)
+        );
+        assert!(Money::from_str("1.5").is_err());
+    }
+
+    #[test]
+    fn ratio() {
+        let amount = Money::from(1005);
+        assert_eq!(amount.ratio(1, 10, &TaxRounding::Floor), Money::from(100));
+        assert_eq!(amount.ratio(1, 10, &TaxRounding::Round), Money::from(101));
+        assert_eq!(amount.ratio(1, 10, &TaxRounding::Ceil), Money::from(101));
+        assert_eq!(
+            Money::from(1000).ratio(1, 10, &TaxRounding::Ceil),
+            Money::from(100)
+        );
+        assert_eq!(
+            Money::from(-1005).ratio(1, 10, &TaxRounding::Round),
+            Money::from(-101)
+        );
+        assert_eq!(
+            Money::from(-1001).ratio(1, 10, &TaxRounding::Ceil),
+            Money::from(-101)
+        );
+        assert_eq!(
+            Money::from(10000).ratio(1, 3, &TaxRounding::Round),
+            Money::from(3333)
+        );
+    }
+}
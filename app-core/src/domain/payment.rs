@@ -0,0 +1,84 @@
+use crate::domain::money::Money;
+use crate::domain::YMD;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 請求書に対する入金。1つの請求書に分割で複数回入金されることがある
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Payment {
+    pub id: String,
+    pub invoice_id: String,
+    /// 入金日
+    pub received_ymd: YMD,
+    /// 請求書の通貨の最小単位
+    pub amount: Money,
+    pub method: PaymentMethod,
+    pub memo: String,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl Payment {
+    pub fn new(
+        invoice_id: String,
+        received_ymd: YMD,
+        amount: Money,
+        method: PaymentMethod,
+        memo: String,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        if received_ymd.is_empty() {
+            return Err("入金日を入力してください".to_string());
+        }
+        if !amount.is_positive() {
+            return Err("入金額は正の値を入力してください".to_string());
+        }
+        Ok(Payment {
+            id: Uuid::new_v4().to_string(),
+            invoice_id,
+            received_ymd,
+            amount,
+            method,
+            memo,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PaymentMethod {
+    BankTransfer,
+    Cash,
+    CreditCard,
+    Other,
+}
+
+impl PaymentMethod {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::BankTransfer => 0,
+            Self::Cash => 1,
+            Self::CreditCard => 2,
+            Self::Other => 3,
+        }
+    }
+}
+
+impl Default for PaymentMethod {
+    fn default() -> Self {
+        Self::BankTransfer
+    }
+}
+
+impl From<i32> for PaymentMethod {
+    fn from(v: i32) -> PaymentMethod {
+        match v {
+            0 => Self::BankTransfer,
+            1 => Self::Cash,
+            2 => Self::CreditCard,
+            3 => Self::Other,
+            _ => Self::default(),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::str::FromStr;
+use uuid::Uuid;
+
+const MAX_OFFSET_DAYS: i64 = 365;
+
+pub const DEFAULT_SUBJECT_TEMPLATE: &str = "【お支払いのお願い】{subject}（{invoice_number}）";
+pub const DEFAULT_BODY_TEMPLATE: &str = "{supplier} ご担当者様
+
+いつもお世話になっております。{sender}です。
+
+下記の請求書について、お支払い期限は{due}となっております。
+本メールと行き違いでお支払いいただいている場合はご容赦ください。
+
+件名: {subject}
+請求書番号: {invoice_number}
+発行日: {issue}
+お支払い期限: {due}
+ご請求残高: {amount}
+
+ご確認のほどよろしくお願いいたします。
+";
+
+/// 督促メールを送る日。支払期限からの日数をカンマ区切りで書き、負の値は期限前（`-3,0,7,30`）。空の場合は督促しない
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ReminderSchedule {
+    offsets: Vec<i64>,
+}
+
+impl FromStr for ReminderSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut offsets = s
+            .split(',')
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                let offset = v
+                    .parse::<i64>()
+                    .map_err(|_e| format!("{}は日数ではありません", v))?;
+                if offset.abs() > MAX_OFFSET_DAYS {
+                    return Err(format!(
+                        "督促は支払期限の前後{}日以内で指定してください",
+                        MAX_OFFSET_DAYS
+                    ));
+                }
+                Ok(offset)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        offsets.sort_unstable();
+        offsets.dedup();
+
+        Ok(ReminderSchedule { offsets })
+    }
+}
+
+impl ReminderSchedule {
+    /// 請求先ごとの設定があればそれを、なければユーザーの設定を使う
+    pub fn of(user_schedule: &str, supplier_schedule: &str) -> Result<Self, String> {
+        if supplier_schedule.trim().is_empty() {
+            ReminderSchedule::from_str(user_schedule)
+        } else {
+            ReminderSchedule::from_str(supplier_schedule)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// today に送る督促の期限からの日数。バッチが止まっていた場合も、過ぎた段階のうち最も遅いものを1回だけ送る。
+    /// 期限前の段階は期限を過ぎたら送らない
+    pub fn due_offset(&self, due: NaiveDate, today: NaiveDate) -> Option<i64> {
+        let elapsed = (today - due).num_days();
+        self.offsets
+            .iter()
+            .rev()
+            .find(|v| **v <= elapsed)
+            .filter(|v| **v > 0 || elapsed <= 0)
+            .copied()
+    }
+}
+
+impl ToString for ReminderSchedule {
+    fn to_string(&self) -> String {
+        self.offsets
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// 送信済みの督促。請求書と期限からの日数の組で一意にして、同じ督促を二度送らないようにする
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvoiceReminder {
+    pub id: String,
+    pub invoice_id: String,
+    /// 支払期限からの日数（督促スケジュールの段階）
+    pub offset_days: i64,
+    pub sent_to: String,
+    pub subject: String,
+    pub sent_at: chrono::NaiveDateTime,
+}
+
+impl InvoiceReminder {
+    pub fn new(
+        invoice_id: String,
+        offset_days: i64,
+        sent_to: String,
+        subject: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        InvoiceReminder {
+            id: Uuid::new_v4().to_string(),
+            invoice_id,
+            offset_days,
+            sent_to,
+            subject,
+            sent_at: now.naive_utc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod reminder_tests {
+    use crate::domain::reminder::ReminderSchedule;
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    #[test]
+    fn parse_schedule() {
+        assert_eq!(
+            ReminderSchedule::from_str(" 30, -3,0,7, 7")
+                .unwrap()
+                .to_string(),
+            "-3,0,7,30"
+        );
+        assert!(ReminderSchedule::from_str("").unwrap().is_empty());
+        assert!(ReminderSchedule::from_str("3日後").is_err());
+        assert!(ReminderSchedule::from_str("400").is_err());
+
+        assert_eq!(
+            ReminderSchedule::of("-3,0,7,30", "").unwrap().to_string(),
+            "-3,0,7,30"
+        );
+        assert_eq!(
+            ReminderSchedule::of("-3,0,7,30", "14").unwrap().to_string(),
+            "14"
+        );
+    }
+
+    #[test]
+    fn due_offset() {
+        let schedule = ReminderSchedule::from_str("-3,0,7,30").unwrap();
+        let due = NaiveDate::from_ymd(2021, 8, 31);
+        let day = |m: u32, d: u32| NaiveDate::from_ymd(2021, m, d);
+
+        assert_eq!(schedule.due_offset(due, day(8, 27)), None);
+        assert_eq!(schedule.due_offset(due, day(8, 28)), Some(-3));
+        assert_eq!(schedule.due_offset(due, day(8, 30)), Some(-3));
+        assert_eq!(schedule.due_offset(due, day(8, 31)), Some(0));
+        // 期限を過ぎたら期限前・期限日の督促は送らない
+        assert_eq!(schedule.due_offset(due, day(9, 1)), None);
+        assert_eq!(schedule.due_offset(due, day(9, 7)), Some(7));
+        // バッチが止まっていても最も遅い段階を送る
+        assert_eq!(schedule.due_offset(due, day(10, 15)), Some(30));
+
+        let before_only = ReminderSchedule::from_str("-3").unwrap();
+        assert_eq!(before_only.due_offset(due, day(9, 10)), None);
+        assert_eq!(
+            ReminderSchedule::default().due_offset(due, day(8, 31)),
+            None
+        );
+    }
+}
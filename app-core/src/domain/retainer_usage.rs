@@ -0,0 +1,32 @@
+use crate::domain::YM;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// リテイナー契約の請求先の月ごとの利用実績（時間数・チケット数など）
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RetainerUsage {
+    pub id: String,
+    pub supplier_id: String,
+    pub ym: YM,
+    pub quantity: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl RetainerUsage {
+    pub fn new(supplier_id: String, ym: YM, quantity: i32, now: DateTime<Utc>) -> Self {
+        RetainerUsage {
+            id: Uuid::new_v4().to_string(),
+            supplier_id,
+            ym,
+            quantity,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        }
+    }
+
+    pub fn update(&mut self, quantity: i32, now: DateTime<Utc>) {
+        self.quantity = quantity;
+        self.updated_at = now.naive_utc();
+    }
+}
@@ -1,4 +1,6 @@
+use crate::domain::subject_template::SubjectTemplate;
 use chrono::{DateTime, Utc};
+use std::str::FromStr;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -10,6 +12,10 @@ pub struct Sender {
     pub tel: String,
     pub postal_code: String,
     pub address: String,
+    /// 適格請求書発行事業者の登録番号（T + 13桁）
+    pub registration_number: String,
+    /// 請求書の備考の既定のテンプレート。件名と同じプレースホルダーを使える
+    pub remarks_template: String,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -22,9 +28,13 @@ impl Sender {
         tel: String,
         postal_code: String,
         address: String,
+        registration_number: String,
+        remarks_template: String,
         now: DateTime<Utc>,
-    ) -> Self {
-        Sender {
+    ) -> Result<Self, String> {
+        validate_registration_number(&registration_number)?;
+        SubjectTemplate::from_str(remarks_template.as_str())?;
+        Ok(Sender {
             id: Uuid::new_v4().to_string(),
             user_id,
             name,
@@ -32,9 +42,11 @@ impl Sender {
             tel,
             postal_code,
             address,
+            registration_number,
+            remarks_template,
             created_at: now.naive_utc(),
             updated_at: now.naive_utc(),
-        }
+        })
     }
 
     pub fn update(
@@ -44,13 +56,104 @@ impl Sender {
         tel: String,
         postal_code: String,
         address: String,
+        registration_number: String,
+        remarks_template: String,
         now: DateTime<Utc>,
-    ) {
+    ) -> Result<(), String> {
+        validate_registration_number(&registration_number)?;
+        SubjectTemplate::from_str(remarks_template.as_str())?;
         self.name = name;
         self.email = email;
         self.tel = tel;
         self.postal_code = postal_code;
         self.address = address;
+        self.registration_number = registration_number;
+        self.remarks_template = remarks_template;
         self.updated_at = now.naive_utc();
+        Ok(())
+    }
+
+    /// 適格請求書として発行するのに必要な項目が揃っているか
+    pub fn validate_for_qualified_invoice(&self) -> Result<(), String> {
+        let mut missing = vec![];
+        if self.name.is_empty() {
+            missing.push("差出人名");
+        }
+        if validate_registration_number(&self.registration_number).is_err() {
+            missing.push("登録番号");
+        }
+        if !missing.is_empty() {
+            return Err(format!(
+                "適格請求書の必須項目が未設定です: {}",
+                missing.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 登録番号は「T」と法人番号と同じ形式の13桁の数字で、先頭1桁がチェックデジット
+pub fn validate_registration_number(v: &str) -> Result<(), String> {
+    let invalid = || "登録番号はTと13桁の数字で入力してください".to_string();
+
+    let digits = v.strip_prefix('T').ok_or_else(invalid)?;
+    if digits.len() != 13 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let numbers = digits
+        .chars()
+        .map(|c| c.to_digit(10).unwrap())
+        .collect::<Vec<_>>();
+    let sum: u32 = numbers[1..]
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, n)| if i % 2 == 0 { *n } else { n * 2 })
+        .sum();
+    if numbers[0] != 9 - sum % 9 {
+        return Err("登録番号のチェックデジットが正しくありません".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod sender_tests {
+    use crate::domain::sender::{validate_registration_number, Sender};
+    use chrono::Utc;
+
+    #[test]
+    fn registration_number() {
+        assert!(validate_registration_number("T7000012050002").is_ok());
+        assert!(validate_registration_number("T1234567890123").is_err());
+        assert!(validate_registration_number("7000012050002").is_err());
+        assert!(validate_registration_number("T700001205000").is_err());
+        assert!(validate_registration_number("T700001205000A").is_err());
+        assert!(validate_registration_number("").is_err());
+    }
+
+    #[test]
+    fn validate_for_qualified_invoice() {
+        let now = Utc::now();
+        let mut sender = Sender::new(
+            "".to_string(),
+            "株式会社テスト".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "T7000012050002".to_string(),
+            "".to_string(),
+            now,
+        )
+        .unwrap();
+        assert!(sender.validate_for_qualified_invoice().is_ok());
+
+        sender.name = "".to_string();
+        sender.registration_number = "".to_string();
+        assert_eq!(
+            sender.validate_for_qualified_invoice(),
+            Err("適格請求書の必須項目が未設定です: 差出人名, 登録番号".to_string())
+        );
     }
 }
@@ -0,0 +1,297 @@
+use chrono::{Datelike, NaiveDate};
+use std::str::FromStr;
+
+/// 件名のテンプレート。`{名前}` または `{名前:0桁数}`（ゼロ埋め）で値を埋め込み、`{{` `}}` で波括弧そのものを書く
+///
+/// | 名前 | 内容 |
+/// | --- | --- |
+/// | `S` `subject` | 件名 |
+/// | `D` `period` | 請求対象の期間（2021年8月分、2021年4月〜6月分など） |
+/// | `supplier` | 請求先名 |
+/// | `year` `month` | 請求対象月 |
+/// | `era` `era_year` | 請求対象月の元号（令和）と和暦の年 |
+/// | `start` `end` | 請求期間の開始日・終了日（2021年8月1日） |
+/// | `start_month` `start_day` `end_month` `end_day` | 請求期間の開始日・終了日の月と日 |
+/// | `invoice_number` | 請求書番号（misocaで採番する場合は空） |
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SubjectTemplate {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder, Option<usize>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Placeholder {
+    Subject,
+    Period,
+    Supplier,
+    Year,
+    Month,
+    Era,
+    EraYear,
+    Start,
+    End,
+    StartMonth,
+    StartDay,
+    EndMonth,
+    EndDay,
+    InvoiceNumber,
+}
+
+impl Placeholder {
+    fn parse(name: &str) -> Option<Placeholder> {
+        match name {
+            "S" | "subject" => Some(Self::Subject),
+            "D" | "period" => Some(Self::Period),
+            "supplier" => Some(Self::Supplier),
+            "year" => Some(Self::Year),
+            "month" => Some(Self::Month),
+            "era" => Some(Self::Era),
+            "era_year" => Some(Self::EraYear),
+            "start" => Some(Self::Start),
+            "end" => Some(Self::End),
+            "start_month" => Some(Self::StartMonth),
+            "start_day" => Some(Self::StartDay),
+            "end_month" => Some(Self::EndMonth),
+            "end_day" => Some(Self::EndDay),
+            "invoice_number" => Some(Self::InvoiceNumber),
+            _ => None,
+        }
+    }
+
+    /// ゼロ埋めできる数値か
+    fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Self::Year
+                | Self::Month
+                | Self::EraYear
+                | Self::StartMonth
+                | Self::StartDay
+                | Self::EndMonth
+                | Self::EndDay
+        )
+    }
+}
+
+/// テンプレートに埋め込む値
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SubjectContext {
+    pub subject: String,
+    pub period: String,
+    pub supplier_name: String,
+    pub year: u32,
+    pub month: u32,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// 件名の作成時点で採番されていない場合は空
+    pub invoice_number: String,
+}
+
+impl FromStr for SubjectTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments = tokenize(s)?
+            .into_iter()
+            .map(|token| match token {
+                Token::Text(text) => Ok(Segment::Text(text)),
+                Token::Placeholder(name, width) => {
+                    let placeholder = Placeholder::parse(name.as_str())
+                        .ok_or(format!("{{{}}}は使用できないプレースホルダーです", name))?;
+                    if width.is_some() && !placeholder.is_numeric() {
+                        return Err(format!("{{{}}}はゼロ埋めできません", name));
+                    }
+                    Ok(Segment::Placeholder(placeholder, width))
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(SubjectTemplate { segments })
+    }
+}
+
+/// テンプレートの固定の文字列とプレースホルダー（名前とゼロ埋めの桁数）
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Token {
+    Text(String),
+    Placeholder(String, Option<usize>),
+}
+
+/// `{名前}` `{名前:0桁数}` `{{` `}}` を解釈してテンプレートを分割する。名前の検証は呼び出し側で行う
+pub fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut text = String::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '}' => return Err("対応する{がない}があります".to_string()),
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => inner.push(c),
+                        None => return Err("{が閉じられていません".to_string()),
+                    }
+                }
+                if !text.is_empty() {
+                    tokens.push(Token::Text(text.clone()));
+                    text.clear();
+                }
+                tokens.push(parse_placeholder(&inner)?);
+            }
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+
+    Ok(tokens)
+}
+
+fn parse_placeholder(inner: &str) -> Result<Token, String> {
+    let mut parts = inner.splitn(2, ':');
+    let name = parts.next().unwrap_or("");
+
+    let width = match parts.next() {
+        None => None,
+        Some(format) => Some(
+            format
+                .strip_prefix('0')
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .ok_or(format!("{{{}}}の書式が正しくありません", inner))?,
+        ),
+    };
+
+    Ok(Token::Placeholder(name.to_string(), width))
+}
+
+/// 桁数の指定があればゼロ埋めする
+pub fn pad(value: String, width: &Option<usize>) -> String {
+    match width {
+        Some(width) => format!("{:0>width$}", value, width = width),
+        None => value,
+    }
+}
+
+impl SubjectTemplate {
+    pub fn render(&self, ctx: &SubjectContext) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Placeholder(placeholder, width) => pad(value_of(placeholder, ctx), width),
+            })
+            .collect()
+    }
+}
+
+fn value_of(placeholder: &Placeholder, ctx: &SubjectContext) -> String {
+    match placeholder {
+        Placeholder::Subject => ctx.subject.clone(),
+        Placeholder::Period => ctx.period.clone(),
+        Placeholder::Supplier => ctx.supplier_name.clone(),
+        Placeholder::Year => ctx.year.to_string(),
+        Placeholder::Month => ctx.month.to_string(),
+        Placeholder::Era => era(ctx.year, ctx.month).0.to_string(),
+        Placeholder::EraYear => era(ctx.year, ctx.month).1.to_string(),
+        Placeholder::Start => format_date(&ctx.start),
+        Placeholder::End => format_date(&ctx.end),
+        Placeholder::StartMonth => ctx.start.month().to_string(),
+        Placeholder::StartDay => ctx.start.day().to_string(),
+        Placeholder::EndMonth => ctx.end.month().to_string(),
+        Placeholder::EndDay => ctx.end.day().to_string(),
+        Placeholder::InvoiceNumber => ctx.invoice_number.clone(),
+    }
+}
+
+/// 月単位で判定するため、2019年5月以降を令和とする
+fn era(year: u32, month: u32) -> (&'static str, u32) {
+    if (year, month) >= (2019, 5) {
+        ("令和", year - 2018)
+    } else {
+        ("平成", year - 1988)
+    }
+}
+
+fn format_date(date: &NaiveDate) -> String {
+    format!("{}年{}月{}日", date.year(), date.month(), date.day())
+}
+
+#[cfg(test)]
+mod subject_template_tests {
+    use crate::domain::subject_template::{SubjectContext, SubjectTemplate};
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    fn context() -> SubjectContext {
+        SubjectContext {
+            subject: "保守".to_string(),
+            period: "2021年8月分".to_string(),
+            supplier_name: "株式会社サンプル".to_string(),
+            year: 2021,
+            month: 8,
+            start: NaiveDate::from_ymd(2021, 7, 21),
+            end: NaiveDate::from_ymd(2021, 8, 20),
+            invoice_number: "INV-0001".to_string(),
+        }
+    }
+
+    fn render(template: &str) -> String {
+        SubjectTemplate::from_str(template)
+            .unwrap()
+            .render(&context())
+    }
+
+    #[test]
+    fn render_placeholders() {
+        assert_eq!(render("{D} {S}"), "2021年8月分 保守");
+        assert_eq!(
+            render("{supplier}様 {subject} {year}/{month:02}"),
+            "株式会社サンプル様 保守 2021/08"
+        );
+        assert_eq!(render("{era}{era_year}年{month}月分"), "令和3年8月分");
+        assert_eq!(render("{start}〜{end}"), "2021年7月21日〜2021年8月20日");
+        assert_eq!(
+            render("{start_month:02}{start_day:02}-{end_month:02}{end_day:02}"),
+            "0721-0820"
+        );
+        assert_eq!(render("{{{invoice_number}}}"), "{INV-0001}");
+        assert_eq!(
+            SubjectTemplate::from_str("{era}{era_year}年")
+                .unwrap()
+                .render(&SubjectContext {
+                    year: 2019,
+                    month: 4,
+                    ..context()
+                }),
+            "平成31年"
+        );
+    }
+
+    #[test]
+    fn reject_invalid_template() {
+        assert!(SubjectTemplate::from_str("").is_ok());
+        assert!(SubjectTemplate::from_str("{unknown}").is_err());
+        assert!(SubjectTemplate::from_str("{year").is_err());
+        assert!(SubjectTemplate::from_str("year}").is_err());
+        assert!(SubjectTemplate::from_str("{subject:02}").is_err());
+        assert!(SubjectTemplate::from_str("{month:2}").is_err());
+    }
+}
@@ -1,11 +1,24 @@
-use crate::domain::invoice::Invoice;
-use crate::domain::YM;
-use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use crate::domain::calendar::{self, BusinessDayAdjustment};
+use crate::domain::currency::Currency;
+use crate::domain::invoice::{Invoice, InvoiceItem, TaxRounding, TaxSetting, TaxType};
+use crate::domain::invoice_delivery::DeliverySetting;
+use crate::domain::money::Money;
+use crate::domain::retainer_usage::RetainerUsage;
+use crate::domain::subject_template::{SubjectContext, SubjectTemplate};
+use crate::domain::supplier_item::SupplierItem;
+use crate::domain::supplier_milestone::SupplierMilestone;
+use crate::domain::supplier_price::{self, SupplierPrice};
+use crate::domain::supplier_suspension::SupplierSuspension;
+use crate::domain::working_hours::WorkingHours;
+use crate::domain::{YM, YMD};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::cmp;
+use std::str::FromStr;
 use uuid::Uuid;
 
-const CONSUMPTION_TAX_RATE: f64 = 0.1;
 const DATE_PLACEHOLDER: &str = "{D}";
 const SUBJECT_PLACEHOLDER: &str = "{S}";
+const LUMP_SUM_UNIT: &str = "式";
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Supplier {
@@ -14,11 +27,33 @@ pub struct Supplier {
     pub contact_id: String,
     pub contact_group_id: String,
     pub name: String,
-    pub billing_amount: i32,
+    /// currency の最小単位（円、セント）での金額
+    pub billing_amount: Money,
+    pub currency: Currency,
     pub billing_type: BillingType,
     pub end_ym: YM,
     pub subject: String,
     pub subject_template: String,
+    /// 請求書の備考のテンプレート。空の場合は差出人の既定のテンプレートを使う
+    pub remarks_template: String,
+    /// 請求書番号の `{prefix}` に埋め込む請求先ごとの接頭辞
+    pub invoice_number_prefix: String,
+    /// 請求書・督促メールの送付先。空の場合は督促しない
+    pub billing_email: String,
+    /// 督促メールを送る日。空の場合はユーザーの設定を使う
+    pub reminder_schedule: String,
+    pub delivery: DeliverySetting,
+    pub closing_day: ClosingDay,
+    pub payment_term: PaymentTerm,
+    pub business_day_adjustment: BusinessDayAdjustment,
+    pub settlement_range: SettlementRange,
+    pub retainer: RetainerAllowance,
+    pub recurrence: Recurrence,
+    pub proration: Proration,
+    pub tax_setting: TaxSetting,
+    pub withholding_tax: bool,
+    /// アーカイブ済みの請求先には請求書を発行しない
+    pub archived: bool,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -35,9 +70,22 @@ impl Supplier {
         contact_id: String,
         contact_group_id: String,
         name: String,
-        billing_amount: i32,
+        billing_amount: Money,
+        currency: Currency,
+        recurrence: Recurrence,
+        proration: Proration,
         subject: String,
         subject_template: String,
+        remarks_template: String,
+        invoice_number_prefix: String,
+        billing_email: String,
+        reminder_schedule: String,
+        delivery: DeliverySetting,
+        closing_day: ClosingDay,
+        payment_term: PaymentTerm,
+        business_day_adjustment: BusinessDayAdjustment,
+        tax_setting: TaxSetting,
+        withholding_tax: bool,
         now: DateTime<Utc>,
     ) -> Self {
         Supplier {
@@ -47,10 +95,26 @@ impl Supplier {
             contact_group_id,
             name,
             billing_amount,
+            currency,
             billing_type: BillingType::Monthly,
             end_ym: YM { year: 0, month: 0 },
             subject,
             subject_template,
+            remarks_template,
+            invoice_number_prefix,
+            billing_email,
+            reminder_schedule,
+            delivery,
+            closing_day,
+            payment_term,
+            business_day_adjustment,
+            settlement_range: SettlementRange::default(),
+            retainer: RetainerAllowance::default(),
+            recurrence,
+            proration,
+            tax_setting,
+            withholding_tax,
+            archived: false,
             created_at: now.naive_utc(),
             updated_at: now.naive_utc(),
         }
@@ -61,10 +125,21 @@ impl Supplier {
         contact_id: String,
         contact_group_id: String,
         name: String,
-        billing_amount: i32,
+        billing_amount: Money,
+        currency: Currency,
         end_ym: YM,
         subject: String,
         subject_template: String,
+        remarks_template: String,
+        invoice_number_prefix: String,
+        billing_email: String,
+        reminder_schedule: String,
+        delivery: DeliverySetting,
+        closing_day: ClosingDay,
+        payment_term: PaymentTerm,
+        business_day_adjustment: BusinessDayAdjustment,
+        tax_setting: TaxSetting,
+        withholding_tax: bool,
         now: DateTime<Utc>,
     ) -> Self {
         Supplier {
@@ -74,10 +149,189 @@ impl Supplier {
             contact_group_id,
             name,
             billing_amount,
+            currency,
             billing_type: BillingType::OneTime,
             end_ym,
             subject,
             subject_template,
+            remarks_template,
+            invoice_number_prefix,
+            billing_email,
+            reminder_schedule,
+            delivery,
+            closing_day,
+            payment_term,
+            business_day_adjustment,
+            settlement_range: SettlementRange::default(),
+            retainer: RetainerAllowance::default(),
+            recurrence: Recurrence::default(),
+            proration: Proration::default(),
+            tax_setting,
+            withholding_tax,
+            archived: false,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        }
+    }
+
+    pub fn new_as_hourly(
+        user_id: String,
+        contact_id: String,
+        contact_group_id: String,
+        name: String,
+        billing_amount: Money,
+        currency: Currency,
+        settlement_range: SettlementRange,
+        subject: String,
+        subject_template: String,
+        remarks_template: String,
+        invoice_number_prefix: String,
+        billing_email: String,
+        reminder_schedule: String,
+        delivery: DeliverySetting,
+        closing_day: ClosingDay,
+        payment_term: PaymentTerm,
+        business_day_adjustment: BusinessDayAdjustment,
+        tax_setting: TaxSetting,
+        withholding_tax: bool,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Supplier {
+            id: Uuid::new_v4().to_string(),
+            user_id,
+            contact_id,
+            contact_group_id,
+            name,
+            billing_amount,
+            currency,
+            billing_type: BillingType::Hourly,
+            end_ym: YM { year: 0, month: 0 },
+            subject,
+            subject_template,
+            remarks_template,
+            invoice_number_prefix,
+            billing_email,
+            reminder_schedule,
+            delivery,
+            closing_day,
+            payment_term,
+            business_day_adjustment,
+            settlement_range,
+            retainer: RetainerAllowance::default(),
+            recurrence: Recurrence::default(),
+            proration: Proration::default(),
+            tax_setting,
+            withholding_tax,
+            archived: false,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        }
+    }
+
+    /// billing_amount は毎月の固定額で、含まれる利用量を超えた分を超過単価で請求する
+    pub fn new_as_retainer(
+        user_id: String,
+        contact_id: String,
+        contact_group_id: String,
+        name: String,
+        billing_amount: Money,
+        currency: Currency,
+        retainer: RetainerAllowance,
+        subject: String,
+        subject_template: String,
+        remarks_template: String,
+        invoice_number_prefix: String,
+        billing_email: String,
+        reminder_schedule: String,
+        delivery: DeliverySetting,
+        closing_day: ClosingDay,
+        payment_term: PaymentTerm,
+        business_day_adjustment: BusinessDayAdjustment,
+        tax_setting: TaxSetting,
+        withholding_tax: bool,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Supplier {
+            id: Uuid::new_v4().to_string(),
+            user_id,
+            contact_id,
+            contact_group_id,
+            name,
+            billing_amount,
+            currency,
+            billing_type: BillingType::Retainer,
+            end_ym: YM { year: 0, month: 0 },
+            subject,
+            subject_template,
+            remarks_template,
+            invoice_number_prefix,
+            billing_email,
+            reminder_schedule,
+            delivery,
+            closing_day,
+            payment_term,
+            business_day_adjustment,
+            settlement_range: SettlementRange::default(),
+            retainer,
+            recurrence: Recurrence::default(),
+            proration: Proration::default(),
+            tax_setting,
+            withholding_tax,
+            archived: false,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        }
+    }
+
+    /// billing_amount は契約金額の総額で、マイルストーンごとに分割して請求する
+    pub fn new_as_milestone(
+        user_id: String,
+        contact_id: String,
+        contact_group_id: String,
+        name: String,
+        billing_amount: Money,
+        currency: Currency,
+        subject: String,
+        subject_template: String,
+        remarks_template: String,
+        invoice_number_prefix: String,
+        billing_email: String,
+        reminder_schedule: String,
+        delivery: DeliverySetting,
+        closing_day: ClosingDay,
+        payment_term: PaymentTerm,
+        business_day_adjustment: BusinessDayAdjustment,
+        tax_setting: TaxSetting,
+        withholding_tax: bool,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Supplier {
+            id: Uuid::new_v4().to_string(),
+            user_id,
+            contact_id,
+            contact_group_id,
+            name,
+            billing_amount,
+            currency,
+            billing_type: BillingType::Milestone,
+            end_ym: YM { year: 0, month: 0 },
+            subject,
+            subject_template,
+            remarks_template,
+            invoice_number_prefix,
+            billing_email,
+            reminder_schedule,
+            delivery,
+            closing_day,
+            payment_term,
+            business_day_adjustment,
+            settlement_range: SettlementRange::default(),
+            retainer: RetainerAllowance::default(),
+            recurrence: Recurrence::default(),
+            proration: Proration::default(),
+            tax_setting,
+            withholding_tax,
+            archived: false,
             created_at: now.naive_utc(),
             updated_at: now.naive_utc(),
         }
@@ -88,190 +342,1999 @@ impl Supplier {
         contact_id: String,
         contact_group_id: String,
         name: String,
-        billing_amount: i32,
+        billing_amount: Money,
+        currency: Currency,
         end_ym: YM,
+        settlement_range: SettlementRange,
+        retainer: RetainerAllowance,
+        recurrence: Recurrence,
+        proration: Proration,
         subject: String,
         subject_template: String,
+        remarks_template: String,
+        invoice_number_prefix: String,
+        billing_email: String,
+        reminder_schedule: String,
+        delivery: DeliverySetting,
+        closing_day: ClosingDay,
+        payment_term: PaymentTerm,
+        business_day_adjustment: BusinessDayAdjustment,
+        tax_setting: TaxSetting,
+        withholding_tax: bool,
         now: DateTime<Utc>,
     ) {
         self.contact_id = contact_id;
         self.contact_group_id = contact_group_id;
         self.name = name;
-        self.billing_amount = billing_amount;
+        self.currency = currency;
         self.subject = subject;
         self.subject_template = subject_template;
+        self.remarks_template = remarks_template;
+        self.invoice_number_prefix = invoice_number_prefix;
+        self.billing_email = billing_email;
+        self.reminder_schedule = reminder_schedule;
+        self.delivery = delivery;
+        self.closing_day = closing_day;
+        self.payment_term = payment_term;
+        self.business_day_adjustment = business_day_adjustment;
+        self.tax_setting = tax_setting;
+        self.withholding_tax = withholding_tax;
         self.updated_at = now.naive_utc();
 
         if self.billing_type == BillingType::OneTime {
             self.end_ym = end_ym;
         }
+        if self.billing_type == BillingType::Hourly {
+            self.settlement_range = settlement_range;
+        }
+        if self.billing_type == BillingType::Retainer {
+            self.retainer = retainer;
+        }
+        if self.billing_type == BillingType::Monthly {
+            self.recurrence = recurrence;
+            self.proration = proration;
+        }
+        // 定期の請求金額は改定履歴で変える。マイルストーン請求の契約金額だけはここで変える
+        if self.billing_type == BillingType::Milestone {
+            self.billing_amount = billing_amount;
+        }
     }
 
-    pub fn billing_amount_include_tax(&self) -> i32 {
-        let tmp =
-            f64::from(self.billing_amount) + f64::from(self.billing_amount) * CONSUMPTION_TAX_RATE;
-        tmp.floor() as i32
+    pub fn archive(&mut self, now: DateTime<Utc>) {
+        self.archived = true;
+        self.updated_at = now.naive_utc();
     }
 
-    pub fn subject_in_this_month(&self, now: DateTime<Utc>) -> String {
-        let first_day_in_last_month = NaiveDate::from_ymd(now.year(), now.month() - 1, 1);
-        let target_year = first_day_in_last_month.year();
-        let target_month = first_day_in_last_month.month();
+    pub fn restore(&mut self, now: DateTime<Utc>) {
+        self.archived = false;
+        self.updated_at = now.naive_utc();
+    }
 
-        if self.subject_template.is_empty() {
-            return format!(
-                "{} ({}年{}月分)",
-                self.subject.clone(),
-                target_year,
-                target_month
-            );
-        }
-
-        self.subject_template
-            .replace(SUBJECT_PLACEHOLDER, &self.subject)
-            .replace(
-                DATE_PLACEHOLDER,
-                format!("{}年{}月分", target_year, target_month).as_str(),
-            )
+    /// ymを締め月とする請求に適用する金額。改定履歴がなければ登録時の請求金額
+    pub fn billing_amount_at(&self, ym: &YM, prices: &[SupplierPrice]) -> Money {
+        supplier_price::price_at(prices, ym)
+            .map_or(self.billing_amount.clone(), |v| v.billing_amount.clone())
     }
 
-    pub fn payment_date_in_this_month(&self, now: DateTime<Utc>) -> (String, String) {
-        let issue_date = now.format("%Y-%m-%d").to_string();
-        let first_day_in_next_month = NaiveDate::from_ymd(now.year(), now.month() + 1, 1);
-        let last_day_in_month = first_day_in_next_month - Duration::hours(24);
-        let payment_due_on = last_day_in_month.format("%Y-%m-%d").to_string();
-        return (issue_date, payment_due_on);
+    pub fn with_price_at(&self, ym: &YM, prices: &[SupplierPrice]) -> Supplier {
+        Supplier {
+            billing_amount: self.billing_amount_at(ym, prices),
+            ..self.clone()
+        }
     }
-}
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub enum BillingType {
-    Monthly,
-    OneTime,
-}
+    /// 今日を含む期間に適用する金額
+    pub fn with_current_price(&self, prices: &[SupplierPrice], now: DateTime<Utc>) -> Supplier {
+        self.with_price_at(&self.next_billing_period(now).ym(), prices)
+    }
 
-impl BillingType {
-    pub fn int(&self) -> i32 {
-        match self {
-            Self::Monthly => 0,
-            Self::OneTime => 1,
+    /// 請求金額の変更を改定履歴に積む。締まった期間に遡らないよう今日を含む期間の締め月から適用し、
+    /// その月の改定がすでにあれば上書きする。金額が変わらなければ何もしない
+    pub fn reprice(
+        &self,
+        billing_amount: Money,
+        prices: &[SupplierPrice],
+        now: DateTime<Utc>,
+    ) -> Result<Option<SupplierPrice>, String> {
+        let ym = self.next_billing_period(now).ym();
+        if self.billing_amount_at(&ym, prices) == billing_amount {
+            return Ok(None);
+        }
+        match prices.iter().find(|v| v.effective_from == ym) {
+            Some(price) => {
+                let mut price = price.clone();
+                price.update(billing_amount, now)?;
+                Ok(Some(price))
+            }
+            None => SupplierPrice::new(self.id.clone(), ym, billing_amount, now).map(Some),
         }
     }
-}
 
-impl Default for BillingType {
-    fn default() -> Self {
-        Self::Monthly
+    /// 明細の登録があればそれを、なければ件名と請求金額の1行を基本とし、
+    /// 契約終了月で周期を打ち切った場合は月割りの調整行を、
+    /// 契約の開始・終了が期間の途中にある月は日割りの調整行を、時間精算の場合は超過・控除の精算行を、
+    /// リテイナー契約の場合は超過利用の行を加える
+    pub fn invoice_items(
+        &self,
+        subject: String,
+        supplier_items: Vec<SupplierItem>,
+        working_hours: Option<WorkingHours>,
+        usage: Option<RetainerUsage>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<InvoiceItem>, String> {
+        let mut items = if supplier_items.is_empty() {
+            vec![InvoiceItem {
+                name: subject,
+                quantity: 1,
+                unit: LUMP_SUM_UNIT.to_string(),
+                unit_price: self.billing_amount.clone(),
+                tax_type: self.tax_setting.tax_type.clone(),
+                withholding: self.withholding_tax,
+            }]
+        } else {
+            supplier_items
+                .iter()
+                .map(|v| v.to_invoice_item())
+                .collect::<Vec<_>>()
+        };
+
+        if self.billing_type == BillingType::Monthly {
+            let truncation = self.truncation_items(&items, now);
+            items.extend(truncation);
+            let proration = self.proration_items(&items, now);
+            items.extend(proration);
+        }
+
+        if self.billing_type == BillingType::Retainer {
+            let usage = usage.ok_or("利用実績が登録されていません".to_string())?;
+            let retainer = &self.retainer;
+
+            let overage = retainer.overage_quantity(usage.quantity);
+            if overage > 0 {
+                items.push(InvoiceItem {
+                    name: format!(
+                        "超過利用（契約{}{}を超えた分）",
+                        retainer.included_quantity, retainer.unit
+                    ),
+                    quantity: overage,
+                    unit: retainer.unit.clone(),
+                    unit_price: retainer.overage_rate.clone(),
+                    tax_type: self.tax_setting.tax_type.clone(),
+                    withholding: self.withholding_tax,
+                });
+            }
+            return Ok(items);
+        }
+
+        if self.billing_type != BillingType::Hourly {
+            return Ok(items);
+        }
+
+        let working_hours = working_hours.ok_or("稼働時間が登録されていません".to_string())?;
+        let range = &self.settlement_range;
+
+        let excess = range.excess_minutes(working_hours.minutes);
+        if excess > 0 {
+            items.push(InvoiceItem {
+                name: format!(
+                    "超過精算（{}時間 × {}）",
+                    format_hours(excess),
+                    format_rate(&range.excess_rate, &self.currency)
+                ),
+                quantity: 1,
+                unit: LUMP_SUM_UNIT.to_string(),
+                unit_price: range.excess_amount(working_hours.minutes),
+                tax_type: self.tax_setting.tax_type.clone(),
+                withholding: self.withholding_tax,
+            });
+        }
+
+        let shortage = range.shortage_minutes(working_hours.minutes);
+        if shortage > 0 {
+            items.push(InvoiceItem {
+                name: format!(
+                    "控除精算（{}時間 × {}）",
+                    format_hours(shortage),
+                    format_rate(&range.deduction_rate, &self.currency)
+                ),
+                quantity: 1,
+                unit: LUMP_SUM_UNIT.to_string(),
+                unit_price: -range.deduction_amount(working_hours.minutes),
+                tax_type: self.tax_setting.tax_type.clone(),
+                withholding: self.withholding_tax,
+            });
+        }
+
+        Ok(items)
     }
-}
 
-impl From<i32> for BillingType {
-    fn from(v: i32) -> BillingType {
-        match v {
-            0 => Self::Monthly,
-            1 => Self::OneTime,
-            _ => Self::default(),
+    /// 契約終了月で打ち切った周期は、請求周期の金額から含まれない月の分を差し引く
+    fn truncation_items(&self, items: &[InvoiceItem], now: DateTime<Utc>) -> Vec<InvoiceItem> {
+        match self
+            .recurrence
+            .covered_months(&self.billing_period(now).ym())
+        {
+            Some((covered_months, interval_months)) if covered_months < interval_months => self
+                .ratio_items(
+                    format!(
+                        "契約終了月までの月割り（{}ヶ月 / {}ヶ月）",
+                        covered_months, interval_months
+                    ),
+                    items,
+                    covered_months,
+                    interval_months,
+                ),
+            _ => vec![],
         }
     }
-}
 
-#[cfg(test)]
-mod supplier_tests {
-    use crate::domain::supplier::{BillingType, Supplier};
-    use crate::domain::YM;
-    use chrono::{NaiveDateTime, TimeZone, Utc};
+    /// 契約期間外の日数分を差し引く
+    fn proration_items(&self, items: &[InvoiceItem], now: DateTime<Utc>) -> Vec<InvoiceItem> {
+        let period = self.covered_period(&self.billing_period(now).ym());
+        let (active_days, total_days) = self.proration.days(&period);
+        if total_days == 0 || active_days >= total_days {
+            return vec![];
+        }
 
-    #[test]
-    fn billing_amount_include_tax() {
-        let now = Utc::now();
+        self.ratio_items(
+            format!(
+                "日割り調整（{}{}日 / {}日）",
+                self.proration.basis.label(),
+                active_days,
+                total_days
+            ),
+            items,
+            active_days,
+            total_days,
+        )
+    }
 
-        let supplier = Supplier {
-            id: "".to_string(),
-            user_id: "".to_string(),
-            contact_id: "".to_string(),
-            contact_group_id: "".to_string(),
-            name: "".to_string(),
-            billing_amount: 200000,
-            billing_type: BillingType::OneTime,
-            end_ym: YM { year: 0, month: 0 },
-            subject: "".to_string(),
-            subject_template: "".to_string(),
-            created_at: now.naive_utc(),
-            updated_at: now.naive_utc(),
-        };
+    /// 明細の金額を numerator / denominator にするための調整行。元の明細と同じ税率・源泉徴収で課税されるよう、
+    /// 税率と源泉徴収の組み合わせごとに1行ずつ作る。端数は消費税と同じ丸め方にする
+    fn ratio_items(
+        &self,
+        name: String,
+        items: &[InvoiceItem],
+        numerator: i64,
+        denominator: i64,
+    ) -> Vec<InvoiceItem> {
+        let mut groups: Vec<(TaxType, bool)> = vec![];
+        for item in items {
+            let group = (item.tax_type.clone(), item.withholding);
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
 
-        assert_eq!(supplier.billing_amount_include_tax(), 220000);
+        groups
+            .into_iter()
+            .filter_map(|(tax_type, withholding)| {
+                let amount: Money = items
+                    .iter()
+                    .filter(|v| v.tax_type == tax_type && v.withholding == withholding)
+                    .map(|v| v.amount())
+                    .sum();
+                let prorated = amount.ratio(numerator, denominator, &self.tax_setting.rounding);
+                if prorated == amount {
+                    return None;
+                }
+                Some(InvoiceItem {
+                    name: name.clone(),
+                    quantity: 1,
+                    unit: LUMP_SUM_UNIT.to_string(),
+                    unit_price: prorated - amount,
+                    tax_type,
+                    withholding,
+                })
+            })
+            .collect()
     }
 
-    #[test]
-    fn subject_in_this_month() {
-        let dt = NaiveDateTime::parse_from_str("2021/09/01 12:00:00", "%Y/%m/%d %H:%M:%S").unwrap();
-        let now = Utc.from_local_datetime(&dt).unwrap();
+    pub fn milestone_amount(&self, milestone: &SupplierMilestone) -> Money {
+        milestone.amount(&self.billing_amount, &self.tax_setting.rounding)
+    }
 
-        let supplier1 = Supplier {
-            id: "".to_string(),
-            user_id: "".to_string(),
-            contact_id: "".to_string(),
-            contact_group_id: "".to_string(),
-            name: "".to_string(),
-            billing_amount: 0,
-            billing_type: BillingType::OneTime,
-            end_ym: YM { year: 0, month: 0 },
-            subject: "通常の件名テスト".to_string(),
-            subject_template: "".to_string(),
-            created_at: now.naive_utc(),
-            updated_at: now.naive_utc(),
-        };
+    /// マイルストーンの請求はその金額の1行のみ
+    pub fn milestone_items(
+        &self,
+        subject: String,
+        milestone: &SupplierMilestone,
+    ) -> Vec<InvoiceItem> {
+        vec![InvoiceItem {
+            name: subject,
+            quantity: 1,
+            unit: LUMP_SUM_UNIT.to_string(),
+            unit_price: self.milestone_amount(milestone),
+            tax_type: self.tax_setting.tax_type.clone(),
+            withholding: self.withholding_tax,
+        }]
+    }
 
-        assert_eq!(
-            supplier1.subject_in_this_month(now),
-            "通常の件名テスト (2021年8月分)"
-        );
+    pub fn billing_amount_include_tax(&self) -> Money {
+        self.tax_setting.amount_include_tax(&self.billing_amount)
+    }
 
-        let supplier2 = Supplier {
-            id: "".to_string(),
-            user_id: "".to_string(),
-            contact_id: "".to_string(),
-            contact_group_id: "".to_string(),
-            name: "".to_string(),
-            billing_amount: 0,
-            billing_type: BillingType::OneTime,
-            end_ym: YM { year: 0, month: 0 },
-            subject: "テンプレートの件名テスト".to_string(),
-            subject_template: "{D} {S}".to_string(),
-            created_at: now.naive_utc(),
-            updated_at: now.naive_utc(),
+    pub fn billing_amount_exclude_tax(&self) -> Money {
+        self.tax_setting.amount_exclude_tax(&self.billing_amount)
+    }
+
+    /// 直近で締まった請求期間（今日より前の締め日で終わる期間）
+    pub fn billing_period(&self, now: DateTime<Utc>) -> BillingPeriod {
+        let today = now.naive_utc().date();
+        let this_month = YM::from(today);
+        let closing_in_this_month = self.closing_day.date_in(&this_month);
+        let end = if closing_in_this_month < today {
+            closing_in_this_month
+        } else {
+            self.closing_day.date_in(&this_month.add_months(-1))
         };
+        self.billing_period_ending_at(end)
+    }
 
-        assert_eq!(
-            supplier2.subject_in_this_month(now),
-            "2021年8月分 テンプレートの件名テスト"
+    /// 今日を含む、まだ締まっていない請求期間
+    pub fn next_billing_period(&self, now: DateTime<Utc>) -> BillingPeriod {
+        let today = now.naive_utc().date();
+        let this_month = YM::from(today);
+        let closing_in_this_month = self.closing_day.date_in(&this_month);
+        let end = if closing_in_this_month >= today {
+            closing_in_this_month
+        } else {
+            self.closing_day.date_in(&this_month.add_months(1))
+        };
+        self.billing_period_ending_at(end)
+    }
+
+    /// ymを締め月とする請求でまとめる期間（請求周期の最初の月の期間開始日から締め日まで）
+    fn covered_period(&self, ym: &YM) -> BillingPeriod {
+        let (from, to) = self
+            .recurrence
+            .covered_range(ym)
+            .unwrap_or((ym.clone(), ym.clone()));
+        BillingPeriod {
+            start: self
+                .billing_period_ending_at(self.closing_day.date_in(&from))
+                .start,
+            end: self.closing_day.date_in(&to),
+        }
+    }
+
+    fn billing_period_ending_at(&self, end: NaiveDate) -> BillingPeriod {
+        let prev_end = self.closing_day.date_in(&YM::from(end).add_months(-1));
+        BillingPeriod {
+            start: prev_end + Duration::days(1),
+            end,
+        }
+    }
+
+    /// 請求周期の最終月の締め日を過ぎたものから順に、次に請求する期間を探す
+    pub fn next_issue_period(
+        &self,
+        now: DateTime<Utc>,
+        suspensions: &[SupplierSuspension],
+    ) -> Option<BillingPeriod> {
+        let mut period = self.next_billing_period(now);
+        let until_resume = suspensions
+            .iter()
+            .map(|v| period.ym().months_until(&v.to_ym) + 1)
+            .max()
+            .unwrap_or(0);
+        let until_start = period.ym().months_until(&self.recurrence.start_ym);
+        let until_contract = self
+            .proration
+            .start_date
+            .to_date()
+            .map_or(0, |v| period.ym().months_until(&YM::from(v)) + 1);
+        let limit = cmp::max(
+            cmp::max(cmp::max(until_resume, until_start), until_contract),
+            0,
         );
+        for _ in 0..=(limit + self.recurrence.interval_months as i32) {
+            if self.is_billing_target(&period.ym(), suspensions) {
+                return Some(period);
+            }
+            let next_end = self.closing_day.date_in(&period.ym().add_months(1));
+            period = self.billing_period_ending_at(next_end);
+        }
+        None
     }
 
-    #[test]
-    fn payment_date_in_this_month() {
-        let dt = NaiveDateTime::parse_from_str("2021/09/01 12:00:00", "%Y/%m/%d %H:%M:%S").unwrap();
-        let now = Utc.from_local_datetime(&dt).unwrap();
+    /// 締め日の翌日以降で、直近で締まった期間の請求書を発行できるか。
+    /// バッチが動かなかった日があっても次の締め日までは発行し、登録より前に締まった期間は発行しない
+    pub fn is_issue_due(&self, now: DateTime<Utc>) -> bool {
+        let issue_date = self.billing_period(now).end + Duration::days(1);
+        issue_date <= now.naive_utc().date() && issue_date >= self.created_at.date()
+    }
 
-        let supplier = Supplier {
-            id: "".to_string(),
-            user_id: "".to_string(),
-            contact_id: "".to_string(),
-            contact_group_id: "".to_string(),
-            name: "".to_string(),
-            billing_amount: 0,
-            billing_type: BillingType::OneTime,
-            end_ym: YM { year: 0, month: 0 },
-            subject: "".to_string(),
-            subject_template: "".to_string(),
-            created_at: now.naive_utc(),
-            updated_at: now.naive_utc(),
+    /// 直近で締まった月が請求周期の最終月で、休止期間に含まれていないか
+    pub fn is_billing_month(&self, now: DateTime<Utc>, suspensions: &[SupplierSuspension]) -> bool {
+        self.is_billing_target(&self.billing_period(now).ym(), suspensions)
+    }
+
+    fn is_billing_target(&self, ym: &YM, suspensions: &[SupplierSuspension]) -> bool {
+        !self.archived
+            && !suspensions.iter().any(|v| v.covers(ym))
+            && self.recurrence.covered_range(ym).is_some()
+            && self.proration.days(&self.covered_period(ym)).0 > 0
+    }
+
+    pub fn subject_in_this_month(&self, now: DateTime<Utc>, invoice_number: &str) -> String {
+        self.subject_at(&self.billing_period(now).ym(), invoice_number)
+    }
+
+    /// ymを締め月とする請求の件名。請求書番号は採番した後に渡し、misocaで採番する場合は空にする
+    pub fn subject_at(&self, ym: &YM, invoice_number: &str) -> String {
+        self.render_subject(
+            self.covered_label_at(ym),
+            &self.covered_period(ym),
+            invoice_number,
+        )
+    }
+
+    /// テンプレートの期間の代わりにマイルストーン名を入れる
+    pub fn subject_for_milestone(
+        &self,
+        milestone: &SupplierMilestone,
+        period: &BillingPeriod,
+        invoice_number: &str,
+    ) -> String {
+        self.render_subject(milestone.name.clone(), period, invoice_number)
+    }
+
+    fn render_subject(
+        &self,
+        label: String,
+        period: &BillingPeriod,
+        invoice_number: &str,
+    ) -> String {
+        if self.subject_template.is_empty() {
+            return format!("{} ({})", self.subject.clone(), label);
+        }
+
+        match SubjectTemplate::from_str(self.subject_template.as_str()) {
+            Ok(template) => {
+                template.render(&self.subject_context(label.clone(), period, invoice_number))
+            }
+            // 検証を入れる前に登録されたテンプレートは従来どおり置換する
+            Err(_) => self
+                .subject_template
+                .replace(SUBJECT_PLACEHOLDER, &self.subject)
+                .replace(DATE_PLACEHOLDER, label.as_str()),
+        }
+    }
+
+    /// ymを締め月とする請求の備考。請求先のテンプレートが空の場合は差出人のテンプレートを使う
+    pub fn remarks_at(&self, ym: &YM, default_template: &str, invoice_number: &str) -> String {
+        self.render_remarks(
+            self.covered_label_at(ym),
+            &self.covered_period(ym),
+            default_template,
+            invoice_number,
+        )
+    }
+
+    pub fn remarks_for_milestone(
+        &self,
+        milestone: &SupplierMilestone,
+        period: &BillingPeriod,
+        default_template: &str,
+        invoice_number: &str,
+    ) -> String {
+        self.render_remarks(
+            milestone.name.clone(),
+            period,
+            default_template,
+            invoice_number,
+        )
+    }
+
+    fn render_remarks(
+        &self,
+        label: String,
+        period: &BillingPeriod,
+        default_template: &str,
+        invoice_number: &str,
+    ) -> String {
+        let template = if self.remarks_template.is_empty() {
+            default_template
+        } else {
+            self.remarks_template.as_str()
         };
 
-        let (issue_date, payment_due_on) = supplier.payment_date_in_this_month(now);
+        match SubjectTemplate::from_str(template) {
+            Ok(template) => template.render(&self.subject_context(label, period, invoice_number)),
+            Err(_) => template.to_string(),
+        }
+    }
 
-        assert_eq!(issue_date, "2021-09-01");
-        assert_eq!(payment_due_on, "2021-09-30");
+    fn covered_label_at(&self, ym: &YM) -> String {
+        let (from, to) = self
+            .recurrence
+            .covered_range(ym)
+            .unwrap_or((ym.clone(), ym.clone()));
+        covered_label(&from, &to)
+    }
+
+    fn subject_context(
+        &self,
+        label: String,
+        period: &BillingPeriod,
+        invoice_number: &str,
+    ) -> SubjectContext {
+        let ym = period.ym();
+        SubjectContext {
+            subject: self.subject.clone(),
+            period: label,
+            supplier_name: self.name.clone(),
+            year: ym.year,
+            month: ym.month,
+            start: period.start,
+            end: period.end,
+            invoice_number: invoice_number.to_string(),
+        }
+    }
+
+    /// 手動で請求する場合は今日を発行日とし、今日を含む請求期間の締め日から支払期日を求める
+    pub fn payment_date_on_demand(&self, now: DateTime<Utc>) -> (String, String) {
+        self.payment_date(self.next_billing_period(now).end, now.naive_utc().date())
+    }
+
+    pub fn payment_date_in_this_month(&self, now: DateTime<Utc>) -> (String, String) {
+        self.payment_date(self.billing_period(now).end, now.naive_utc().date())
+    }
+
+    pub fn next_payment_date(
+        &self,
+        now: DateTime<Utc>,
+        suspensions: &[SupplierSuspension],
+    ) -> Option<(String, String)> {
+        let period = self.next_issue_period(now, suspensions)?;
+        Some(self.payment_date(period.end, period.end + Duration::days(1)))
+    }
+
+    /// 支払期日は調整前の発行日から計算し、どちらも営業日に寄せる。発行日は締め日より前に戻さない
+    fn payment_date(&self, closing_date: NaiveDate, issue_date: NaiveDate) -> (String, String) {
+        let payment_due_on = self.payment_term.due_date(closing_date, issue_date);
+        (
+            self.business_day_adjustment
+                .forward()
+                .adjust(issue_date)
+                .format("%Y-%m-%d")
+                .to_string(),
+            self.business_day_adjustment
+                .adjust(payment_due_on)
+                .format("%Y-%m-%d")
+                .to_string(),
+        )
+    }
+}
+
+/// 精算幅。下限を下回った時間は控除単価で差し引き、上限を超えた時間は超過単価で請求する
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct SettlementRange {
+    pub lower_hours: i32,
+    pub upper_hours: i32,
+    pub excess_rate: Money,
+    pub deduction_rate: Money,
+}
+
+impl SettlementRange {
+    pub fn new(
+        lower_hours: i32,
+        upper_hours: i32,
+        excess_rate: Money,
+        deduction_rate: Money,
+    ) -> Result<Self, String> {
+        if lower_hours < 0 || upper_hours < lower_hours {
+            return Err("精算幅の下限・上限が不正です".to_string());
+        }
+        if excess_rate < Money::zero() || deduction_rate < Money::zero() {
+            return Err("精算単価は0以上で指定してください".to_string());
+        }
+        Ok(SettlementRange {
+            lower_hours,
+            upper_hours,
+            excess_rate,
+            deduction_rate,
+        })
+    }
+
+    pub fn excess_minutes(&self, minutes: i32) -> i32 {
+        cmp::max(minutes - self.upper_hours * 60, 0)
+    }
+
+    pub fn shortage_minutes(&self, minutes: i32) -> i32 {
+        cmp::max(self.lower_hours * 60 - minutes, 0)
+    }
+
+    /// 1時間あたりの単価を分単位で按分し、端数は切り捨てる
+    pub fn excess_amount(&self, minutes: i32) -> Money {
+        self.excess_rate
+            .ratio(self.excess_minutes(minutes) as i64, 60, &TaxRounding::Floor)
+    }
+
+    pub fn deduction_amount(&self, minutes: i32) -> Money {
+        self.deduction_rate.ratio(
+            self.shortage_minutes(minutes) as i64,
+            60,
+            &TaxRounding::Floor,
+        )
+    }
+}
+
+/// リテイナー契約で月額に含まれる利用量と、それを超えた分の単価
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct RetainerAllowance {
+    pub included_quantity: i32,
+    /// 利用量の単位（時間、件など）
+    pub unit: String,
+    pub overage_rate: Money,
+}
+
+impl RetainerAllowance {
+    pub fn new(included_quantity: i32, unit: String, overage_rate: Money) -> Result<Self, String> {
+        if included_quantity < 0 {
+            return Err("月額に含まれる利用量は0以上で指定してください".to_string());
+        }
+        if overage_rate < Money::zero() {
+            return Err("超過単価は0以上で指定してください".to_string());
+        }
+        Ok(RetainerAllowance {
+            included_quantity,
+            unit,
+            overage_rate,
+        })
+    }
+
+    pub fn overage_quantity(&self, quantity: i32) -> i32 {
+        cmp::max(quantity - self.included_quantity, 0)
+    }
+
+    pub fn overage_amount(&self, quantity: i32) -> Money {
+        self.overage_rate.times(self.overage_quantity(quantity))
+    }
+}
+
+/// 請求の周期。interval_months ごとに start_ym から区切った期間をまとめて、その最終月の締め後に請求する
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Recurrence {
+    pub interval_months: u32,
+    pub start_ym: YM,
+    /// 契約終了月。空の場合は期限なし
+    pub end_ym: YM,
+}
+
+impl Recurrence {
+    pub fn new(interval_months: i32, start_ym: YM, end_ym: YM) -> Result<Self, String> {
+        if interval_months < 1 || interval_months > 60 {
+            return Err("請求の間隔は1〜60ヶ月で指定してください".to_string());
+        }
+        if interval_months > 1 && start_ym.is_empty() {
+            return Err("請求の間隔が2ヶ月以上の場合は開始月を指定してください".to_string());
+        }
+        if !start_ym.is_empty() && !end_ym.is_empty() && end_ym < start_ym {
+            return Err("契約終了月は開始月以降で指定してください".to_string());
+        }
+        Ok(Recurrence {
+            interval_months: interval_months as u32,
+            start_ym,
+            end_ym,
+        })
+    }
+
+    /// targetが請求周期の最終月（契約終了月を含む）であれば、その請求でまとめる最初と最後の月
+    pub fn covered_range(&self, target: &YM) -> Option<(YM, YM)> {
+        if !self.start_ym.is_empty() && target < &self.start_ym {
+            return None;
+        }
+        if !self.end_ym.is_empty() && target > &self.end_ym {
+            return None;
+        }
+
+        let anchor = if self.start_ym.is_empty() {
+            target.clone()
+        } else {
+            self.start_ym.clone()
+        };
+        let interval = self.interval_months as i32;
+        let elapsed = anchor.months_until(target);
+        let from = anchor.add_months(elapsed - elapsed % interval);
+        let mut to = from.add_months(interval - 1);
+        if !self.end_ym.is_empty() && self.end_ym < to {
+            to = self.end_ym.clone();
+        }
+
+        if &to != target {
+            return None;
+        }
+        Some((from, to))
+    }
+
+    /// targetを最終月とする請求でまとめる月数と、本来の請求周期の月数
+    pub fn covered_months(&self, target: &YM) -> Option<(i64, i64)> {
+        let (from, to) = self.covered_range(target)?;
+        Some((
+            from.months_until(&to) as i64 + 1,
+            self.interval_months as i64,
+        ))
+    }
+}
+
+impl Default for Recurrence {
+    fn default() -> Self {
+        Recurrence {
+            interval_months: 1,
+            start_ym: YM { year: 0, month: 0 },
+            end_ym: YM { year: 0, month: 0 },
+        }
+    }
+}
+
+/// 契約の開始日・終了日。期間の途中で始まる・終わる月は、契約期間に含まれる日数で日割りする
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Proration {
+    pub basis: ProrationBasis,
+    /// 空の場合は開始日の指定なし
+    pub start_date: YMD,
+    /// 空の場合は終了日の指定なし
+    pub end_date: YMD,
+}
+
+impl Proration {
+    pub fn new(basis: ProrationBasis, start_date: YMD, end_date: YMD) -> Result<Self, String> {
+        if !start_date.is_empty() && start_date.to_date().is_none() {
+            return Err("契約開始日が不正です".to_string());
+        }
+        if !end_date.is_empty() && end_date.to_date().is_none() {
+            return Err("契約終了日が不正です".to_string());
+        }
+        if let (Some(start), Some(end)) = (start_date.to_date(), end_date.to_date()) {
+            if end < start {
+                return Err("契約終了日は開始日以降で指定してください".to_string());
+            }
+        }
+        Ok(Proration {
+            basis,
+            start_date,
+            end_date,
+        })
+    }
+
+    /// periodのうち契約期間に含まれる日数と、period全体の日数
+    pub fn days(&self, period: &BillingPeriod) -> (i64, i64) {
+        let start = self
+            .start_date
+            .to_date()
+            .map_or(period.start, |v| cmp::max(v, period.start));
+        let end = self
+            .end_date
+            .to_date()
+            .map_or(period.end, |v| cmp::min(v, period.end));
+        (
+            self.basis.count(start, end),
+            self.basis.count(period.start, period.end),
+        )
+    }
+}
+
+impl Default for Proration {
+    fn default() -> Self {
+        Proration {
+            basis: ProrationBasis::default(),
+            start_date: YMD {
+                year: 0,
+                month: 0,
+                day: 0,
+            },
+            end_date: YMD {
+                year: 0,
+                month: 0,
+                day: 0,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ProrationBasis {
+    CalendarDays,
+    BusinessDays,
+}
+
+impl ProrationBasis {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::CalendarDays => 0,
+            Self::BusinessDays => 1,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Self::CalendarDays => "暦日",
+            Self::BusinessDays => "営業日",
+        }
+    }
+
+    /// startからendまで（両端を含む）の日数
+    pub fn count(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        if end < start {
+            return 0;
+        }
+        match self {
+            Self::CalendarDays => (end - start).num_days() + 1,
+            Self::BusinessDays => {
+                let mut count = 0;
+                let mut date = start;
+                while date <= end {
+                    if calendar::is_business_day(date) {
+                        count += 1;
+                    }
+                    date = date + Duration::days(1);
+                }
+                count
+            }
+        }
+    }
+}
+
+impl Default for ProrationBasis {
+    fn default() -> Self {
+        Self::CalendarDays
+    }
+}
+
+impl From<i32> for ProrationBasis {
+    fn from(v: i32) -> ProrationBasis {
+        match v {
+            0 => Self::CalendarDays,
+            1 => Self::BusinessDays,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// 例: 2026年4月分、2026年4月〜6月分、2026年11月〜2027年1月分
+fn covered_label(from: &YM, to: &YM) -> String {
+    if from == to {
+        format!("{}年{}月分", to.year, to.month)
+    } else if from.year == to.year {
+        format!("{}年{}月〜{}月分", from.year, from.month, to.month)
+    } else {
+        format!(
+            "{}年{}月〜{}年{}月分",
+            from.year, from.month, to.year, to.month
+        )
+    }
+}
+
+fn format_hours(minutes: i32) -> String {
+    if minutes % 60 == 0 {
+        return (minutes / 60).to_string();
+    }
+    format!("{:.2}", minutes as f64 / 60.0)
+        .trim_end_matches('0')
+        .to_string()
+}
+
+/// 最小単位の単価を通貨単位で表示する。円は「円」、外貨は通貨コードを付ける
+fn format_rate(rate: &Money, currency: &Currency) -> String {
+    match currency {
+        Currency::JPY => format!("{}円", rate.to_major_string(currency)),
+        _ => format!("{} {}", rate.to_major_string(currency), currency.code()),
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BillingPeriod {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl BillingPeriod {
+    /// 締め日が属する月を請求対象月とする
+    pub fn ym(&self) -> YM {
+        YM::from(self.end)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ClosingDay {
+    EndOfMonth,
+    Day(u32),
+}
+
+impl ClosingDay {
+    pub fn new(v: i32) -> Result<Self, String> {
+        match v {
+            0 => Ok(Self::EndOfMonth),
+            1..=28 => Ok(Self::Day(v as u32)),
+            _ => Err("締め日は1〜28日、または0（末日）で指定してください".to_string()),
+        }
+    }
+
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::EndOfMonth => 0,
+            Self::Day(day) => *day as i32,
+        }
+    }
+
+    pub fn date_in(&self, ym: &YM) -> NaiveDate {
+        match self {
+            Self::EndOfMonth => ym.last_day(),
+            Self::Day(day) => NaiveDate::from_ymd(ym.year as i32, ym.month, *day),
+        }
+    }
+}
+
+impl Default for ClosingDay {
+    fn default() -> Self {
+        Self::EndOfMonth
+    }
+}
+
+impl From<i32> for ClosingDay {
+    fn from(v: i32) -> ClosingDay {
+        Self::new(v).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PaymentTerm {
+    pub term_type: PaymentTermType,
+    pub months: u32,
+    pub day: u32,
+}
+
+impl PaymentTerm {
+    pub fn new(term_type: PaymentTermType, months: i32, day: i32) -> Result<Self, String> {
+        if months < 0 || months > 12 {
+            return Err("支払月は0〜12ヶ月後で指定してください".to_string());
+        }
+        match term_type {
+            PaymentTermType::MonthEnd => {}
+            PaymentTermType::MonthDay => {
+                if day < 1 || day > 31 {
+                    return Err("支払日は1〜31日で指定してください".to_string());
+                }
+            }
+            PaymentTermType::DaysAfterIssue => {
+                if day < 0 || day > 365 {
+                    return Err("支払日数は0〜365日で指定してください".to_string());
+                }
+            }
+        }
+        Ok(PaymentTerm {
+            term_type,
+            months: months as u32,
+            day: day as u32,
+        })
+    }
+
+    /// 翌月末であれば締め日の翌月末日、翌々月10日であれば締め日の翌々月10日（月末を超える場合は末日）
+    pub fn due_date(&self, closing_date: NaiveDate, issue_date: NaiveDate) -> NaiveDate {
+        let target = YM::from(closing_date).add_months(self.months as i32);
+        match self.term_type {
+            PaymentTermType::MonthEnd => target.last_day(),
+            PaymentTermType::MonthDay => {
+                let last_day = target.last_day();
+                NaiveDate::from_ymd_opt(target.year as i32, target.month, self.day)
+                    .unwrap_or(last_day)
+            }
+            PaymentTermType::DaysAfterIssue => issue_date + Duration::days(self.day as i64),
+        }
+    }
+}
+
+impl Default for PaymentTerm {
+    fn default() -> Self {
+        PaymentTerm {
+            term_type: PaymentTermType::MonthEnd,
+            months: 1,
+            day: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PaymentTermType {
+    MonthEnd,
+    MonthDay,
+    DaysAfterIssue,
+}
+
+impl PaymentTermType {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::MonthEnd => 0,
+            Self::MonthDay => 1,
+            Self::DaysAfterIssue => 2,
+        }
+    }
+}
+
+impl Default for PaymentTermType {
+    fn default() -> Self {
+        Self::MonthEnd
+    }
+}
+
+impl From<i32> for PaymentTermType {
+    fn from(v: i32) -> PaymentTermType {
+        match v {
+            0 => Self::MonthEnd,
+            1 => Self::MonthDay,
+            2 => Self::DaysAfterIssue,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BillingType {
+    Monthly,
+    OneTime,
+    Hourly,
+    Milestone,
+    Retainer,
+}
+
+impl BillingType {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::Monthly => 0,
+            Self::OneTime => 1,
+            Self::Hourly => 2,
+            Self::Milestone => 3,
+            Self::Retainer => 4,
+        }
+    }
+}
+
+impl Default for BillingType {
+    fn default() -> Self {
+        Self::Monthly
+    }
+}
+
+impl From<i32> for BillingType {
+    fn from(v: i32) -> BillingType {
+        match v {
+            0 => Self::Monthly,
+            1 => Self::OneTime,
+            2 => Self::Hourly,
+            3 => Self::Milestone,
+            4 => Self::Retainer,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod supplier_tests {
+    use crate::domain::calendar::BusinessDayAdjustment;
+    use crate::domain::currency::Currency;
+    use crate::domain::invoice::{TaxRounding, TaxSetting, TaxType};
+    use crate::domain::invoice_delivery::DeliverySetting;
+    use crate::domain::money::Money;
+    use crate::domain::retainer_usage::RetainerUsage;
+    use crate::domain::supplier::{
+        BillingType, ClosingDay, PaymentTerm, PaymentTermType, Proration, ProrationBasis,
+        Recurrence, RetainerAllowance, SettlementRange, Supplier,
+    };
+    use crate::domain::supplier_item::SupplierItem;
+    use crate::domain::supplier_milestone::{
+        validate_total, MilestoneAmountType, SupplierMilestone,
+    };
+    use crate::domain::supplier_price::{upcoming_price, SupplierPrice};
+    use crate::domain::supplier_suspension::SupplierSuspension;
+    use crate::domain::working_hours::WorkingHours;
+    use crate::domain::{YM, YMD};
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+    fn datetime(s: &str) -> DateTime<Utc> {
+        let dt = NaiveDateTime::parse_from_str(s, "%Y/%m/%d %H:%M:%S").unwrap();
+        Utc.from_local_datetime(&dt).unwrap()
+    }
+
+    fn supplier(now: DateTime<Utc>) -> Supplier {
+        Supplier {
+            id: "".to_string(),
+            user_id: "".to_string(),
+            contact_id: "".to_string(),
+            contact_group_id: "".to_string(),
+            name: "".to_string(),
+            billing_amount: Money::from(0),
+            currency: Currency::JPY,
+            billing_type: BillingType::OneTime,
+            end_ym: YM { year: 0, month: 0 },
+            subject: "".to_string(),
+            subject_template: "".to_string(),
+            remarks_template: "".to_string(),
+            invoice_number_prefix: "".to_string(),
+            billing_email: "".to_string(),
+            reminder_schedule: "".to_string(),
+            delivery: DeliverySetting::default(),
+            closing_day: ClosingDay::EndOfMonth,
+            payment_term: PaymentTerm::default(),
+            business_day_adjustment: BusinessDayAdjustment::Unadjusted,
+            settlement_range: SettlementRange::default(),
+            retainer: RetainerAllowance::default(),
+            recurrence: Recurrence::default(),
+            proration: Proration::default(),
+            tax_setting: TaxSetting::default(),
+            withholding_tax: false,
+            archived: false,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        }
+    }
+
+    #[test]
+    fn billing_amount_include_tax() {
+        let now = Utc::now();
+
+        let exclusive = Supplier {
+            billing_amount: Money::from(200000),
+            ..supplier(now)
+        };
+
+        assert_eq!(exclusive.billing_amount_include_tax(), Money::from(220000));
+        assert_eq!(exclusive.billing_amount_exclude_tax(), Money::from(200000));
+
+        let inclusive = Supplier {
+            billing_amount: Money::from(108000),
+            tax_setting: TaxSetting {
+                tax_type: TaxType::Reduced8,
+                inclusive: true,
+                ..TaxSetting::default()
+            },
+            ..supplier(now)
+        };
+
+        assert_eq!(inclusive.billing_amount_include_tax(), Money::from(108000));
+        assert_eq!(inclusive.billing_amount_exclude_tax(), Money::from(100000));
+    }
+
+    #[test]
+    fn subject_in_this_month() {
+        let now = datetime("2021/09/01 12:00:00");
+
+        let supplier1 = Supplier {
+            subject: "通常の件名テスト".to_string(),
+            ..supplier(now)
+        };
+
+        assert_eq!(
+            supplier1.subject_in_this_month(now, ""),
+            "通常の件名テスト (2021年8月分)"
+        );
+
+        let supplier2 = Supplier {
+            subject: "テンプレートの件名テスト".to_string(),
+            subject_template: "{D} {S}".to_string(),
+            ..supplier(now)
+        };
+
+        assert_eq!(
+            supplier2.subject_in_this_month(now, ""),
+            "2021年8月分 テンプレートの件名テスト"
+        );
+
+        let supplier3 = Supplier {
+            name: "サンプル".to_string(),
+            subject: "保守".to_string(),
+            subject_template: "{supplier}様 {S} {era}{era_year}年{month:02}月（{start}〜{end}）"
+                .to_string(),
+            ..supplier(now)
+        };
+
+        assert_eq!(
+            supplier3.subject_at(
+                &YM {
+                    year: 2021,
+                    month: 12
+                },
+                ""
+            ),
+            "サンプル様 保守 令和3年12月（2021年12月1日〜2021年12月31日）"
+        );
+
+        // 検証を入れる前に登録されたテンプレート
+        let supplier4 = Supplier {
+            subject: "保守".to_string(),
+            subject_template: "{D} {S} {X}".to_string(),
+            ..supplier(now)
+        };
+
+        assert_eq!(
+            supplier4.subject_in_this_month(now, ""),
+            "2021年8月分 保守 {X}"
+        );
+
+        // 採番した請求書番号を件名と備考に入れる
+        let supplier5 = Supplier {
+            subject: "保守".to_string(),
+            subject_template: "{S} {D}（{invoice_number}）".to_string(),
+            remarks_template: "請求書番号: {invoice_number}".to_string(),
+            ..supplier(now)
+        };
+        assert_eq!(
+            supplier5.subject_in_this_month(now, "INV-2021-0042"),
+            "保守 2021年8月分（INV-2021-0042）"
+        );
+        assert_eq!(
+            supplier5.remarks_at(
+                &YM {
+                    year: 2021,
+                    month: 8
+                },
+                "",
+                "INV-2021-0042"
+            ),
+            "請求書番号: INV-2021-0042"
+        );
+    }
+
+    #[test]
+    fn remarks_at() {
+        let now = datetime("2021/09/01 12:00:00");
+        let ym = YM {
+            year: 2021,
+            month: 8,
+        };
+        let default_template = "振込手数料はご負担ください。\n{D}";
+
+        let supplier1 = Supplier {
+            name: "サンプル".to_string(),
+            ..supplier(now)
+        };
+        assert_eq!(
+            supplier1.remarks_at(&ym, default_template, ""),
+            "振込手数料はご負担ください。\n2021年8月分"
+        );
+        assert_eq!(supplier1.remarks_at(&ym, "", ""), "");
+
+        let supplier2 = Supplier {
+            remarks_template: "注文番号: PO-123（{supplier}様 {year}年{month:02}月）".to_string(),
+            ..supplier1
+        };
+        assert_eq!(
+            supplier2.remarks_at(&ym, default_template, ""),
+            "注文番号: PO-123（サンプル様 2021年08月）"
+        );
+    }
+
+    #[test]
+    fn subject_in_this_month_across_year() {
+        let now = datetime("2022/01/01 12:00:00");
+
+        let supplier = Supplier {
+            subject: "年跨ぎの件名テスト".to_string(),
+            ..supplier(now)
+        };
+
+        assert_eq!(
+            supplier.subject_in_this_month(now, ""),
+            "年跨ぎの件名テスト (2021年12月分)"
+        );
+    }
+
+    #[test]
+    fn payment_date_in_this_month() {
+        let now = datetime("2021/09/01 12:00:00");
+
+        let supplier = supplier(now);
+
+        let (issue_date, payment_due_on) = supplier.payment_date_in_this_month(now);
+
+        assert_eq!(issue_date, "2021-09-01");
+        assert_eq!(payment_due_on, "2021-09-30");
+    }
+
+    #[test]
+    fn payment_date_in_this_month_across_year() {
+        let now = datetime("2021/12/01 12:00:00");
+
+        let supplier = Supplier {
+            payment_term: PaymentTerm::new(PaymentTermType::MonthDay, 2, 10).unwrap(),
+            ..supplier(now)
+        };
+
+        let (issue_date, payment_due_on) = supplier.payment_date_in_this_month(now);
+
+        assert_eq!(issue_date, "2021-12-01");
+        assert_eq!(payment_due_on, "2022-01-10");
+    }
+
+    #[test]
+    fn payment_date_on_business_day() {
+        // 2022-05-01(日)発行、2022-05-31(火)期日、2022-07-31(日)期日
+        let now = datetime("2022/05/01 12:00:00");
+
+        let supplier1 = Supplier {
+            business_day_adjustment: BusinessDayAdjustment::Next,
+            ..supplier(now)
+        };
+
+        let (issue_date, payment_due_on) = supplier1.payment_date_in_this_month(now);
+        assert_eq!(issue_date, "2022-05-02");
+        assert_eq!(payment_due_on, "2022-05-31");
+
+        let supplier2 = Supplier {
+            payment_term: PaymentTerm::new(PaymentTermType::MonthEnd, 3, 0).unwrap(),
+            business_day_adjustment: BusinessDayAdjustment::Previous,
+            ..supplier(now)
+        };
+
+        // 前営業日に寄せるのは支払期日だけで、発行日は月末締めより前に戻さない
+        let (issue_date, payment_due_on) = supplier2.payment_date_in_this_month(now);
+        assert_eq!(issue_date, "2022-05-02");
+        assert_eq!(payment_due_on, "2022-07-29");
+    }
+
+    #[test]
+    fn billing_period_with_closing_day() {
+        let now = datetime("2022/01/21 12:00:00");
+
+        let supplier = Supplier {
+            closing_day: ClosingDay::Day(20),
+            ..supplier(now)
+        };
+
+        let period = supplier.billing_period(now);
+        assert_eq!(period.start, NaiveDate::from_ymd(2021, 12, 21));
+        assert_eq!(period.end, NaiveDate::from_ymd(2022, 1, 20));
+        assert_eq!(
+            period.ym(),
+            YM {
+                year: 2022,
+                month: 1
+            }
+        );
+        assert!(supplier.is_issue_due(now));
+        assert!(!supplier.is_issue_due(datetime("2022/01/20 12:00:00")));
+        // 発行日にバッチが動かなくても、次の締め日までは発行する
+        assert!(supplier.is_issue_due(datetime("2022/01/25 12:00:00")));
+        assert!(supplier.is_issue_due(datetime("2022/02/20 12:00:00")));
+
+        // 締め日の後に登録した請求先は、その期間を発行しない
+        let registered_later = Supplier {
+            created_at: datetime("2022/01/22 09:00:00").naive_utc(),
+            ..supplier.clone()
+        };
+        assert!(!registered_later.is_issue_due(datetime("2022/01/25 12:00:00")));
+        assert!(registered_later.is_issue_due(datetime("2022/02/21 12:00:00")));
+
+        let next = supplier.next_billing_period(now);
+        assert_eq!(next.start, NaiveDate::from_ymd(2022, 1, 21));
+        assert_eq!(next.end, NaiveDate::from_ymd(2022, 2, 20));
+    }
+
+    #[test]
+    fn quarterly_recurrence() {
+        let ym = |year, month| YM { year, month };
+        let now = datetime("2026/07/01 12:00:00");
+
+        let supplier = Supplier {
+            subject: "保守".to_string(),
+            billing_type: BillingType::Monthly,
+            recurrence: Recurrence::new(3, ym(2026, 4), ym(2027, 2)).unwrap(),
+            ..supplier(now)
+        };
+
+        assert!(supplier.is_billing_month(now, &[]));
+        assert_eq!(
+            supplier.subject_in_this_month(now, ""),
+            "保守 (2026年4月〜6月分)"
+        );
+        assert!(!supplier.is_billing_month(datetime("2026/08/01 12:00:00"), &[]));
+
+        let now = datetime("2027/01/01 12:00:00");
+        assert!(supplier.is_billing_month(now, &[]));
+        assert_eq!(
+            supplier.subject_in_this_month(now, ""),
+            "保守 (2026年10月〜12月分)"
+        );
+
+        // 契約終了月で周期を打ち切って請求する
+        let now = datetime("2027/03/01 12:00:00");
+        assert!(supplier.is_billing_month(now, &[]));
+        assert_eq!(
+            supplier.subject_in_this_month(now, ""),
+            "保守 (2027年1月〜2月分)"
+        );
+
+        // 打ち切った周期は含まれる月数で月割りする
+        let quarterly = Supplier {
+            billing_amount: Money::from(300000),
+            ..supplier.clone()
+        };
+        let items = quarterly
+            .invoice_items("件名".to_string(), vec![], None, None, now)
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].name, "契約終了月までの月割り（2ヶ月 / 3ヶ月）");
+        assert_eq!(items[1].unit_price, Money::from(-100000));
+        let items = quarterly
+            .invoice_items(
+                "件名".to_string(),
+                vec![],
+                None,
+                None,
+                datetime("2027/01/01 12:00:00"),
+            )
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert!(!supplier.is_billing_month(datetime("2027/04/01 12:00:00"), &[]));
+        assert!(!supplier.is_billing_month(datetime("2026/04/01 12:00:00"), &[]));
+
+        let next = supplier
+            .next_issue_period(datetime("2026/07/15 12:00:00"), &[])
+            .unwrap();
+        assert_eq!(next.end, NaiveDate::from_ymd(2026, 9, 30));
+        assert!(supplier
+            .next_issue_period(datetime("2027/03/15 12:00:00"), &[])
+            .is_none());
+
+        assert!(Recurrence::new(0, ym(2026, 4), ym(0, 0)).is_err());
+        assert!(Recurrence::new(12, ym(0, 0), ym(0, 0)).is_err());
+        assert!(Recurrence::new(12, ym(2026, 4), ym(2026, 3)).is_err());
+    }
+
+    #[test]
+    fn suspension_and_archive() {
+        let ym = |year, month| YM { year, month };
+        let now = datetime("2026/08/01 12:00:00");
+
+        let mut supplier = Supplier {
+            billing_type: BillingType::Monthly,
+            ..supplier(now)
+        };
+        let suspensions =
+            vec![SupplierSuspension::new("".to_string(), ym(2026, 7), ym(2026, 9), now).unwrap()];
+
+        assert!(supplier.is_billing_month(now, &[]));
+        assert!(!supplier.is_billing_month(now, &suspensions));
+        assert!(supplier.is_billing_month(datetime("2026/11/01 12:00:00"), &suspensions));
+
+        let next = supplier.next_issue_period(now, &suspensions).unwrap();
+        assert_eq!(next.end, NaiveDate::from_ymd(2026, 10, 31));
+
+        supplier.archive(now);
+        assert!(!supplier.is_billing_month(datetime("2026/11/01 12:00:00"), &suspensions));
+        assert!(supplier.next_issue_period(now, &suspensions).is_none());
+
+        assert!(SupplierSuspension::new("".to_string(), ym(2026, 9), ym(2026, 7), now).is_err());
+    }
+
+    #[test]
+    fn billing_amount_with_price_history() {
+        let ym = |year, month| YM { year, month };
+        let now = Utc::now();
+
+        let supplier = Supplier {
+            billing_amount: Money::from(500000),
+            ..supplier(now)
+        };
+        let prices = vec![
+            SupplierPrice::new("".to_string(), ym(2026, 4), Money::from(550000), now).unwrap(),
+            SupplierPrice::new("".to_string(), ym(2027, 4), Money::from(600000), now).unwrap(),
+        ];
+
+        assert_eq!(
+            supplier.billing_amount_at(&ym(2026, 3), &prices),
+            Money::from(500000)
+        );
+        assert_eq!(
+            supplier.billing_amount_at(&ym(2026, 4), &prices),
+            Money::from(550000)
+        );
+        assert_eq!(
+            supplier.billing_amount_at(&ym(2027, 3), &prices),
+            Money::from(550000)
+        );
+        assert_eq!(
+            supplier.with_price_at(&ym(2027, 5), &prices).billing_amount,
+            Money::from(600000)
+        );
+        assert_eq!(
+            upcoming_price(&prices, &ym(2026, 10)).map(|v| v.billing_amount.clone()),
+            Some(Money::from(600000))
+        );
+        assert!(upcoming_price(&prices, &ym(2027, 4)).is_none());
+        assert!(SupplierPrice::new("".to_string(), ym(0, 0), Money::from(1), now).is_err());
+    }
+
+    #[test]
+    fn reprice_after_price_history() {
+        let ym = |year, month| YM { year, month };
+        let now = datetime("2026/06/15 12:00:00");
+
+        let mut supplier = Supplier {
+            billing_amount: Money::from(500000),
+            billing_type: BillingType::Monthly,
+            ..supplier(now)
+        };
+        let mut prices = vec![
+            SupplierPrice::new("".to_string(), ym(2026, 4), Money::from(550000), now).unwrap(),
+            SupplierPrice::new("".to_string(), ym(2027, 4), Money::from(700000), now).unwrap(),
+        ];
+        assert_eq!(
+            supplier.with_current_price(&prices, now).billing_amount,
+            Money::from(550000)
+        );
+
+        // 改定履歴がある状態で金額を変えても、今日を含む期間から効く
+        let price = supplier
+            .reprice(Money::from(600000), &prices, now)
+            .unwrap()
+            .unwrap();
+        assert_eq!(price.effective_from, ym(2026, 6));
+        prices.push(price);
+        assert_eq!(
+            supplier.billing_amount_at(&ym(2026, 5), &prices),
+            Money::from(550000)
+        );
+        assert_eq!(
+            supplier.with_current_price(&prices, now).billing_amount,
+            Money::from(600000)
+        );
+        assert_eq!(
+            supplier.billing_amount_at(&ym(2027, 4), &prices),
+            Money::from(700000)
+        );
+
+        // 同じ月の改定は上書きし、金額が変わらなければ積まない
+        let price = supplier
+            .reprice(Money::from(620000), &prices, now)
+            .unwrap()
+            .unwrap();
+        assert_eq!(price.id, prices[2].id);
+        assert_eq!(price.billing_amount, Money::from(620000));
+        assert!(supplier
+            .reprice(Money::from(600000), &prices, now)
+            .unwrap()
+            .is_none());
+
+        // 登録時の金額はそのまま残る
+        supplier.update(
+            supplier.contact_id.clone(),
+            supplier.contact_group_id.clone(),
+            supplier.name.clone(),
+            Money::from(900000),
+            Currency::JPY,
+            YM { year: 0, month: 0 },
+            SettlementRange::default(),
+            RetainerAllowance::default(),
+            Recurrence::default(),
+            Proration::default(),
+            supplier.subject.clone(),
+            supplier.subject_template.clone(),
+            supplier.remarks_template.clone(),
+            supplier.invoice_number_prefix.clone(),
+            supplier.billing_email.clone(),
+            supplier.reminder_schedule.clone(),
+            DeliverySetting::default(),
+            ClosingDay::EndOfMonth,
+            PaymentTerm::default(),
+            BusinessDayAdjustment::Unadjusted,
+            TaxSetting::default(),
+            false,
+            now,
+        );
+        assert_eq!(supplier.billing_amount, Money::from(500000));
+    }
+
+    #[test]
+    fn proration_at_contract_start_and_end() {
+        let ymd = |year, month, day| YMD { year, month, day };
+        let now = datetime("2026/05/01 12:00:00");
+
+        let mut supplier = Supplier {
+            subject: "保守".to_string(),
+            billing_amount: Money::from(300000),
+            billing_type: BillingType::Monthly,
+            proration: Proration::new(
+                ProrationBasis::CalendarDays,
+                ymd(2026, 4, 16),
+                ymd(2026, 6, 10),
+            )
+            .unwrap(),
+            ..supplier(now)
+        };
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], None, None, now)
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].unit_price, Money::from(300000));
+        assert_eq!(items[1].name, "日割り調整（暦日15日 / 30日）");
+        assert_eq!(items[1].unit_price, Money::from(-150000));
+
+        let items = supplier
+            .invoice_items(
+                "件名".to_string(),
+                vec![],
+                None,
+                None,
+                datetime("2026/06/01 12:00:00"),
+            )
+            .unwrap();
+        assert_eq!(items.len(), 1);
+
+        let items = supplier
+            .invoice_items(
+                "件名".to_string(),
+                vec![],
+                None,
+                None,
+                datetime("2026/07/01 12:00:00"),
+            )
+            .unwrap();
+        assert_eq!(items[1].name, "日割り調整（暦日10日 / 30日）");
+        assert_eq!(items[1].unit_price, Money::from(-200000));
+
+        assert!(!supplier.is_billing_month(datetime("2026/04/01 12:00:00"), &[]));
+        assert!(supplier.is_billing_month(now, &[]));
+        assert!(!supplier.is_billing_month(datetime("2026/08/01 12:00:00"), &[]));
+        let next = supplier
+            .next_issue_period(datetime("2026/03/10 12:00:00"), &[])
+            .unwrap();
+        assert_eq!(next.end, NaiveDate::from_ymd(2026, 4, 30));
+
+        // 2026年4月の営業日は21日（29日は昭和の日）、16日以降は10日
+        supplier.proration.basis = ProrationBasis::BusinessDays;
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], None, None, now)
+            .unwrap();
+        assert_eq!(items[1].name, "日割り調整（営業日10日 / 21日）");
+        assert_eq!(items[1].unit_price, Money::from(-157143));
+
+        supplier.tax_setting.rounding = TaxRounding::Ceil;
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], None, None, now)
+            .unwrap();
+        assert_eq!(items[1].unit_price, Money::from(-157142));
+
+        // 明細の税率・源泉徴収ごとに、その明細と同じ扱いの調整行にする
+        supplier.proration.basis = ProrationBasis::CalendarDays;
+        supplier.tax_setting.rounding = TaxRounding::Floor;
+        let supplier_items = vec![
+            SupplierItem::new(
+                "".to_string(),
+                "保守".to_string(),
+                1,
+                "式".to_string(),
+                Money::from(200000),
+                TaxType::Standard10,
+                true,
+                now,
+            )
+            .unwrap(),
+            SupplierItem::new(
+                "".to_string(),
+                "飲料".to_string(),
+                1,
+                "式".to_string(),
+                Money::from(100000),
+                TaxType::Reduced8,
+                false,
+                now,
+            )
+            .unwrap(),
+        ];
+        let items = supplier
+            .invoice_items("件名".to_string(), supplier_items, None, None, now)
+            .unwrap();
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[2].tax_type, TaxType::Standard10);
+        assert!(items[2].withholding);
+        assert_eq!(items[2].unit_price, Money::from(-100000));
+        assert_eq!(items[3].tax_type, TaxType::Reduced8);
+        assert!(!items[3].withholding);
+        assert_eq!(items[3].unit_price, Money::from(-50000));
+
+        assert!(Proration::new(
+            ProrationBasis::CalendarDays,
+            ymd(2026, 4, 16),
+            ymd(2026, 4, 15)
+        )
+        .is_err());
+        assert!(
+            Proration::new(ProrationBasis::CalendarDays, ymd(2026, 2, 30), ymd(0, 0, 0)).is_err()
+        );
+    }
+
+    #[test]
+    fn milestone_billing() {
+        let ym = |year, month| YM { year, month };
+        let now = datetime("2026/05/01 12:00:00");
+
+        let supplier = Supplier {
+            subject: "Webサイト制作".to_string(),
+            billing_amount: Money::from(1000000),
+            billing_type: BillingType::Milestone,
+            withholding_tax: true,
+            ..supplier(now)
+        };
+
+        let milestone = |name: &str, amount_type, amount_value, target_ym| {
+            SupplierMilestone::new(
+                "".to_string(),
+                name.to_string(),
+                0,
+                amount_type,
+                amount_value,
+                target_ym,
+                now,
+            )
+            .unwrap()
+        };
+        let mut deposit = milestone(
+            "着手金",
+            MilestoneAmountType::Percentage,
+            Money::from(30),
+            ym(2026, 4),
+        );
+        let interim = milestone(
+            "中間金",
+            MilestoneAmountType::Fixed,
+            Money::from(300000),
+            ym(0, 0),
+        );
+        let last = milestone(
+            "残金",
+            MilestoneAmountType::Percentage,
+            Money::from(40),
+            ym(2026, 9),
+        );
+
+        assert!(deposit.is_due(&supplier.billing_period(now).ym()));
+        assert!(!interim.is_due(&ym(0, 0)));
+        assert_eq!(
+            supplier.subject_for_milestone(&deposit, &supplier.billing_period(now), ""),
+            "Webサイト制作 (着手金)"
+        );
+
+        let items = supplier.milestone_items("件名".to_string(), &deposit);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].unit_price, Money::from(300000));
+        assert!(items[0].withholding);
+
+        let (issue_date, payment_due_on) =
+            supplier.payment_date_on_demand(datetime("2026/05/20 12:00:00"));
+        assert_eq!(issue_date, "2026-05-20");
+        assert_eq!(payment_due_on, "2026-06-30");
+
+        let milestones = vec![deposit.clone(), interim.clone(), last.clone()];
+        assert!(validate_total(&milestones, &Money::from(1000000), &TaxRounding::Floor).is_ok());
+        assert!(validate_total(&milestones, &Money::from(900000), &TaxRounding::Floor).is_err());
+
+        deposit.mark_billed("invoice".to_string(), now);
+        assert!(!deposit.is_due(&ym(2026, 4)));
+        assert!(deposit
+            .update(
+                "着手金".to_string(),
+                0,
+                MilestoneAmountType::Fixed,
+                Money::from(1),
+                ym(0, 0),
+                now
+            )
+            .is_err());
+
+        assert!(SupplierMilestone::new(
+            "".to_string(),
+            "".to_string(),
+            0,
+            MilestoneAmountType::Fixed,
+            Money::from(1),
+            ym(0, 0),
+            now
+        )
+        .is_err());
+        assert!(SupplierMilestone::new(
+            "".to_string(),
+            "残金".to_string(),
+            0,
+            MilestoneAmountType::Percentage,
+            Money::from(101),
+            ym(0, 0),
+            now
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn payment_term_due_date() {
+        let closing = NaiveDate::from_ymd(2021, 11, 30);
+        let issue = NaiveDate::from_ymd(2021, 12, 1);
+
+        let month_end = PaymentTerm::new(PaymentTermType::MonthEnd, 1, 0).unwrap();
+        assert_eq!(
+            month_end.due_date(closing, issue),
+            NaiveDate::from_ymd(2021, 12, 31)
+        );
+
+        let month_day = PaymentTerm::new(PaymentTermType::MonthDay, 3, 31).unwrap();
+        assert_eq!(
+            month_day.due_date(closing, issue),
+            NaiveDate::from_ymd(2022, 2, 28)
+        );
+
+        let days_after = PaymentTerm::new(PaymentTermType::DaysAfterIssue, 0, 45).unwrap();
+        assert_eq!(
+            days_after.due_date(closing, issue),
+            NaiveDate::from_ymd(2022, 1, 15)
+        );
+
+        assert!(ClosingDay::new(29).is_err());
+        assert!(PaymentTerm::new(PaymentTermType::MonthDay, 1, 0).is_err());
+    }
+
+    #[test]
+    fn invoice_items_with_settlement_range() {
+        let now = Utc::now();
+
+        let supplier = Supplier {
+            billing_amount: Money::from(600000),
+            billing_type: BillingType::Hourly,
+            settlement_range: SettlementRange::new(140, 180, Money::from(3500), Money::from(4000))
+                .unwrap(),
+            ..supplier(now)
+        };
+
+        let hours = |minutes| {
+            Some(WorkingHours::new(
+                "".to_string(),
+                YM {
+                    year: 2021,
+                    month: 8,
+                },
+                minutes,
+                now,
+            ))
+        };
+
+        assert!(supplier
+            .invoice_items("件名".to_string(), vec![], None, None, now)
+            .is_err());
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], hours(160 * 60), None, now)
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].unit_price, Money::from(600000));
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], hours(190 * 60 + 30), None, now)
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].name, "超過精算（10.5時間 × 3500円）");
+        assert_eq!(items[1].unit_price, Money::from(36750));
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], hours(130 * 60 - 20), None, now)
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].name, "控除精算（10.33時間 × 4000円）");
+        assert_eq!(items[1].unit_price, Money::from(-41333));
+
+        let supplier = Supplier {
+            currency: Currency::USD,
+            withholding_tax: false,
+            ..supplier
+        };
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], hours(190 * 60 + 30), None, now)
+            .unwrap();
+        assert_eq!(items[1].name, "超過精算（10.5時間 × 35.00 USD）");
+        assert_eq!(items[1].unit_price, Money::from(36750));
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], hours(130 * 60 - 20), None, now)
+            .unwrap();
+        assert_eq!(items[1].name, "控除精算（10.33時間 × 40.00 USD）");
+    }
+
+    #[test]
+    fn invoice_items_with_retainer() {
+        let now = Utc::now();
+
+        let supplier = Supplier {
+            billing_amount: Money::from(200000),
+            billing_type: BillingType::Retainer,
+            retainer: RetainerAllowance::new(20, "時間".to_string(), Money::from(12000)).unwrap(),
+            ..supplier(now)
+        };
+
+        let usage = |quantity| {
+            Some(RetainerUsage::new(
+                "".to_string(),
+                YM {
+                    year: 2026,
+                    month: 9,
+                },
+                quantity,
+                now,
+            ))
+        };
+
+        assert!(supplier
+            .invoice_items("件名".to_string(), vec![], None, None, now)
+            .is_err());
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], None, usage(20), now)
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].unit_price, Money::from(200000));
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], None, usage(23), now)
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].name, "超過利用（契約20時間を超えた分）");
+        assert_eq!(items[1].quantity, 3);
+        assert_eq!(items[1].unit, "時間");
+        assert_eq!(items[1].amount(), Money::from(36000));
+        assert_eq!(supplier.retainer.overage_amount(23), Money::from(36000));
+
+        assert!(RetainerAllowance::new(-1, "件".to_string(), Money::from(0)).is_err());
+    }
+
+    #[test]
+    fn invoice_items_with_supplier_items() {
+        let now = Utc::now();
+
+        let supplier = Supplier {
+            billing_amount: Money::from(300000),
+            billing_type: BillingType::Monthly,
+            ..supplier(now)
+        };
+
+        let items = supplier
+            .invoice_items("件名".to_string(), vec![], None, None, now)
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "件名");
+        assert_eq!(items[0].unit_price, Money::from(300000));
+
+        let supplier_items = vec![
+            SupplierItem::new(
+                "".to_string(),
+                "システム開発".to_string(),
+                1,
+                "人月".to_string(),
+                Money::from(800000),
+                TaxType::Standard10,
+                true,
+                now,
+            )
+            .unwrap(),
+            SupplierItem::new(
+                "".to_string(),
+                "サーバー費用".to_string(),
+                2,
+                "台".to_string(),
+                Money::from(15000),
+                TaxType::Standard10,
+                false,
+                now,
+            )
+            .unwrap(),
+        ];
+
+        let items = supplier
+            .invoice_items("件名".to_string(), supplier_items, None, None, now)
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].name, "システム開発");
+        assert_eq!(items[1].quantity, 2);
+        assert_eq!(items[1].unit, "台");
+        assert!(items[0].withholding);
+        assert!(!items[1].withholding);
     }
 }
@@ -0,0 +1,154 @@
+use crate::domain::invoice::{InvoiceItem, TaxType};
+use crate::domain::money::Money;
+use crate::domain::YM;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 1回だけ請求に加える値引き・過請求の調整・追加請求。target_ym が空の場合は次に発行する請求書に含める
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SupplierAdjustment {
+    pub id: String,
+    pub supplier_id: String,
+    pub description: String,
+    /// 値引きの場合は負の金額
+    pub amount: Money,
+    pub tax_type: TaxType,
+    /// この月を締め月とする請求で適用する
+    pub target_ym: YM,
+    /// 適用済みの場合は発行した請求書
+    pub invoice_id: Option<String>,
+    pub cancelled: bool,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl SupplierAdjustment {
+    pub fn new(
+        supplier_id: String,
+        description: String,
+        amount: Money,
+        tax_type: TaxType,
+        target_ym: YM,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        if description.is_empty() {
+            return Err("内容を入力してください".to_string());
+        }
+        if amount.is_zero() {
+            return Err("金額を入力してください".to_string());
+        }
+        Ok(SupplierAdjustment {
+            id: Uuid::new_v4().to_string(),
+            supplier_id,
+            description,
+            amount,
+            tax_type,
+            target_ym,
+            invoice_id: None,
+            cancelled: false,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        })
+    }
+
+    pub fn is_applied(&self) -> bool {
+        self.invoice_id.is_some()
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.is_applied() && !self.cancelled
+    }
+
+    /// ymを締め月とする請求で適用するか。対象月の請求が休止などで発行されなかった場合は次の請求で適用する
+    pub fn is_due(&self, ym: &YM) -> bool {
+        self.is_pending() && (self.target_ym.is_empty() || &self.target_ym <= ym)
+    }
+
+    pub fn cancel(&mut self, now: DateTime<Utc>) -> Result<(), String> {
+        if self.is_applied() {
+            return Err("請求済みの調整は取り消せません".to_string());
+        }
+        if self.cancelled {
+            return Err("すでに取り消されています".to_string());
+        }
+        self.cancelled = true;
+        self.updated_at = now.naive_utc();
+        Ok(())
+    }
+
+    pub fn mark_applied(&mut self, invoice_id: String, now: DateTime<Utc>) {
+        self.invoice_id = Some(invoice_id);
+        self.updated_at = now.naive_utc();
+    }
+
+    pub fn invoice_item(&self, withholding: bool) -> InvoiceItem {
+        InvoiceItem {
+            name: self.description.clone(),
+            quantity: 1,
+            unit: "式".to_string(),
+            unit_price: self.amount.clone(),
+            tax_type: self.tax_type.clone(),
+            withholding,
+        }
+    }
+}
+
+#[cfg(test)]
+mod supplier_adjustment_tests {
+    use crate::domain::invoice::TaxType;
+    use crate::domain::money::Money;
+    use crate::domain::supplier_adjustment::SupplierAdjustment;
+    use crate::domain::YM;
+    use chrono::{TimeZone, Utc};
+    use std::str::FromStr;
+
+    #[test]
+    fn adjustment_is_due() {
+        let now = Utc.ymd(2021, 4, 10).and_hms(0, 0, 0);
+        let ym = |s: &str| YM::from_str(s).unwrap();
+
+        assert!(SupplierAdjustment::new(
+            "".to_string(),
+            "値引き".to_string(),
+            Money::from(0),
+            TaxType::Standard10,
+            ym(""),
+            now
+        )
+        .is_err());
+
+        let next = SupplierAdjustment::new(
+            "".to_string(),
+            "値引き".to_string(),
+            Money::from(-5000),
+            TaxType::Standard10,
+            ym(""),
+            now,
+        )
+        .unwrap();
+        assert!(next.is_due(&ym("2021-04")));
+        assert_eq!(next.invoice_item(true).amount(), Money::from(-5000));
+
+        let mut targeted = SupplierAdjustment::new(
+            "".to_string(),
+            "3月分の過請求".to_string(),
+            Money::from(-10000),
+            TaxType::Standard10,
+            ym("2021-05"),
+            now,
+        )
+        .unwrap();
+        assert!(!targeted.is_due(&ym("2021-04")));
+        assert!(targeted.is_due(&ym("2021-05")));
+        assert!(targeted.is_due(&ym("2021-06")));
+
+        targeted.cancel(now).unwrap();
+        assert!(!targeted.is_due(&ym("2021-05")));
+        assert!(targeted.cancel(now).is_err());
+
+        let mut applied = next.clone();
+        applied.mark_applied("1".to_string(), now);
+        assert!(!applied.is_due(&ym("2021-04")));
+        assert!(applied.cancel(now).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::domain::invoice::{InvoiceItem, TaxType};
+use crate::domain::money::Money;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 請求書に毎回載せる明細行。登録がある場合は請求金額の1行の代わりに使う
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SupplierItem {
+    pub id: String,
+    pub supplier_id: String,
+    pub name: String,
+    pub quantity: i32,
+    pub unit: String,
+    pub unit_price: Money,
+    pub tax_type: TaxType,
+    /// 源泉徴収の対象とするか
+    pub withholding: bool,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl SupplierItem {
+    pub fn new(
+        supplier_id: String,
+        name: String,
+        quantity: i32,
+        unit: String,
+        unit_price: Money,
+        tax_type: TaxType,
+        withholding: bool,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        validate(&name, quantity)?;
+        Ok(SupplierItem {
+            id: Uuid::new_v4().to_string(),
+            supplier_id,
+            name,
+            quantity,
+            unit,
+            unit_price,
+            tax_type,
+            withholding,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        })
+    }
+
+    pub fn update(
+        &mut self,
+        name: String,
+        quantity: i32,
+        unit: String,
+        unit_price: Money,
+        tax_type: TaxType,
+        withholding: bool,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        validate(&name, quantity)?;
+        self.name = name;
+        self.quantity = quantity;
+        self.unit = unit;
+        self.unit_price = unit_price;
+        self.tax_type = tax_type;
+        self.withholding = withholding;
+        self.updated_at = now.naive_utc();
+        Ok(())
+    }
+
+    pub fn amount(&self) -> Money {
+        self.unit_price.times(self.quantity)
+    }
+
+    pub fn to_invoice_item(&self) -> InvoiceItem {
+        InvoiceItem {
+            name: self.name.clone(),
+            quantity: self.quantity,
+            unit: self.unit.clone(),
+            unit_price: self.unit_price.clone(),
+            tax_type: self.tax_type.clone(),
+            withholding: self.withholding,
+        }
+    }
+}
+
+fn validate(name: &str, quantity: i32) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("明細名を入力してください".to_string());
+    }
+    if quantity <= 0 {
+        return Err("数量は1以上で指定してください".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use crate::domain::invoice::TaxRounding;
+use crate::domain::money::Money;
+use crate::domain::YM;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 着手金・中間金・残金のように分割して請求する区切り。target_ym が空の場合は手動で請求する
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SupplierMilestone {
+    pub id: String,
+    pub supplier_id: String,
+    pub name: String,
+    /// 並び順（昇順）
+    pub position: i32,
+    pub amount_type: MilestoneAmountType,
+    /// 金額（通貨の最小単位）または契約金額に対する割合（%）
+    pub amount_value: Money,
+    /// この月を締め月とする請求で発行する
+    pub target_ym: YM,
+    /// 請求済みの場合は発行した請求書
+    pub invoice_id: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl SupplierMilestone {
+    pub fn new(
+        supplier_id: String,
+        name: String,
+        position: i32,
+        amount_type: MilestoneAmountType,
+        amount_value: Money,
+        target_ym: YM,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        validate(&name, &amount_type, &amount_value)?;
+        Ok(SupplierMilestone {
+            id: Uuid::new_v4().to_string(),
+            supplier_id,
+            name,
+            position,
+            amount_type,
+            amount_value,
+            target_ym,
+            invoice_id: None,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        })
+    }
+
+    pub fn update(
+        &mut self,
+        name: String,
+        position: i32,
+        amount_type: MilestoneAmountType,
+        amount_value: Money,
+        target_ym: YM,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        if self.is_billed() {
+            return Err("請求済みのマイルストーンは変更できません".to_string());
+        }
+        validate(&name, &amount_type, &amount_value)?;
+        self.name = name;
+        self.position = position;
+        self.amount_type = amount_type;
+        self.amount_value = amount_value;
+        self.target_ym = target_ym;
+        self.updated_at = now.naive_utc();
+        Ok(())
+    }
+
+    pub fn is_billed(&self) -> bool {
+        self.invoice_id.is_some()
+    }
+
+    /// ymを締め月とする請求で発行する未請求のマイルストーンか
+    pub fn is_due(&self, ym: &YM) -> bool {
+        !self.is_billed() && !self.target_ym.is_empty() && &self.target_ym == ym
+    }
+
+    pub fn mark_billed(&mut self, invoice_id: String, now: DateTime<Utc>) {
+        self.invoice_id = Some(invoice_id);
+        self.updated_at = now.naive_utc();
+    }
+
+    /// 割合指定の場合は契約金額に割合を掛け、消費税と同じ丸め方で端数処理する
+    pub fn amount(&self, contract_amount: &Money, rounding: &TaxRounding) -> Money {
+        match self.amount_type {
+            MilestoneAmountType::Fixed => self.amount_value.clone(),
+            MilestoneAmountType::Percentage => {
+                contract_amount.percent(&self.amount_value, rounding)
+            }
+        }
+    }
+}
+
+/// マイルストーンの合計が契約金額を超えていないか
+pub fn validate_total(
+    milestones: &[SupplierMilestone],
+    contract_amount: &Money,
+    rounding: &TaxRounding,
+) -> Result<(), String> {
+    let total: Money = milestones
+        .iter()
+        .map(|v| v.amount(contract_amount, rounding))
+        .sum();
+    if &total > contract_amount {
+        return Err("マイルストーンの合計が契約金額を超えています".to_string());
+    }
+    Ok(())
+}
+
+fn validate(
+    name: &str,
+    amount_type: &MilestoneAmountType,
+    amount_value: &Money,
+) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("マイルストーン名を入力してください".to_string());
+    }
+    match amount_type {
+        MilestoneAmountType::Fixed => {
+            if !amount_value.is_positive() {
+                return Err("金額は1円以上で指定してください".to_string());
+            }
+        }
+        MilestoneAmountType::Percentage => {
+            if amount_value < &Money::from(1) || amount_value > &Money::from(100) {
+                return Err("割合は1〜100%で指定してください".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MilestoneAmountType {
+    Fixed,
+    Percentage,
+}
+
+impl MilestoneAmountType {
+    pub fn int(&self) -> i32 {
+        match self {
+            Self::Fixed => 0,
+            Self::Percentage => 1,
+        }
+    }
+}
+
+impl Default for MilestoneAmountType {
+    fn default() -> Self {
+        Self::Fixed
+    }
+}
+
+impl From<i32> for MilestoneAmountType {
+    fn from(v: i32) -> MilestoneAmountType {
+        match v {
+            0 => Self::Fixed,
+            1 => Self::Percentage,
+            _ => Self::default(),
+        }
+    }
+}
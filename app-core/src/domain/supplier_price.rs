@@ -0,0 +1,67 @@
+use crate::domain::money::Money;
+use crate::domain::YM;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 請求金額の改定履歴。effective_from の月を締め月とする請求から適用する
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SupplierPrice {
+    pub id: String,
+    pub supplier_id: String,
+    pub effective_from: YM,
+    pub billing_amount: Money,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl SupplierPrice {
+    pub fn new(
+        supplier_id: String,
+        effective_from: YM,
+        billing_amount: Money,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        validate(&effective_from, &billing_amount)?;
+        Ok(SupplierPrice {
+            id: Uuid::new_v4().to_string(),
+            supplier_id,
+            effective_from,
+            billing_amount,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        })
+    }
+
+    pub fn update(&mut self, billing_amount: Money, now: DateTime<Utc>) -> Result<(), String> {
+        validate(&self.effective_from, &billing_amount)?;
+        self.billing_amount = billing_amount;
+        self.updated_at = now.naive_utc();
+        Ok(())
+    }
+}
+
+/// ymの時点で有効な改定（適用開始月がym以前で最も新しいもの）
+pub fn price_at<'a>(prices: &'a [SupplierPrice], ym: &YM) -> Option<&'a SupplierPrice> {
+    prices
+        .iter()
+        .filter(|v| &v.effective_from <= ym)
+        .max_by(|a, b| a.effective_from.cmp(&b.effective_from))
+}
+
+/// ymより後に予定されている直近の改定
+pub fn upcoming_price<'a>(prices: &'a [SupplierPrice], ym: &YM) -> Option<&'a SupplierPrice> {
+    prices
+        .iter()
+        .filter(|v| &v.effective_from > ym)
+        .min_by(|a, b| a.effective_from.cmp(&b.effective_from))
+}
+
+fn validate(effective_from: &YM, billing_amount: &Money) -> Result<(), String> {
+    if effective_from.is_empty() {
+        return Err("適用開始月を指定してください".to_string());
+    }
+    if billing_amount < &Money::zero() {
+        return Err("請求金額は0以上で指定してください".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::domain::YM;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 継続請求を休止する期間。from_ym〜to_ym を締め月とする請求は発行しない
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SupplierSuspension {
+    pub id: String,
+    pub supplier_id: String,
+    pub from_ym: YM,
+    pub to_ym: YM,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl SupplierSuspension {
+    pub fn new(
+        supplier_id: String,
+        from_ym: YM,
+        to_ym: YM,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        if from_ym.is_empty() || to_ym.is_empty() {
+            return Err("休止期間の開始月と終了月を指定してください".to_string());
+        }
+        if to_ym < from_ym {
+            return Err("休止期間の終了月は開始月以降で指定してください".to_string());
+        }
+        Ok(SupplierSuspension {
+            id: Uuid::new_v4().to_string(),
+            supplier_id,
+            from_ym,
+            to_ym,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        })
+    }
+
+    pub fn covers(&self, ym: &YM) -> bool {
+        &self.from_ym <= ym && ym <= &self.to_ym
+    }
+}
@@ -1,9 +1,23 @@
+use crate::domain::invoice_number::InvoiceNumberFormat;
+use crate::domain::mail_template::MailTemplate;
+use crate::domain::reminder::ReminderSchedule;
 use chrono::{DateTime, Utc};
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct User {
     pub id: String,
     pub misoca_refresh_token: String,
+    /// 空の場合はmisocaで採番する
+    pub invoice_number_format: String,
+    /// 督促メールを送る日（支払期限からの日数）。空の場合は督促しない
+    pub reminder_schedule: String,
+    /// 督促メールの件名・本文のテンプレート。空の場合は既定のテンプレートを使う
+    pub reminder_subject_template: String,
+    pub reminder_body_template: String,
+    /// 請求書を送付するメールの件名・本文のテンプレート。空の場合は既定のテンプレートを使う
+    pub invoice_mail_subject_template: String,
+    pub invoice_mail_body_template: String,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -13,6 +27,12 @@ impl User {
         User {
             id,
             misoca_refresh_token: "".to_string(),
+            invoice_number_format: "".to_string(),
+            reminder_schedule: "".to_string(),
+            reminder_subject_template: "".to_string(),
+            reminder_body_template: "".to_string(),
+            invoice_mail_subject_template: "".to_string(),
+            invoice_mail_body_template: "".to_string(),
             created_at: now.naive_utc(),
             updated_at: now.naive_utc(),
         }
@@ -22,4 +42,46 @@ impl User {
         self.misoca_refresh_token = token;
         self.updated_at = now.naive_utc();
     }
+
+    pub fn update_invoice_number_format(
+        &mut self,
+        invoice_number_format: String,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        InvoiceNumberFormat::from_str(invoice_number_format.as_str())?;
+        self.invoice_number_format = invoice_number_format;
+        self.updated_at = now.naive_utc();
+        Ok(())
+    }
+
+    pub fn update_reminder_setting(
+        &mut self,
+        reminder_schedule: String,
+        reminder_subject_template: String,
+        reminder_body_template: String,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        ReminderSchedule::from_str(reminder_schedule.as_str())?;
+        MailTemplate::from_str(reminder_subject_template.as_str())?;
+        MailTemplate::from_str(reminder_body_template.as_str())?;
+        self.reminder_schedule = reminder_schedule;
+        self.reminder_subject_template = reminder_subject_template;
+        self.reminder_body_template = reminder_body_template;
+        self.updated_at = now.naive_utc();
+        Ok(())
+    }
+
+    pub fn update_invoice_mail_setting(
+        &mut self,
+        invoice_mail_subject_template: String,
+        invoice_mail_body_template: String,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        MailTemplate::from_str(invoice_mail_subject_template.as_str())?;
+        MailTemplate::from_str(invoice_mail_body_template.as_str())?;
+        self.invoice_mail_subject_template = invoice_mail_subject_template;
+        self.invoice_mail_body_template = invoice_mail_body_template;
+        self.updated_at = now.naive_utc();
+        Ok(())
+    }
 }
@@ -0,0 +1,36 @@
+use crate::domain::YM;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 時間精算の請求先の月ごとの稼働実績（分単位）
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WorkingHours {
+    pub id: String,
+    pub supplier_id: String,
+    pub ym: YM,
+    pub minutes: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl WorkingHours {
+    pub fn new(supplier_id: String, ym: YM, minutes: i32, now: DateTime<Utc>) -> Self {
+        WorkingHours {
+            id: Uuid::new_v4().to_string(),
+            supplier_id,
+            ym,
+            minutes,
+            created_at: now.naive_utc(),
+            updated_at: now.naive_utc(),
+        }
+    }
+
+    pub fn update(&mut self, minutes: i32, now: DateTime<Utc>) {
+        self.minutes = minutes;
+        self.updated_at = now.naive_utc();
+    }
+
+    pub fn hours(&self) -> f64 {
+        f64::from(self.minutes) / 60.0
+    }
+}
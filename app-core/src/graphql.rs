@@ -5,28 +5,63 @@ use juniper::*;
 use juniper_from_schema::graphql_schema_from_file;
 
 use crate::ddb;
+use crate::domain::money::Money;
 use crate::graphql::bank::*;
+use crate::graphql::billing_summary::*;
+use crate::graphql::exchange_rate::*;
+use crate::graphql::expense::*;
+use crate::graphql::holiday::*;
 use crate::graphql::invoice::*;
+use crate::graphql::invoice_delivery::*;
 use crate::graphql::invoice_history::*;
 use crate::graphql::me::*;
+use crate::graphql::overdue_invoice::*;
 use crate::graphql::page_info::*;
+use crate::graphql::payment::*;
+use crate::graphql::retainer_usage::*;
 use crate::graphql::sender::*;
 use crate::graphql::supplier::*;
+use crate::graphql::supplier_adjustment::*;
+use crate::graphql::supplier_item::*;
+use crate::graphql::supplier_milestone::*;
+use crate::graphql::supplier_outstanding::*;
+use crate::graphql::supplier_price::*;
+use crate::graphql::supplier_suspension::*;
+use crate::graphql::tax_summary::*;
+use crate::graphql::working_hours::*;
+use crate::mail;
 use crate::misoca;
 
 use self::mutation::*;
 use self::query::*;
 
 mod bank;
+mod billing_summary;
+mod exchange_rate;
+mod expense;
 mod get_misoca_token;
+mod holiday;
 mod invoice;
+mod invoice_delivery;
 mod invoice_history;
 mod me;
+mod money;
 mod mutation;
+mod overdue_invoice;
 mod page_info;
+mod payment;
 mod query;
+mod retainer_usage;
 mod sender;
 mod supplier;
+mod supplier_adjustment;
+mod supplier_item;
+mod supplier_milestone;
+mod supplier_outstanding;
+mod supplier_price;
+mod supplier_suspension;
+mod tax_summary;
+mod working_hours;
 
 #[allow(unused)]
 graphql_schema_from_file!("src/graphql/schema.graphql", context_type: Context);
@@ -34,6 +69,8 @@ graphql_schema_from_file!("src/graphql/schema.graphql", context_type: Context);
 pub struct Context {
     pub authenticated_user_id: Option<String>,
     pub misoca_cli: misoca::Client,
+    /// SMTPの設定がない環境ではメールを送らない
+    pub mail_cli: Option<mail::Client>,
     pub connection: Arc<Mutex<MysqlConnection>>,
     pub invoice_loader_by_supplier: ddb::invoice::LoaderBySupplier,
 }
@@ -41,11 +78,16 @@ pub struct Context {
 impl juniper::Context for Context {}
 
 impl Context {
-    pub fn new(authenticated_user_id: Option<String>, misoca_cli: misoca::Client) -> Self {
+    pub fn new(
+        authenticated_user_id: Option<String>,
+        misoca_cli: misoca::Client,
+        mail_cli: Option<mail::Client>,
+    ) -> Self {
         let conn_ref = Arc::new(Mutex::new(ddb::establish_connection()));
         Self {
             authenticated_user_id,
             misoca_cli,
+            mail_cli,
             connection: Arc::clone(&conn_ref),
             invoice_loader_by_supplier: ddb::invoice::BatcherBySupplier::new_loader(Arc::clone(
                 &conn_ref,
@@ -0,0 +1,53 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct BillingSummary {
+    pub summary: domain::invoice::BillingSummary,
+}
+#[async_trait]
+impl BillingSummaryFields for BillingSummary {
+    fn field_ym(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.summary.ym.to_string())
+    }
+
+    fn field_currency(&self, _: &Executor<Context>) -> FieldResult<GraphQLCurrency> {
+        Ok(match self.summary.currency {
+            domain::currency::Currency::JPY => GraphQLCurrency::Jpy,
+            domain::currency::Currency::USD => GraphQLCurrency::Usd,
+            domain::currency::Currency::EUR => GraphQLCurrency::Eur,
+        })
+    }
+
+    fn field_billed_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.summary.billed_amount.clone())
+    }
+
+    fn field_cancelled_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.summary.cancelled_amount.clone())
+    }
+
+    fn field_credited_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.summary.credited_amount.clone())
+    }
+
+    fn field_net_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.summary.net_amount())
+    }
+
+    fn field_billed_amount_jpy(&self, _: &Executor<Context>) -> FieldResult<Option<Money>> {
+        Ok(self.summary.billed_amount_jpy.clone())
+    }
+
+    fn field_cancelled_amount_jpy(&self, _: &Executor<Context>) -> FieldResult<Option<Money>> {
+        Ok(self.summary.cancelled_amount_jpy.clone())
+    }
+
+    fn field_credited_amount_jpy(&self, _: &Executor<Context>) -> FieldResult<Option<Money>> {
+        Ok(self.summary.credited_amount_jpy.clone())
+    }
+
+    fn field_net_amount_jpy(&self, _: &Executor<Context>) -> FieldResult<Option<Money>> {
+        Ok(self.summary.net_amount_jpy())
+    }
+}
@@ -0,0 +1,25 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct ExchangeRate {
+    pub exchange_rate: domain::currency::ExchangeRate,
+}
+#[async_trait]
+impl ExchangeRateFields for ExchangeRate {
+    fn field_currency(&self, _: &Executor<Context>) -> FieldResult<GraphQLCurrency> {
+        Ok(match self.exchange_rate.currency {
+            domain::currency::Currency::JPY => GraphQLCurrency::Jpy,
+            domain::currency::Currency::USD => GraphQLCurrency::Usd,
+            domain::currency::Currency::EUR => GraphQLCurrency::Eur,
+        })
+    }
+
+    fn field_ymd(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.exchange_rate.ymd.to_string())
+    }
+
+    fn field_rate(&self, _: &Executor<Context>) -> FieldResult<f64> {
+        Ok(self.exchange_rate.rate as f64 / 100.0)
+    }
+}
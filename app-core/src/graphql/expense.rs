@@ -0,0 +1,64 @@
+use crate::graphql::*;
+use crate::INVOICE_BUCKET;
+use crate::INVOICE_PDF_DOWNLOAD_DURATION;
+use crate::{domain, FieldErrorWithCode};
+use cloud_storage::Object;
+
+#[derive(Debug, Clone)]
+pub struct Expense {
+    pub expense: domain::expense::Expense,
+}
+#[async_trait]
+impl ExpenseFields for Expense {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.expense.id.clone()))
+    }
+
+    fn field_ymd(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.expense.ymd.to_string())
+    }
+
+    fn field_description(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.expense.description.clone())
+    }
+
+    fn field_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.expense.amount.clone())
+    }
+
+    fn field_tax_type(&self, _: &Executor<Context>) -> FieldResult<GraphQLTaxType> {
+        Ok(match self.expense.tax_type {
+            domain::invoice::TaxType::Standard10 => GraphQLTaxType::Standard10,
+            domain::invoice::TaxType::Reduced8 => GraphQLTaxType::Reduced8,
+            domain::invoice::TaxType::NonTaxable => GraphQLTaxType::NonTaxable,
+        })
+    }
+
+    fn field_has_receipt(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.expense.receipt_path.is_some())
+    }
+
+    async fn field_receipt_download_url<'s, 'r, 'a>(
+        &'s self,
+        _: &Executor<'r, 'a, Context>,
+    ) -> FieldResult<Option<String>> {
+        let path = match self.expense.receipt_path.clone() {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        let download_url = Object::read(INVOICE_BUCKET, path.as_str())
+            .await
+            .map(|o| o.download_url(INVOICE_PDF_DOWNLOAD_DURATION))
+            .map_err(FieldErrorWithCode::from)?;
+        Ok(download_url.ok())
+    }
+
+    fn field_billed(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.expense.is_billed())
+    }
+
+    fn field_invoice_id(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        Ok(self.expense.invoice_id.clone())
+    }
+}
@@ -0,0 +1,17 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct Holiday {
+    pub holiday: domain::calendar::Holiday,
+}
+#[async_trait]
+impl HolidayFields for Holiday {
+    fn field_ymd(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.holiday.date.format("%Y-%m-%d").to_string())
+    }
+
+    fn field_name(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.holiday.name.clone())
+    }
+}
@@ -1,5 +1,6 @@
-use crate::domain;
+use crate::ddb::Dao;
 use crate::graphql::*;
+use crate::{domain, CoreError, FieldErrorWithCode};
 use juniper_from_schema::{QueryTrail, Walked};
 
 #[derive(Debug, Clone)]
@@ -28,6 +29,8 @@ impl InvoiceFields for Invoice {
         Ok(match self.invoice.payment_status {
             domain::invoice::PaymentStatus::UnPaid => GraphQLPaymentStatus::UnPaid,
             domain::invoice::PaymentStatus::Paid => GraphQLPaymentStatus::Paid,
+            domain::invoice::PaymentStatus::PartiallyPaid => GraphQLPaymentStatus::PartiallyPaid,
+            domain::invoice::PaymentStatus::Overpaid => GraphQLPaymentStatus::Overpaid,
         })
     }
 
@@ -35,6 +38,7 @@ impl InvoiceFields for Invoice {
         Ok(match self.invoice.invoice_status {
             domain::invoice::InvoiceStatus::UnSubmitted => GraphQLInvoiceStatus::UnSubmitted,
             domain::invoice::InvoiceStatus::Submitted => GraphQLInvoiceStatus::Submitted,
+            domain::invoice::InvoiceStatus::Cancelled => GraphQLInvoiceStatus::Cancelled,
         })
     }
 
@@ -46,13 +50,187 @@ impl InvoiceFields for Invoice {
         Ok(self.invoice.subject.clone())
     }
 
-    fn field_total_amount(&self, _: &Executor<Context>) -> FieldResult<i32> {
+    fn field_currency(&self, _: &Executor<Context>) -> FieldResult<GraphQLCurrency> {
+        Ok(match self.invoice.currency {
+            domain::currency::Currency::JPY => GraphQLCurrency::Jpy,
+            domain::currency::Currency::USD => GraphQLCurrency::Usd,
+            domain::currency::Currency::EUR => GraphQLCurrency::Eur,
+        })
+    }
+
+    fn field_total_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
         Ok(self.invoice.total_amount.clone())
     }
 
-    fn field_tax(&self, _: &Executor<Context>) -> FieldResult<i32> {
+    fn field_tax(&self, _: &Executor<Context>) -> FieldResult<Money> {
         Ok(self.invoice.tax.clone())
     }
+
+    fn field_withholding_tax(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.invoice.withholding_tax.clone())
+    }
+
+    fn field_payment_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.invoice.payment_amount.clone())
+    }
+
+    fn field_paid_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.invoice.paid_amount.clone())
+    }
+
+    fn field_outstanding_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.invoice.outstanding_amount())
+    }
+
+    async fn field_payment_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Payment, Walked>,
+    ) -> FieldResult<Vec<Payment>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let payment_dao: Dao<domain::payment::Payment> = Dao::new();
+
+        let payments = payment_dao
+            .get_all_by_invoice(&conn, self.invoice.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(payments
+            .iter()
+            .map(|v| Payment {
+                payment: v.to_owned(),
+            })
+            .collect())
+    }
+
+    async fn field_delivery_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, InvoiceDelivery, Walked>,
+    ) -> FieldResult<Vec<InvoiceDelivery>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let invoice_delivery_dao: Dao<domain::invoice_delivery::InvoiceDelivery> = Dao::new();
+
+        let deliveries = invoice_delivery_dao
+            .get_all_by_invoice(&conn, self.invoice.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(deliveries
+            .iter()
+            .map(|v| InvoiceDelivery {
+                delivery: v.to_owned(),
+            })
+            .collect())
+    }
+
+    async fn field_exchange_rate<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, ExchangeRate, Walked>,
+    ) -> FieldResult<Option<ExchangeRate>> {
+        let exchange_rate = self.exchange_rate(exec.context())?;
+        Ok(exchange_rate.map(|v| ExchangeRate { exchange_rate: v }))
+    }
+
+    async fn field_total_amount_jpy<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+    ) -> FieldResult<Option<Money>> {
+        self.to_jpy(exec.context(), &self.invoice.total_amount)
+    }
+
+    async fn field_payment_amount_jpy<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+    ) -> FieldResult<Option<Money>> {
+        self.to_jpy(exec.context(), &self.invoice.payment_amount)
+    }
+
+    fn field_tax_summary_list<'s, 'r>(
+        &'s self,
+        _: &Executor<Context>,
+        _: &QueryTrail<'r, TaxSummary, Walked>,
+    ) -> FieldResult<Vec<TaxSummary>> {
+        Ok(self
+            .invoice
+            .tax_summaries
+            .iter()
+            .map(|v| TaxSummary { summary: v.clone() })
+            .collect())
+    }
+
+    fn field_remarks(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.invoice.remarks.clone())
+    }
+
+    fn field_cancelled_at(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        Ok(self.invoice.cancelled_at.map(|v| v.to_string()))
+    }
+
+    fn field_cancel_reason(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.invoice.cancel_reason.clone())
+    }
+
+    async fn field_credit_note<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Invoice, Walked>,
+    ) -> FieldResult<Option<Invoice>> {
+        self.linked(exec.context(), &self.invoice.credit_note_id)
+    }
+
+    async fn field_original_invoice<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Invoice, Walked>,
+    ) -> FieldResult<Option<Invoice>> {
+        self.linked(exec.context(), &self.invoice.original_invoice_id)
+    }
+}
+
+impl Invoice {
+    /// 発行日の仲値。円の請求書は換算しないためNone
+    fn exchange_rate(&self, ctx: &Context) -> FieldResult<Option<domain::currency::ExchangeRate>> {
+        if self.invoice.currency == domain::currency::Currency::JPY {
+            return Ok(None);
+        }
+
+        let conn = ctx.get_mutex_connection();
+        let exchange_rate_dao: Dao<domain::currency::ExchangeRate> = Dao::new();
+        match exchange_rate_dao.get_latest_on(
+            &conn,
+            &self.invoice.currency,
+            &self.invoice.issue_ymd,
+        ) {
+            Ok(v) => Ok(Some(v)),
+            Err(CoreError::NotFound) => Ok(None),
+            Err(e) => Err(FieldErrorWithCode::from(e).into()),
+        }
+    }
+
+    /// 発行日の仲値で円に換算する。仲値が登録されていない場合はNone
+    fn to_jpy(&self, ctx: &Context, amount: &Money) -> FieldResult<Option<Money>> {
+        if self.invoice.currency == domain::currency::Currency::JPY {
+            return Ok(Some(amount.clone()));
+        }
+        Ok(self.exchange_rate(ctx)?.map(|v| v.to_jpy(amount)))
+    }
+
+    /// 赤伝と元の請求書の間の参照をたどる
+    fn linked(&self, ctx: &Context, id: &Option<String>) -> FieldResult<Option<Invoice>> {
+        let id = match id {
+            Some(id) => id.clone(),
+            None => return Ok(None),
+        };
+
+        let conn = ctx.get_mutex_connection();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let invoice = invoice_dao
+            .get(&conn, id)
+            .map_err(FieldErrorWithCode::from)?;
+        Ok(Some(Invoice { invoice }))
+    }
 }
 
 #[derive(Debug, Clone)]
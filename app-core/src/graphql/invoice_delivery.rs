@@ -0,0 +1,33 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct InvoiceDelivery {
+    pub delivery: domain::invoice_delivery::InvoiceDelivery,
+}
+#[async_trait]
+impl InvoiceDeliveryFields for InvoiceDelivery {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.delivery.id.clone()))
+    }
+
+    fn field_sent_to(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.delivery.sent_to.clone())
+    }
+
+    fn field_cc(&self, _: &Executor<Context>) -> FieldResult<Vec<String>> {
+        Ok(self.delivery.cc.clone())
+    }
+
+    fn field_bcc(&self, _: &Executor<Context>) -> FieldResult<Vec<String>> {
+        Ok(self.delivery.bcc.clone())
+    }
+
+    fn field_subject(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.delivery.subject.clone())
+    }
+
+    fn field_sent_at(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.delivery.sent_at.to_string())
+    }
+}
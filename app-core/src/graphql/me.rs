@@ -13,6 +13,30 @@ impl MeFields for Me {
         Ok(Into::into(self.user.id.clone()))
     }
 
+    fn field_invoice_number_format(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.user.invoice_number_format.clone())
+    }
+
+    fn field_reminder_schedule(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.user.reminder_schedule.clone())
+    }
+
+    fn field_reminder_subject_template(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.user.reminder_subject_template.clone())
+    }
+
+    fn field_reminder_body_template(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.user.reminder_body_template.clone())
+    }
+
+    fn field_invoice_mail_subject_template(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.user.invoice_mail_subject_template.clone())
+    }
+
+    fn field_invoice_mail_body_template(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.user.invoice_mail_body_template.clone())
+    }
+
     async fn field_supplier_list<'s, 'r, 'a>(
         &'s self,
         exec: &Executor<'r, 'a, Context>,
@@ -0,0 +1,21 @@
+use crate::graphql::*;
+use std::str::FromStr;
+
+/// Intの範囲を超える金額を扱えるよう、10進数の文字列でやりとりする
+#[juniper::graphql_scalar(name = "Money")]
+impl<S> GraphQLScalar for Money
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Option<Money> {
+        v.as_string_value().and_then(|s| Money::from_str(s).ok())
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
+    }
+}
@@ -4,6 +4,9 @@ use crate::graphql::supplier::Supplier;
 use crate::graphql::Context;
 use crate::graphql::*;
 use crate::misoca;
+use crate::task::allocate_invoice_number;
+use crate::task::send_invoice;
+use crate::task::sync_invoice;
 use crate::INVOICE_BUCKET;
 use crate::INVOICE_PDF_DOWNLOAD_DURATION;
 use crate::{domain, FieldErrorWithCode};
@@ -82,11 +85,67 @@ impl MutationFields for Mutation {
         let end_ym: String = input.end_ym;
         let subject: String = input.subject;
         let subject_template: String = input.subject_template;
-        let billing_amount: i32 = input.billing_amount;
+        domain::subject_template::SubjectTemplate::from_str(subject_template.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let billing_amount: Money = input.billing_amount;
         let billing_type = match input.billing_type {
             GraphQLBillingType::Monthly => domain::supplier::BillingType::Monthly,
             GraphQLBillingType::OneTime => domain::supplier::BillingType::OneTime,
+            GraphQLBillingType::Hourly => domain::supplier::BillingType::Hourly,
+            GraphQLBillingType::Milestone => domain::supplier::BillingType::Milestone,
+            GraphQLBillingType::Retainer => domain::supplier::BillingType::Retainer,
         };
+        let settings = SupplierSettings::default()
+            .merge(SupplierSettingsInput {
+                currency: input.currency,
+                remarks_template: input.remarks_template,
+                invoice_number_prefix: input.invoice_number_prefix,
+                billing_email: input.billing_email,
+                reminder_schedule: input.reminder_schedule,
+                billing_cc: input.billing_cc,
+                billing_bcc: input.billing_bcc,
+                auto_send_invoice: input.auto_send_invoice,
+                closing_day: input.closing_day,
+                payment_term_type: input.payment_term_type,
+                payment_term_months: input.payment_term_months,
+                payment_term_day: input.payment_term_day,
+                business_day_adjustment: input.business_day_adjustment,
+                settlement_lower_hours: input.settlement_lower_hours,
+                settlement_upper_hours: input.settlement_upper_hours,
+                excess_hourly_rate: input.excess_hourly_rate,
+                deduction_hourly_rate: input.deduction_hourly_rate,
+                retainer_included_quantity: input.retainer_included_quantity,
+                retainer_unit: input.retainer_unit,
+                retainer_overage_rate: input.retainer_overage_rate,
+                interval_months: input.interval_months,
+                start_ym: input.start_ym,
+                contract_end_ym: input.contract_end_ym,
+                contract_start_date: input.contract_start_date,
+                contract_end_date: input.contract_end_date,
+                proration_basis: input.proration_basis,
+                tax_type: input.tax_type,
+                tax_inclusive: input.tax_inclusive,
+                tax_rounding: input.tax_rounding,
+                withholding_tax: input.withholding_tax,
+            })
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let SupplierSettings {
+            currency,
+            remarks_template,
+            invoice_number_prefix,
+            billing_email,
+            reminder_schedule,
+            delivery,
+            closing_day,
+            payment_term,
+            business_day_adjustment,
+            settlement_range,
+            retainer,
+            recurrence,
+            proration,
+            tax_setting,
+            withholding_tax,
+        } = settings;
 
         let access_token = get_misoca_token::exec(ctx, now)
             .await
@@ -133,8 +192,21 @@ impl MutationFields for Mutation {
                         contact_group_id,
                         name,
                         billing_amount,
+                        currency,
+                        recurrence,
+                        proration,
                         subject,
                         subject_template,
+                        remarks_template,
+                        invoice_number_prefix,
+                        billing_email,
+                        reminder_schedule,
+                        delivery,
+                        closing_day,
+                        payment_term,
+                        business_day_adjustment,
+                        tax_setting,
+                        withholding_tax,
                         now,
                     )
                 }
@@ -147,9 +219,89 @@ impl MutationFields for Mutation {
                         contact_group_id,
                         name,
                         billing_amount,
+                        currency,
                         ym,
                         subject,
                         subject_template,
+                        remarks_template,
+                        invoice_number_prefix,
+                        billing_email,
+                        reminder_schedule,
+                        delivery,
+                        closing_day,
+                        payment_term,
+                        business_day_adjustment,
+                        tax_setting,
+                        withholding_tax,
+                        now,
+                    )
+                }
+                domain::supplier::BillingType::Hourly => domain::supplier::Supplier::new_as_hourly(
+                    authenticated_user_id,
+                    contact_id,
+                    contact_group_id,
+                    name,
+                    billing_amount,
+                    currency,
+                    settlement_range,
+                    subject,
+                    subject_template,
+                    remarks_template,
+                    invoice_number_prefix,
+                    billing_email,
+                    reminder_schedule,
+                    delivery,
+                    closing_day,
+                    payment_term,
+                    business_day_adjustment,
+                    tax_setting,
+                    withholding_tax,
+                    now,
+                ),
+                domain::supplier::BillingType::Retainer => {
+                    domain::supplier::Supplier::new_as_retainer(
+                        authenticated_user_id,
+                        contact_id,
+                        contact_group_id,
+                        name,
+                        billing_amount,
+                        currency,
+                        retainer,
+                        subject,
+                        subject_template,
+                        remarks_template,
+                        invoice_number_prefix,
+                        billing_email,
+                        reminder_schedule,
+                        delivery,
+                        closing_day,
+                        payment_term,
+                        business_day_adjustment,
+                        tax_setting,
+                        withholding_tax,
+                        now,
+                    )
+                }
+                domain::supplier::BillingType::Milestone => {
+                    domain::supplier::Supplier::new_as_milestone(
+                        authenticated_user_id,
+                        contact_id,
+                        contact_group_id,
+                        name,
+                        billing_amount,
+                        currency,
+                        subject,
+                        subject_template,
+                        remarks_template,
+                        invoice_number_prefix,
+                        billing_email,
+                        reminder_schedule,
+                        delivery,
+                        closing_day,
+                        payment_term,
+                        business_day_adjustment,
+                        tax_setting,
+                        withholding_tax,
                         now,
                     )
                 }
@@ -172,6 +324,13 @@ impl MutationFields for Mutation {
         let ctx = exec.context();
         let conn = ctx.get_new_connection();
         let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_milestone_dao: Dao<domain::supplier_milestone::SupplierMilestone> = Dao::new();
+        let supplier_price_dao: Dao<domain::supplier_price::SupplierPrice> = Dao::new();
+        let supplier_item_dao: Dao<domain::supplier_item::SupplierItem> = Dao::new();
+        let supplier_adjustment_dao: Dao<domain::supplier_adjustment::SupplierAdjustment> =
+            Dao::new();
+        let expense_dao: Dao<domain::expense::Expense> = Dao::new();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
         let misoca_cli = &ctx.misoca_cli;
         let authenticated_user_id = ctx
             .authenticated_user_id
@@ -183,7 +342,68 @@ impl MutationFields for Mutation {
         let end_ym: String = input.end_ym;
         let subject: String = input.subject;
         let subject_template: String = input.subject_template;
-        let billing_amount: i32 = input.billing_amount;
+        domain::subject_template::SubjectTemplate::from_str(subject_template.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let billing_amount: Money = input.billing_amount;
+
+        // 省略された設定は現在の値のままにする
+        let current = supplier_dao
+            .get(&conn, id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        if current.user_id != authenticated_user_id {
+            return Err(FieldErrorWithCode::from(CoreError::Forbidden).into());
+        }
+        let settings = SupplierSettings::of(&current)
+            .merge(SupplierSettingsInput {
+                currency: input.currency,
+                remarks_template: input.remarks_template,
+                invoice_number_prefix: input.invoice_number_prefix,
+                billing_email: input.billing_email,
+                reminder_schedule: input.reminder_schedule,
+                billing_cc: input.billing_cc,
+                billing_bcc: input.billing_bcc,
+                auto_send_invoice: input.auto_send_invoice,
+                closing_day: input.closing_day,
+                payment_term_type: input.payment_term_type,
+                payment_term_months: input.payment_term_months,
+                payment_term_day: input.payment_term_day,
+                business_day_adjustment: input.business_day_adjustment,
+                settlement_lower_hours: input.settlement_lower_hours,
+                settlement_upper_hours: input.settlement_upper_hours,
+                excess_hourly_rate: input.excess_hourly_rate,
+                deduction_hourly_rate: input.deduction_hourly_rate,
+                retainer_included_quantity: input.retainer_included_quantity,
+                retainer_unit: input.retainer_unit,
+                retainer_overage_rate: input.retainer_overage_rate,
+                interval_months: input.interval_months,
+                start_ym: input.start_ym,
+                contract_end_ym: input.contract_end_ym,
+                contract_start_date: input.contract_start_date,
+                contract_end_date: input.contract_end_date,
+                proration_basis: input.proration_basis,
+                tax_type: input.tax_type,
+                tax_inclusive: input.tax_inclusive,
+                tax_rounding: input.tax_rounding,
+                withholding_tax: input.withholding_tax,
+            })
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let SupplierSettings {
+            currency,
+            remarks_template,
+            invoice_number_prefix,
+            billing_email,
+            reminder_schedule,
+            delivery,
+            closing_day,
+            payment_term,
+            business_day_adjustment,
+            settlement_range,
+            retainer,
+            recurrence,
+            proration,
+            tax_setting,
+            withholding_tax,
+        } = settings;
 
         let access_token = get_misoca_token::exec(ctx, now)
             .await
@@ -223,33 +443,1160 @@ impl MutationFields for Mutation {
 
             let ym = domain::YM::from_str(end_ym.as_str())?;
 
-            supplier.update(
-                contact_id,
-                contact_group_id,
-                name,
-                billing_amount,
-                ym,
-                subject,
-                subject_template,
+            // 金額は通貨の最小単位で保存しているため、通貨を変えると登録済みの金額の桁が変わってしまう
+            if supplier.currency != currency {
+                let has_amounts = invoice_dao.get_count_by_supplier(&conn, supplier.id.clone())?
+                    > 0
+                    || !supplier_price_dao
+                        .get_all_by_supplier(&conn, supplier.id.clone())?
+                        .is_empty()
+                    || !supplier_item_dao
+                        .get_all_by_supplier(&conn, supplier.id.clone())?
+                        .is_empty()
+                    || !supplier_milestone_dao
+                        .get_all_by_supplier(&conn, supplier.id.clone())?
+                        .is_empty()
+                    || !supplier_adjustment_dao
+                        .get_all_by_supplier(&conn, supplier.id.clone())?
+                        .is_empty()
+                    || !expense_dao
+                        .get_all_by_supplier(&conn, supplier.id.clone())?
+                        .is_empty();
+                if has_amounts {
+                    return Err(CoreError::BadRequest(
+                        "請求書や金額を登録済みの請求先は通貨を変更できません".to_string(),
+                    ));
+                }
+            }
+
+            supplier.update(
+                contact_id,
+                contact_group_id,
+                name,
+                billing_amount.clone(),
+                currency,
+                ym,
+                settlement_range,
+                retainer,
+                recurrence,
+                proration,
+                subject,
+                subject_template,
+                remarks_template,
+                invoice_number_prefix,
+                billing_email,
+                reminder_schedule,
+                delivery,
+                closing_day,
+                payment_term,
+                business_day_adjustment,
+                tax_setting,
+                withholding_tax,
+                now,
+            );
+            if supplier.billing_type == domain::supplier::BillingType::Milestone {
+                let milestones =
+                    supplier_milestone_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
+                domain::supplier_milestone::validate_total(
+                    &milestones,
+                    &supplier.billing_amount,
+                    &supplier.tax_setting.rounding,
+                )
+                .map_err(CoreError::BadRequest)?;
+            } else {
+                let prices = supplier_price_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
+                if let Some(price) = supplier
+                    .reprice(billing_amount, &prices, now)
+                    .map_err(CoreError::BadRequest)?
+                {
+                    if prices.iter().any(|v| v.id == price.id) {
+                        supplier_price_dao.update(&conn, &price)?;
+                    } else {
+                        supplier_price_dao.insert(&conn, &price)?;
+                    }
+                }
+            }
+            supplier_dao.update(&conn, &supplier)?;
+            Ok(supplier)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Supplier { supplier })
+    }
+
+    async fn field_delete_supplier<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        input: DeleteSupplierInput,
+    ) -> FieldResult<bool> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let working_hours_dao: Dao<domain::working_hours::WorkingHours> = Dao::new();
+        let retainer_usage_dao: Dao<domain::retainer_usage::RetainerUsage> = Dao::new();
+        let supplier_item_dao: Dao<domain::supplier_item::SupplierItem> = Dao::new();
+        let supplier_suspension_dao: Dao<domain::supplier_suspension::SupplierSuspension> =
+            Dao::new();
+        let supplier_price_dao: Dao<domain::supplier_price::SupplierPrice> = Dao::new();
+        let supplier_milestone_dao: Dao<domain::supplier_milestone::SupplierMilestone> = Dao::new();
+        let expense_dao: Dao<domain::expense::Expense> = Dao::new();
+        let supplier_adjustment_dao: Dao<domain::supplier_adjustment::SupplierAdjustment> =
+            Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            // 発行した請求書と入金の記録は残す必要があるため、請求先はアーカイブしてもらう
+            if invoice_dao.get_count_by_supplier(&conn, supplier.id.clone())? > 0 {
+                return Err(CoreError::BadRequest(
+                    "請求書を発行済みの請求先は削除できません。アーカイブしてください".to_string(),
+                ));
+            }
+
+            working_hours_dao.delete_by_supplier(&conn, supplier.id.clone())?;
+            retainer_usage_dao.delete_by_supplier(&conn, supplier.id.clone())?;
+            supplier_item_dao.delete_by_supplier(&conn, supplier.id.clone())?;
+            supplier_suspension_dao.delete_by_supplier(&conn, supplier.id.clone())?;
+            supplier_price_dao.delete_by_supplier(&conn, supplier.id.clone())?;
+            supplier_milestone_dao.delete_by_supplier(&conn, supplier.id.clone())?;
+            expense_dao.delete_by_supplier(&conn, supplier.id.clone())?;
+            supplier_adjustment_dao.delete_by_supplier(&conn, supplier.id.clone())?;
+            supplier_dao.delete(&conn, supplier.id.clone())?;
+            Ok(())
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(true)
+    }
+
+    async fn field_record_working_hours<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, WorkingHours, Walked>,
+        input: RecordWorkingHoursInput,
+    ) -> FieldResult<WorkingHours> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let working_hours_dao: Dao<domain::working_hours::WorkingHours> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let supplier_id: String = input.supplier_id;
+        let ym = domain::YM::from_str(input.ym.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        if ym.is_empty() || input.hours < 0.0 {
+            return Err(FieldErrorWithCode::from(CoreError::BadRequest(
+                "稼働時間の指定が不正です".to_string(),
+            ))
+            .into());
+        }
+        let minutes = (input.hours * 60.0).round() as i32;
+
+        let working_hours = Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+            if supplier.billing_type != domain::supplier::BillingType::Hourly {
+                return Err(CoreError::BadRequest(
+                    "時間精算の請求先ではありません".to_string(),
+                ));
+            }
+
+            match working_hours_dao.get_by_supplier_and_ym(&conn, supplier.id.clone(), &ym) {
+                Ok(mut working_hours) => {
+                    working_hours.update(minutes, now);
+                    working_hours_dao.update(&conn, &working_hours)?;
+                    Ok(working_hours)
+                }
+                Err(CoreError::NotFound) => {
+                    let working_hours = domain::working_hours::WorkingHours::new(
+                        supplier.id.clone(),
+                        ym.clone(),
+                        minutes,
+                        now,
+                    );
+                    working_hours_dao.insert(&conn, &working_hours)?;
+                    Ok(working_hours)
+                }
+                Err(e) => Err(e),
+            }
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(WorkingHours { working_hours })
+    }
+
+    async fn field_record_retainer_usage<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, RetainerUsage, Walked>,
+        input: RecordRetainerUsageInput,
+    ) -> FieldResult<RetainerUsage> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let retainer_usage_dao: Dao<domain::retainer_usage::RetainerUsage> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let supplier_id: String = input.supplier_id;
+        let ym = domain::YM::from_str(input.ym.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let quantity: i32 = input.quantity;
+        if ym.is_empty() || quantity < 0 {
+            return Err(FieldErrorWithCode::from(CoreError::BadRequest(
+                "利用実績の指定が不正です".to_string(),
+            ))
+            .into());
+        }
+
+        let usage = Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+            if supplier.billing_type != domain::supplier::BillingType::Retainer {
+                return Err(CoreError::BadRequest(
+                    "リテイナー契約の請求先ではありません".to_string(),
+                ));
+            }
+
+            match retainer_usage_dao.get_by_supplier_and_ym(&conn, supplier.id.clone(), &ym) {
+                Ok(mut usage) => {
+                    usage.update(quantity, now);
+                    retainer_usage_dao.update(&conn, &usage)?;
+                    Ok(usage)
+                }
+                Err(CoreError::NotFound) => {
+                    let usage = domain::retainer_usage::RetainerUsage::new(
+                        supplier.id.clone(),
+                        ym.clone(),
+                        quantity,
+                        now,
+                    );
+                    retainer_usage_dao.insert(&conn, &usage)?;
+                    Ok(usage)
+                }
+                Err(e) => Err(e),
+            }
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(RetainerUsage { usage })
+    }
+
+    async fn field_create_supplier_item<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierItem, Walked>,
+        input: CreateSupplierItemInput,
+    ) -> FieldResult<SupplierItem> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_item_dao: Dao<domain::supplier_item::SupplierItem> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let supplier_id: String = input.supplier_id;
+        let name: String = input.name;
+        let quantity: i32 = input.quantity;
+        let unit: String = input.unit;
+        let unit_price: Money = input.unit_price;
+        let tax_type = to_domain_tax_type(input.tax_type);
+        let withholding: bool = input.withholding;
+
+        let item = Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            let item = domain::supplier_item::SupplierItem::new(
+                supplier.id.clone(),
+                name,
+                quantity,
+                unit,
+                unit_price,
+                tax_type,
+                withholding,
+                now,
+            )
+            .map_err(CoreError::BadRequest)?;
+            supplier_item_dao.insert(&conn, &item)?;
+            Ok(item)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(SupplierItem { item })
+    }
+
+    async fn field_update_supplier_item<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierItem, Walked>,
+        input: UpdateSupplierItemInput,
+    ) -> FieldResult<SupplierItem> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_item_dao: Dao<domain::supplier_item::SupplierItem> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+        let name: String = input.name;
+        let quantity: i32 = input.quantity;
+        let unit: String = input.unit;
+        let unit_price: Money = input.unit_price;
+        let tax_type = to_domain_tax_type(input.tax_type);
+        let withholding: bool = input.withholding;
+
+        let item = Tx::run(&conn, || {
+            let mut item = supplier_item_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, item.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            item.update(name, quantity, unit, unit_price, tax_type, withholding, now)
+                .map_err(CoreError::BadRequest)?;
+            supplier_item_dao.update(&conn, &item)?;
+            Ok(item)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(SupplierItem { item })
+    }
+
+    async fn field_delete_supplier_item<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        input: DeleteSupplierItemInput,
+    ) -> FieldResult<bool> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_item_dao: Dao<domain::supplier_item::SupplierItem> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        Tx::run(&conn, || {
+            let item = supplier_item_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, item.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            supplier_item_dao.delete(&conn, item.id.clone())
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(true)
+    }
+
+    async fn field_archive_supplier<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Supplier, Walked>,
+        input: ArchiveSupplierInput,
+    ) -> FieldResult<Supplier> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        let supplier = Tx::run(&conn, || {
+            let mut supplier = supplier_dao.get(&conn, id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            supplier.archive(now);
+            supplier_dao.update(&conn, &supplier)?;
+            Ok(supplier)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Supplier { supplier })
+    }
+
+    async fn field_restore_supplier<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Supplier, Walked>,
+        input: RestoreSupplierInput,
+    ) -> FieldResult<Supplier> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        let supplier = Tx::run(&conn, || {
+            let mut supplier = supplier_dao.get(&conn, id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            supplier.restore(now);
+            supplier_dao.update(&conn, &supplier)?;
+            Ok(supplier)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Supplier { supplier })
+    }
+
+    async fn field_create_supplier_suspension<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierSuspension, Walked>,
+        input: CreateSupplierSuspensionInput,
+    ) -> FieldResult<SupplierSuspension> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_suspension_dao: Dao<domain::supplier_suspension::SupplierSuspension> =
+            Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let supplier_id: String = input.supplier_id;
+        let from_ym = domain::YM::from_str(input.from_ym.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let to_ym = domain::YM::from_str(input.to_ym.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+
+        let suspension = Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            let suspension = domain::supplier_suspension::SupplierSuspension::new(
+                supplier.id.clone(),
+                from_ym,
+                to_ym,
+                now,
+            )
+            .map_err(CoreError::BadRequest)?;
+            supplier_suspension_dao.insert(&conn, &suspension)?;
+            Ok(suspension)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(SupplierSuspension { suspension })
+    }
+
+    async fn field_delete_supplier_suspension<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        input: DeleteSupplierSuspensionInput,
+    ) -> FieldResult<bool> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_suspension_dao: Dao<domain::supplier_suspension::SupplierSuspension> =
+            Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        Tx::run(&conn, || {
+            let suspension = supplier_suspension_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, suspension.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            supplier_suspension_dao.delete(&conn, suspension.id.clone())
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(true)
+    }
+
+    async fn field_schedule_supplier_price<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierPrice, Walked>,
+        input: ScheduleSupplierPriceInput,
+    ) -> FieldResult<SupplierPrice> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_price_dao: Dao<domain::supplier_price::SupplierPrice> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let supplier_id: String = input.supplier_id;
+        let effective_from = domain::YM::from_str(input.effective_from.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let billing_amount: Money = input.billing_amount;
+
+        let price = Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+            if supplier.billing_type == domain::supplier::BillingType::Milestone {
+                return Err(CoreError::BadRequest(
+                    "マイルストーン請求の契約金額は改定できません".to_string(),
+                ));
+            }
+
+            match supplier_price_dao.get_by_supplier_and_effective_from(
+                &conn,
+                supplier.id.clone(),
+                &effective_from,
+            ) {
+                Ok(mut price) => {
+                    price
+                        .update(billing_amount, now)
+                        .map_err(CoreError::BadRequest)?;
+                    supplier_price_dao.update(&conn, &price)?;
+                    Ok(price)
+                }
+                Err(CoreError::NotFound) => {
+                    let price = domain::supplier_price::SupplierPrice::new(
+                        supplier.id.clone(),
+                        effective_from.clone(),
+                        billing_amount,
+                        now,
+                    )
+                    .map_err(CoreError::BadRequest)?;
+                    supplier_price_dao.insert(&conn, &price)?;
+                    Ok(price)
+                }
+                Err(e) => Err(e),
+            }
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(SupplierPrice { price })
+    }
+
+    async fn field_delete_supplier_price<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        input: DeleteSupplierPriceInput,
+    ) -> FieldResult<bool> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_price_dao: Dao<domain::supplier_price::SupplierPrice> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        Tx::run(&conn, || {
+            let price = supplier_price_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, price.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            supplier_price_dao.delete(&conn, price.id.clone())
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(true)
+    }
+
+    async fn field_create_supplier_milestone<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierMilestone, Walked>,
+        input: CreateSupplierMilestoneInput,
+    ) -> FieldResult<SupplierMilestone> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_milestone_dao: Dao<domain::supplier_milestone::SupplierMilestone> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let supplier_id: String = input.supplier_id;
+        let name: String = input.name;
+        let amount_type = to_domain_milestone_amount_type(input.amount_type);
+        let amount_value: Money = input.amount_value;
+        let target_ym = domain::YM::from_str(input.target_ym.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+
+        let (milestone, amount) = Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+            if supplier.billing_type != domain::supplier::BillingType::Milestone {
+                return Err(CoreError::BadRequest(
+                    "マイルストーン請求の請求先ではありません".to_string(),
+                ));
+            }
+
+            let mut milestones =
+                supplier_milestone_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
+            let position = milestones.iter().map(|v| v.position + 1).max().unwrap_or(0);
+
+            let milestone = domain::supplier_milestone::SupplierMilestone::new(
+                supplier.id.clone(),
+                name,
+                position,
+                amount_type,
+                amount_value,
+                target_ym,
+                now,
+            )
+            .map_err(CoreError::BadRequest)?;
+            milestones.push(milestone.clone());
+            domain::supplier_milestone::validate_total(
+                &milestones,
+                &supplier.billing_amount,
+                &supplier.tax_setting.rounding,
+            )
+            .map_err(CoreError::BadRequest)?;
+
+            supplier_milestone_dao.insert(&conn, &milestone)?;
+            let amount = supplier.milestone_amount(&milestone);
+            Ok((milestone, amount))
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(SupplierMilestone { milestone, amount })
+    }
+
+    async fn field_update_supplier_milestone<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierMilestone, Walked>,
+        input: UpdateSupplierMilestoneInput,
+    ) -> FieldResult<SupplierMilestone> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_milestone_dao: Dao<domain::supplier_milestone::SupplierMilestone> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+        let name: String = input.name;
+        let position: i32 = input.position;
+        let amount_type = to_domain_milestone_amount_type(input.amount_type);
+        let amount_value: Money = input.amount_value;
+        let target_ym = domain::YM::from_str(input.target_ym.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+
+        let (milestone, amount) = Tx::run(&conn, || {
+            let mut milestone = supplier_milestone_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, milestone.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            milestone
+                .update(name, position, amount_type, amount_value, target_ym, now)
+                .map_err(CoreError::BadRequest)?;
+
+            let milestones = supplier_milestone_dao
+                .get_all_by_supplier(&conn, supplier.id.clone())?
+                .into_iter()
+                .map(|v| {
+                    if v.id == milestone.id {
+                        milestone.clone()
+                    } else {
+                        v
+                    }
+                })
+                .collect::<Vec<_>>();
+            domain::supplier_milestone::validate_total(
+                &milestones,
+                &supplier.billing_amount,
+                &supplier.tax_setting.rounding,
+            )
+            .map_err(CoreError::BadRequest)?;
+
+            supplier_milestone_dao.update(&conn, &milestone)?;
+            let amount = supplier.milestone_amount(&milestone);
+            Ok((milestone, amount))
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(SupplierMilestone { milestone, amount })
+    }
+
+    async fn field_delete_supplier_milestone<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        input: DeleteSupplierMilestoneInput,
+    ) -> FieldResult<bool> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_milestone_dao: Dao<domain::supplier_milestone::SupplierMilestone> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        Tx::run(&conn, || {
+            let milestone = supplier_milestone_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, milestone.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+            if milestone.is_billed() {
+                return Err(CoreError::BadRequest(
+                    "請求済みのマイルストーンは削除できません".to_string(),
+                ));
+            }
+
+            supplier_milestone_dao.delete(&conn, milestone.id.clone())
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(true)
+    }
+
+    async fn field_bill_supplier_milestone<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Invoice, Walked>,
+        input: BillSupplierMilestoneInput,
+    ) -> FieldResult<Invoice> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_new_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_milestone_dao: Dao<domain::supplier_milestone::SupplierMilestone> = Dao::new();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let bank_dao: Dao<domain::bank::Bank> = Dao::new();
+        let sender_dao: Dao<domain::sender::Sender> = Dao::new();
+        let user_dao: Dao<domain::user::User> = Dao::new();
+        let misoca_cli = &ctx.misoca_cli;
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        let mut milestone = supplier_milestone_dao
+            .get(&conn, id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let supplier = supplier_dao
+            .get(&conn, milestone.supplier_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        if supplier.user_id != authenticated_user_id {
+            return Err(FieldErrorWithCode::from(CoreError::Forbidden).into());
+        }
+        if supplier.archived {
+            return Err(FieldErrorWithCode::from(CoreError::BadRequest(
+                "アーカイブ済みの請求先には請求できません".to_string(),
+            ))
+            .into());
+        }
+        if milestone.is_billed() {
+            return Err(FieldErrorWithCode::from(CoreError::BadRequest(
+                "このマイルストーンは請求済みです".to_string(),
+            ))
+            .into());
+        }
+
+        let user = user_dao
+            .get(&conn, authenticated_user_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let banks = bank_dao
+            .get_all_by_user(&conn, authenticated_user_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let senders = sender_dao
+            .get_all_by_user(&conn, authenticated_user_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let sender = senders.first().cloned().ok_or_else(|| {
+            FieldErrorWithCode::from(CoreError::BadRequest(
+                "差出人が登録されていません".to_string(),
+            ))
+        })?;
+        sender
+            .validate_for_qualified_invoice()
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+
+        let (issue_date, payment_due_on) = supplier.payment_date_on_demand(now);
+
+        let access_token = get_misoca_token::exec(ctx, now)
+            .await
+            .map_err(FieldErrorWithCode::from)?;
+
+        // 件名と備考に請求書番号を入れられるよう、描画する前に採番する。
+        // 同時に請求されたマイルストーンに番号を使わないよう、行をロックして請求済みでないことを確かめ直す
+        let invoice_number = Tx::run(&conn, || {
+            if supplier_milestone_dao
+                .get_for_update(&conn, id.clone())?
+                .is_billed()
+            {
+                return Err(CoreError::BadRequest(
+                    "このマイルストーンは請求済みです".to_string(),
+                ));
+            }
+            allocate_invoice_number::exec(&conn, &user, &supplier, issue_date.as_str(), now)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+        let number = invoice_number.clone().unwrap_or_default();
+
+        let subject = supplier.subject_for_milestone(
+            &milestone,
+            &supplier.next_billing_period(now),
+            number.as_str(),
+        );
+        let items = supplier.milestone_items(subject.clone(), &milestone);
+        let withholding_tax = domain::invoice::withholding_tax(&items, &supplier.currency);
+        let tax_summaries = supplier.tax_setting.summaries(&items);
+        let remarks = supplier.remarks_for_milestone(
+            &milestone,
+            &supplier.next_billing_period(now),
+            sender.remarks_template.as_str(),
+            number.as_str(),
+        );
+
+        let mut invoice = match misoca_cli
+            .create_invoice(misoca::invoice::create_invoice::Input {
+                access_token,
+                supplier_id: supplier.id.clone(),
+                contact_id: supplier.contact_id.clone(),
+                subject,
+                issue_date,
+                payment_due_on,
+                invoice_number: invoice_number.clone(),
+                currency: supplier.currency.clone(),
+                items,
+                remarks,
+                tax_setting: supplier.tax_setting.clone(),
+                bank: banks.first().cloned(),
+                sender: Some(sender),
+                now,
+            })
+            .await
+        {
+            Ok(invoice) => invoice,
+            Err(e) => {
+                allocate_invoice_number::void(&conn, &user, invoice_number, &e, now)
+                    .map_err(FieldErrorWithCode::from)?;
+                return Err(FieldErrorWithCode::from(e).into());
+            }
+        };
+        invoice.apply_withholding_tax(withholding_tax);
+        invoice.apply_tax_summaries(tax_summaries);
+        milestone.mark_billed(invoice.id.clone(), now);
+
+        // misocaで発行済みの番号は欠番にせず、登録できなければ突き合わせのためにエラーにする
+        Tx::run(&conn, || {
+            if supplier_milestone_dao
+                .get_for_update(&conn, id.clone())?
+                .is_billed()
+            {
+                return Err(CoreError::BadRequest(
+                    "このマイルストーンは請求済みです".to_string(),
+                ));
+            }
+            invoice_dao.insert(&conn, &invoice)?;
+            supplier_milestone_dao.update(&conn, &milestone)
+        })
+        .map_err(|e| allocate_invoice_number::unrecorded(&invoice, e))
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Invoice { invoice })
+    }
+
+    async fn field_create_expense<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Expense, Walked>,
+        input: CreateExpenseInput,
+    ) -> FieldResult<Expense> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let expense_dao: Dao<domain::expense::Expense> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let supplier_id: String = input.supplier_id;
+        let ymd = domain::YMD::from_str(input.ymd.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let description: String = input.description;
+        let amount: Money = input.amount;
+        let tax_type = to_domain_tax_type(input.tax_type);
+
+        let expense = Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            let expense = domain::expense::Expense::new(
+                supplier.id.clone(),
+                ymd,
+                description,
+                amount,
+                tax_type,
+                now,
+            )
+            .map_err(CoreError::BadRequest)?;
+            expense_dao.insert(&conn, &expense)?;
+            Ok(expense)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Expense { expense })
+    }
+
+    async fn field_update_expense<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Expense, Walked>,
+        input: UpdateExpenseInput,
+    ) -> FieldResult<Expense> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let expense_dao: Dao<domain::expense::Expense> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+        let ymd = domain::YMD::from_str(input.ymd.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let description: String = input.description;
+        let amount: Money = input.amount;
+        let tax_type = to_domain_tax_type(input.tax_type);
+
+        let expense = Tx::run(&conn, || {
+            let mut expense = expense_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, expense.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            expense
+                .update(ymd, description, amount, tax_type, now)
+                .map_err(CoreError::BadRequest)?;
+            expense_dao.update(&conn, &expense)?;
+            Ok(expense)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Expense { expense })
+    }
+
+    async fn field_delete_expense<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        input: DeleteExpenseInput,
+    ) -> FieldResult<bool> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let expense_dao: Dao<domain::expense::Expense> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+
+        Tx::run(&conn, || {
+            let expense = expense_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, expense.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+            if expense.is_billed() {
+                return Err(CoreError::BadRequest(
+                    "請求済みの立替金は削除できません".to_string(),
+                ));
+            }
+
+            expense_dao.delete(&conn, expense.id.clone())
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(true)
+    }
+
+    async fn field_attach_expense_receipt<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Expense, Walked>,
+        input: AttachExpenseReceiptInput,
+    ) -> FieldResult<Expense> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_new_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let expense_dao: Dao<domain::expense::Expense> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+        let file_name: String = input.file_name;
+        let content_type: String = input.content_type;
+        let data = base64::decode(input.data.as_str()).map_err(|_e| {
+            FieldErrorWithCode::from(CoreError::BadRequest(
+                "ファイルの内容を読み込めません".to_string(),
+            ))
+        })?;
+
+        let mut expense = expense_dao
+            .get(&conn, id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let supplier = supplier_dao
+            .get(&conn, expense.supplier_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        if supplier.user_id != authenticated_user_id {
+            return Err(FieldErrorWithCode::from(CoreError::Forbidden).into());
+        }
+
+        let path = expense.receipt_path(file_name.as_str(), now);
+        Object::create(INVOICE_BUCKET, data, path.as_str(), content_type.as_str())
+            .await
+            .map_err(FieldErrorWithCode::from)?;
+
+        expense.attach_receipt(path, now);
+        Tx::run(&conn, || {
+            expense_dao.update(&conn, &expense)?;
+            Ok(())
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Expense { expense })
+    }
+
+    async fn field_add_supplier_adjustment<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierAdjustment, Walked>,
+        input: AddSupplierAdjustmentInput,
+    ) -> FieldResult<SupplierAdjustment> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let supplier_adjustment_dao: Dao<domain::supplier_adjustment::SupplierAdjustment> =
+            Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let supplier_id: String = input.supplier_id;
+        let description: String = input.description;
+        let amount: Money = input.amount;
+        let tax_type = to_domain_tax_type(input.tax_type);
+        let target_ym = domain::YM::from_str(input.target_ym.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+
+        let adjustment = Tx::run(&conn, || {
+            let supplier = supplier_dao.get(&conn, supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+
+            let adjustment = domain::supplier_adjustment::SupplierAdjustment::new(
+                supplier.id.clone(),
+                description,
+                amount,
+                tax_type,
+                target_ym,
                 now,
-            );
-            supplier_dao.update(&conn, &supplier)?;
-            Ok(supplier)
+            )
+            .map_err(CoreError::BadRequest)?;
+            supplier_adjustment_dao.insert(&conn, &adjustment)?;
+            Ok(adjustment)
         })
         .map_err(FieldErrorWithCode::from)?;
 
-        Ok(Supplier { supplier })
+        Ok(SupplierAdjustment { adjustment })
     }
 
-    async fn field_delete_supplier<'s, 'r, 'a>(
+    async fn field_cancel_supplier_adjustment<'s, 'r, 'a>(
         &'s self,
         exec: &Executor<'r, 'a, Context>,
-        input: DeleteSupplierInput,
-    ) -> FieldResult<bool> {
+        _: &QueryTrail<'r, SupplierAdjustment, Walked>,
+        input: CancelSupplierAdjustmentInput,
+    ) -> FieldResult<SupplierAdjustment> {
+        let now: DateTime<Utc> = Utc::now();
         let ctx = exec.context();
         let conn = ctx.get_mutex_connection();
         let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
-        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let supplier_adjustment_dao: Dao<domain::supplier_adjustment::SupplierAdjustment> =
+            Dao::new();
         let authenticated_user_id = ctx
             .authenticated_user_id
             .clone()
@@ -257,19 +1604,20 @@ impl MutationFields for Mutation {
 
         let id: String = input.id;
 
-        Tx::run(&conn, || {
-            let supplier = supplier_dao.get(&conn, id.clone())?;
+        let adjustment = Tx::run(&conn, || {
+            let mut adjustment = supplier_adjustment_dao.get(&conn, id.clone())?;
+            let supplier = supplier_dao.get(&conn, adjustment.supplier_id.clone())?;
             if supplier.user_id != authenticated_user_id {
                 return Err(CoreError::Forbidden);
             }
 
-            invoice_dao.delete_by_supplier(&conn, supplier.id.clone())?;
-            supplier_dao.delete(&conn, supplier.id.clone())?;
-            Ok(())
+            adjustment.cancel(now).map_err(CoreError::BadRequest)?;
+            supplier_adjustment_dao.update(&conn, &adjustment)?;
+            Ok(adjustment)
         })
         .map_err(FieldErrorWithCode::from)?;
 
-        Ok(true)
+        Ok(SupplierAdjustment { adjustment })
     }
 
     async fn field_connect_misoca<'s, 'r, 'a>(
@@ -282,7 +1630,6 @@ impl MutationFields for Mutation {
         let conn = ctx.get_new_connection();
         let user_dao: Dao<domain::user::User> = Dao::new();
         let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
-        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
         let misoca_cli = &ctx.misoca_cli;
         let authenticated_user_id = ctx
             .authenticated_user_id
@@ -320,29 +1667,13 @@ impl MutationFields for Mutation {
                     access_token: access_token.clone(),
                     page: 1,
                     per_page: 100,
-                    supplier_id: supplier.id.clone(),
                     contact_group_id: supplier.contact_group_id.clone(),
                 })
                 .await
                 .map_err(FieldErrorWithCode::from)?;
 
-            Tx::run(&conn, || {
-                for invoice in invoices {
-                    match invoice_dao.get(&conn, invoice.id.clone()) {
-                        Ok(current) => {
-                            if current.should_update(&invoice) {
-                                invoice_dao.update(&conn, &invoice)?;
-                            }
-                        }
-                        Err(CoreError::NotFound) => {
-                            invoice_dao.insert(&conn, &invoice)?;
-                        }
-                        Err(_) => {}
-                    }
-                }
-                Ok(())
-            })
-            .map_err(FieldErrorWithCode::from)?;
+            sync_invoice::save_remote(&conn, &supplier, invoices)
+                .map_err(FieldErrorWithCode::from)?;
         }
 
         Ok(true)
@@ -356,7 +1687,6 @@ impl MutationFields for Mutation {
         let ctx = exec.context();
         let conn = ctx.get_new_connection();
         let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
-        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
         let misoca_cli = &ctx.misoca_cli;
         let authenticated_user_id = ctx
             .authenticated_user_id
@@ -377,29 +1707,13 @@ impl MutationFields for Mutation {
                     access_token: access_token.clone(),
                     page: 1,
                     per_page: 100,
-                    supplier_id: supplier.id.clone(),
                     contact_group_id: supplier.contact_group_id.clone(),
                 })
                 .await
                 .map_err(FieldErrorWithCode::from)?;
 
-            Tx::run(&conn, || {
-                for invoice in invoices {
-                    match invoice_dao.get(&conn, invoice.id.clone()) {
-                        Ok(current) => {
-                            if current.should_update(&invoice) {
-                                invoice_dao.update(&conn, &invoice)?;
-                            }
-                        }
-                        Err(CoreError::NotFound) => {
-                            invoice_dao.insert(&conn, &invoice)?;
-                        }
-                        Err(_) => {}
-                    }
-                }
-                Ok(())
-            })
-            .map_err(FieldErrorWithCode::from)?;
+            sync_invoice::save_remote(&conn, &supplier, invoices)
+                .map_err(FieldErrorWithCode::from)?;
         }
 
         Ok(true)
@@ -422,20 +1736,14 @@ impl MutationFields for Mutation {
             .get(&conn, invoice_id.clone())
             .map_err(FieldErrorWithCode::from)?;
 
-        let next_path = format!(
-            "invoice/{}_{}.pdf",
-            invoice.id.clone(),
-            invoice.updated_at.format("%Y%m%d%H%M%S")
-        );
+        let next_path = invoice.pdf_object_path();
 
-        if let Some(path) = invoice.pdf_path.clone() {
-            if next_path == path {
-                let download_url = Object::read(INVOICE_BUCKET, path.as_str())
-                    .await
-                    .map(|o| o.download_url(INVOICE_PDF_DOWNLOAD_DURATION))
-                    .map_err(FieldErrorWithCode::from)?;
-                return Ok(download_url.unwrap_or("".to_string()));
-            }
+        if invoice.has_current_pdf() {
+            let download_url = Object::read(INVOICE_BUCKET, next_path.as_str())
+                .await
+                .map(|o| o.download_url(INVOICE_PDF_DOWNLOAD_DURATION))
+                .map_err(FieldErrorWithCode::from)?;
+            return Ok(download_url.unwrap_or("".to_string()));
         }
 
         let access_token = get_misoca_token::exec(ctx, now)
@@ -472,15 +1780,187 @@ impl MutationFields for Mutation {
         Ok(download_url)
     }
 
-    async fn field_delete_invoice<'s, 'r, 'a>(
+    /// 請求書は削除せずに取り消す。赤伝を発行する場合は、ユーザーの書式で採番してmisocaで作る
+    async fn field_cancel_invoice<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Invoice, Walked>,
+        input: CancelInvoiceInput,
+    ) -> FieldResult<Invoice> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_new_connection();
+        let user_dao: Dao<domain::user::User> = Dao::new();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let sender_dao: Dao<domain::sender::Sender> = Dao::new();
+        let misoca_cli = &ctx.misoca_cli;
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let id: String = input.id;
+        let reason: String = input.reason;
+        let issue_credit_note: bool = input.issue_credit_note;
+
+        let mut invoice = invoice_dao
+            .get(&conn, id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let supplier = supplier_dao
+            .get(&conn, invoice.supplier_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        if supplier.user_id != authenticated_user_id {
+            return Err(FieldErrorWithCode::from(CoreError::Forbidden).into());
+        }
+
+        invoice
+            .cancel(reason, now)
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        if !issue_credit_note {
+            Tx::run(&conn, || invoice_dao.update(&conn, &invoice))
+                .map_err(FieldErrorWithCode::from)?;
+            return Ok(Invoice { invoice });
+        }
+
+        // misocaには取消の状態がないため、元の請求書はそのまま残し、打ち消す赤伝をmisocaで作る
+        let items = invoice
+            .credit_note_items()
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let user = user_dao
+            .get(&conn, authenticated_user_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let senders = sender_dao
+            .get_all_by_user(&conn, authenticated_user_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let sender = senders.first().cloned().ok_or_else(|| {
+            FieldErrorWithCode::from(CoreError::BadRequest(
+                "差出人が登録されていません".to_string(),
+            ))
+        })?;
+        sender
+            .validate_for_qualified_invoice()
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+
+        let issue_date = domain::YMD::from(now.naive_utc().date()).to_string();
+
+        let access_token = get_misoca_token::exec(ctx, now)
+            .await
+            .map_err(FieldErrorWithCode::from)?;
+
+        let invoice_number = Tx::run(&conn, || {
+            allocate_invoice_number::exec(&conn, &user, &supplier, issue_date.as_str(), now)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        let credit_note = match misoca_cli
+            .create_invoice(misoca::invoice::create_invoice::Input {
+                access_token,
+                supplier_id: supplier.id.clone(),
+                contact_id: supplier.contact_id.clone(),
+                subject: invoice.credit_note_subject(),
+                issue_date: issue_date.clone(),
+                payment_due_on: issue_date.clone(),
+                invoice_number: invoice.credit_note_number(invoice_number.clone()),
+                currency: invoice.currency.clone(),
+                items,
+                remarks: "".to_string(),
+                // 明細は税率ごとの税抜金額
+                tax_setting: domain::invoice::TaxSetting {
+                    inclusive: false,
+                    ..supplier.tax_setting.clone()
+                },
+                bank: None,
+                sender: Some(sender),
+                now,
+            })
+            .await
+        {
+            Ok(credit_note) => credit_note,
+            Err(e) => {
+                allocate_invoice_number::void(&conn, &user, invoice_number, &e, now)
+                    .map_err(FieldErrorWithCode::from)?;
+                return Err(FieldErrorWithCode::from(e).into());
+            }
+        };
+        let credit_note = invoice.issue_credit_note(credit_note);
+
+        // misocaで発行済みの番号は欠番にせず、登録できなければ突き合わせのためにエラーにする
+        Tx::run(&conn, || {
+            invoice_dao.insert(&conn, &credit_note)?;
+            invoice_dao.update(&conn, &invoice)
+        })
+        .map_err(|e| allocate_invoice_number::unrecorded(&credit_note, e))
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Invoice { invoice })
+    }
+
+    async fn field_record_payment<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Payment, Walked>,
+        input: RecordPaymentInput,
+    ) -> FieldResult<Payment> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let payment_dao: Dao<domain::payment::Payment> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let invoice_id: String = input.invoice_id;
+        let received_ymd = domain::YMD::from_str(input.received_ymd.as_str())
+            .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+        let amount: Money = input.amount;
+        let method = to_domain_payment_method(input.method);
+        let memo: String = input.memo;
+
+        let payment = Tx::run(&conn, || {
+            let mut invoice = invoice_dao.get(&conn, invoice_id.clone())?;
+            let supplier = supplier_dao.get(&conn, invoice.supplier_id.clone())?;
+            if supplier.user_id != authenticated_user_id {
+                return Err(CoreError::Forbidden);
+            }
+            invoice
+                .validate_for_payment()
+                .map_err(CoreError::BadRequest)?;
+
+            let payment = domain::payment::Payment::new(
+                invoice.id.clone(),
+                received_ymd,
+                amount,
+                method,
+                memo,
+                now,
+            )
+            .map_err(CoreError::BadRequest)?;
+            payment_dao.insert(&conn, &payment)?;
+
+            let payments = payment_dao.get_all_by_invoice(&conn, invoice.id.clone())?;
+            invoice.apply_payments(&payments);
+            invoice_dao.update(&conn, &invoice)?;
+            Ok(payment)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Payment { payment })
+    }
+
+    async fn field_delete_payment<'s, 'r, 'a>(
         &'s self,
         exec: &Executor<'r, 'a, Context>,
-        input: DeleteInvoiceInput,
+        input: DeletePaymentInput,
     ) -> FieldResult<bool> {
         let ctx = exec.context();
         let conn = ctx.get_mutex_connection();
         let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
         let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let payment_dao: Dao<domain::payment::Payment> = Dao::new();
         let authenticated_user_id = ctx
             .authenticated_user_id
             .clone()
@@ -489,15 +1969,18 @@ impl MutationFields for Mutation {
         let id: String = input.id;
 
         Tx::run(&conn, || {
-            let invoice = invoice_dao.get(&conn, id.clone())?;
+            let payment = payment_dao.get(&conn, id.clone())?;
+            let mut invoice = invoice_dao.get(&conn, payment.invoice_id.clone())?;
             let supplier = supplier_dao.get(&conn, invoice.supplier_id.clone())?;
-
             if supplier.user_id != authenticated_user_id {
                 return Err(CoreError::Forbidden);
             }
 
-            invoice_dao.delete(&conn, invoice.id.clone())?;
-            Ok(())
+            payment_dao.delete(&conn, payment.id.clone())?;
+
+            let payments = payment_dao.get_all_by_invoice(&conn, invoice.id.clone())?;
+            invoice.apply_payments(&payments);
+            invoice_dao.update(&conn, &invoice)
         })
         .map_err(FieldErrorWithCode::from)?;
 
@@ -600,6 +2083,8 @@ impl MutationFields for Mutation {
         let tel: String = input.tel;
         let postal_code: String = input.postal_code;
         let address: String = input.address;
+        let registration_number: String = input.registration_number;
+        let remarks_template: String = input.remarks_template;
 
         let sender = Tx::run(&conn, || {
             let senders = sender_dao.get_all_by_user(&conn, authenticated_user_id.clone())?;
@@ -611,13 +2096,27 @@ impl MutationFields for Mutation {
                     tel,
                     postal_code,
                     address,
+                    registration_number,
+                    remarks_template,
                     now,
-                );
+                )
+                .map_err(CoreError::BadRequest)?;
                 sender_dao.insert(&conn, &sender)?;
                 Ok(sender)
             } else {
                 let mut sender = senders.first().cloned().unwrap();
-                sender.update(name, email, tel, postal_code, address, now);
+                sender
+                    .update(
+                        name,
+                        email,
+                        tel,
+                        postal_code,
+                        address,
+                        registration_number,
+                        remarks_template,
+                        now,
+                    )
+                    .map_err(CoreError::BadRequest)?;
                 sender_dao.update(&conn, &sender)?;
                 Ok(sender)
             }
@@ -654,4 +2153,417 @@ impl MutationFields for Mutation {
 
         Ok(true)
     }
+
+    async fn field_update_invoice_number_format<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Me, Walked>,
+        input: UpdateInvoiceNumberFormatInput,
+    ) -> FieldResult<Me> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let user_dao: Dao<domain::user::User> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let format: String = input.format;
+
+        let user = Tx::run(&conn, || {
+            let mut user = user_dao.get(&conn, authenticated_user_id.clone())?;
+            user.update_invoice_number_format(format, now)
+                .map_err(CoreError::BadRequest)?;
+            user_dao.update(&conn, &user)?;
+            Ok(user)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Me { user })
+    }
+
+    async fn field_update_reminder_setting<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Me, Walked>,
+        input: UpdateReminderSettingInput,
+    ) -> FieldResult<Me> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let user_dao: Dao<domain::user::User> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let schedule: String = input.schedule;
+        let subject_template: String = input.subject_template;
+        let body_template: String = input.body_template;
+
+        let user = Tx::run(&conn, || {
+            let mut user = user_dao.get(&conn, authenticated_user_id.clone())?;
+            user.update_reminder_setting(schedule, subject_template, body_template, now)
+                .map_err(CoreError::BadRequest)?;
+            user_dao.update(&conn, &user)?;
+            Ok(user)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Me { user })
+    }
+
+    async fn field_update_invoice_mail_setting<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Me, Walked>,
+        input: UpdateInvoiceMailSettingInput,
+    ) -> FieldResult<Me> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let user_dao: Dao<domain::user::User> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let subject_template: String = input.subject_template;
+        let body_template: String = input.body_template;
+
+        let user = Tx::run(&conn, || {
+            let mut user = user_dao.get(&conn, authenticated_user_id.clone())?;
+            user.update_invoice_mail_setting(subject_template, body_template, now)
+                .map_err(CoreError::BadRequest)?;
+            user_dao.update(&conn, &user)?;
+            Ok(user)
+        })
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Me { user })
+    }
+
+    async fn field_send_invoice<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Invoice, Walked>,
+        input: SendInvoiceInput,
+    ) -> FieldResult<Invoice> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_new_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let misoca_cli = &ctx.misoca_cli;
+        let mail_cli =
+            ctx.mail_cli
+                .as_ref()
+                .ok_or(FieldErrorWithCode::from(CoreError::Internal(
+                    "メールの送信が設定されていません".to_string(),
+                )))?;
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let invoice_id: String = input.invoice_id;
+
+        let invoice = invoice_dao
+            .get(&conn, invoice_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let supplier = supplier_dao
+            .get(&conn, invoice.supplier_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        if supplier.user_id != authenticated_user_id {
+            return Err(FieldErrorWithCode::from(CoreError::Forbidden).into());
+        }
+
+        let access_token = get_misoca_token::exec(ctx, now)
+            .await
+            .map_err(FieldErrorWithCode::from)?;
+
+        let (invoice, _) = send_invoice::exec(
+            &conn,
+            misoca_cli,
+            mail_cli,
+            access_token,
+            &supplier,
+            invoice,
+            now,
+        )
+        .await
+        .map_err(FieldErrorWithCode::from)?;
+
+        Ok(Invoice { invoice })
+    }
+}
+
+fn to_domain_payment_term_type(v: GraphQLPaymentTermType) -> domain::supplier::PaymentTermType {
+    match v {
+        GraphQLPaymentTermType::MonthEnd => domain::supplier::PaymentTermType::MonthEnd,
+        GraphQLPaymentTermType::MonthDay => domain::supplier::PaymentTermType::MonthDay,
+        GraphQLPaymentTermType::DaysAfterIssue => domain::supplier::PaymentTermType::DaysAfterIssue,
+    }
+}
+
+fn to_domain_business_day_adjustment(
+    v: GraphQLBusinessDayAdjustment,
+) -> domain::calendar::BusinessDayAdjustment {
+    match v {
+        GraphQLBusinessDayAdjustment::Unadjusted => {
+            domain::calendar::BusinessDayAdjustment::Unadjusted
+        }
+        GraphQLBusinessDayAdjustment::Previous => domain::calendar::BusinessDayAdjustment::Previous,
+        GraphQLBusinessDayAdjustment::Next => domain::calendar::BusinessDayAdjustment::Next,
+    }
+}
+
+fn to_domain_currency(v: GraphQLCurrency) -> domain::currency::Currency {
+    match v {
+        GraphQLCurrency::Jpy => domain::currency::Currency::JPY,
+        GraphQLCurrency::Usd => domain::currency::Currency::USD,
+        GraphQLCurrency::Eur => domain::currency::Currency::EUR,
+    }
+}
+
+fn to_domain_tax_type(v: GraphQLTaxType) -> domain::invoice::TaxType {
+    match v {
+        GraphQLTaxType::Standard10 => domain::invoice::TaxType::Standard10,
+        GraphQLTaxType::Reduced8 => domain::invoice::TaxType::Reduced8,
+        GraphQLTaxType::NonTaxable => domain::invoice::TaxType::NonTaxable,
+    }
+}
+
+fn to_domain_tax_rounding(v: GraphQLTaxRounding) -> domain::invoice::TaxRounding {
+    match v {
+        GraphQLTaxRounding::Floor => domain::invoice::TaxRounding::Floor,
+        GraphQLTaxRounding::Round => domain::invoice::TaxRounding::Round,
+        GraphQLTaxRounding::Ceil => domain::invoice::TaxRounding::Ceil,
+    }
+}
+
+/// 請求先の設定のうち、入力で省略できる項目
+struct SupplierSettingsInput {
+    currency: Option<GraphQLCurrency>,
+    remarks_template: Option<String>,
+    invoice_number_prefix: Option<String>,
+    billing_email: Option<String>,
+    reminder_schedule: Option<String>,
+    billing_cc: Option<Vec<String>>,
+    billing_bcc: Option<Vec<String>>,
+    auto_send_invoice: Option<bool>,
+    closing_day: Option<i32>,
+    payment_term_type: Option<GraphQLPaymentTermType>,
+    payment_term_months: Option<i32>,
+    payment_term_day: Option<i32>,
+    business_day_adjustment: Option<GraphQLBusinessDayAdjustment>,
+    settlement_lower_hours: Option<i32>,
+    settlement_upper_hours: Option<i32>,
+    excess_hourly_rate: Option<Money>,
+    deduction_hourly_rate: Option<Money>,
+    retainer_included_quantity: Option<i32>,
+    retainer_unit: Option<String>,
+    retainer_overage_rate: Option<Money>,
+    interval_months: Option<i32>,
+    start_ym: Option<String>,
+    contract_end_ym: Option<String>,
+    contract_start_date: Option<String>,
+    contract_end_date: Option<String>,
+    proration_basis: Option<GraphQLProrationBasis>,
+    tax_type: Option<GraphQLTaxType>,
+    tax_inclusive: Option<bool>,
+    tax_rounding: Option<GraphQLTaxRounding>,
+    withholding_tax: Option<bool>,
+}
+
+/// 請求先の設定。既定値はsuppliersテーブルの既定値と同じ
+#[derive(Default)]
+struct SupplierSettings {
+    currency: domain::currency::Currency,
+    remarks_template: String,
+    invoice_number_prefix: String,
+    billing_email: String,
+    reminder_schedule: String,
+    delivery: domain::invoice_delivery::DeliverySetting,
+    closing_day: domain::supplier::ClosingDay,
+    payment_term: domain::supplier::PaymentTerm,
+    business_day_adjustment: domain::calendar::BusinessDayAdjustment,
+    settlement_range: domain::supplier::SettlementRange,
+    retainer: domain::supplier::RetainerAllowance,
+    recurrence: domain::supplier::Recurrence,
+    proration: domain::supplier::Proration,
+    tax_setting: domain::invoice::TaxSetting,
+    withholding_tax: bool,
+}
+
+impl SupplierSettings {
+    fn of(supplier: &domain::supplier::Supplier) -> Self {
+        SupplierSettings {
+            currency: supplier.currency.clone(),
+            remarks_template: supplier.remarks_template.clone(),
+            invoice_number_prefix: supplier.invoice_number_prefix.clone(),
+            billing_email: supplier.billing_email.clone(),
+            reminder_schedule: supplier.reminder_schedule.clone(),
+            delivery: supplier.delivery.clone(),
+            closing_day: supplier.closing_day.clone(),
+            payment_term: supplier.payment_term.clone(),
+            business_day_adjustment: supplier.business_day_adjustment.clone(),
+            settlement_range: supplier.settlement_range.clone(),
+            retainer: supplier.retainer.clone(),
+            recurrence: supplier.recurrence.clone(),
+            proration: supplier.proration.clone(),
+            tax_setting: supplier.tax_setting.clone(),
+            withholding_tax: supplier.withholding_tax,
+        }
+    }
+
+    /// 入力された項目だけを置き換えて検証する
+    fn merge(self, input: SupplierSettingsInput) -> Result<Self, String> {
+        let remarks_template = input.remarks_template.unwrap_or(self.remarks_template);
+        domain::subject_template::SubjectTemplate::from_str(remarks_template.as_str())?;
+        let billing_email = input.billing_email.unwrap_or(self.billing_email);
+        if !billing_email.is_empty() {
+            domain::invoice_delivery::validate_address(billing_email.as_str())?;
+        }
+        let reminder_schedule = input.reminder_schedule.unwrap_or(self.reminder_schedule);
+        domain::reminder::ReminderSchedule::from_str(reminder_schedule.as_str())?;
+
+        Ok(SupplierSettings {
+            currency: input
+                .currency
+                .map(to_domain_currency)
+                .unwrap_or(self.currency),
+            remarks_template,
+            invoice_number_prefix: input
+                .invoice_number_prefix
+                .unwrap_or(self.invoice_number_prefix),
+            billing_email,
+            reminder_schedule,
+            delivery: domain::invoice_delivery::DeliverySetting::new(
+                input.billing_cc.unwrap_or(self.delivery.cc),
+                input.billing_bcc.unwrap_or(self.delivery.bcc),
+                input.auto_send_invoice.unwrap_or(self.delivery.auto_send),
+            )?,
+            closing_day: match input.closing_day {
+                Some(v) => domain::supplier::ClosingDay::new(v)?,
+                None => self.closing_day,
+            },
+            payment_term: domain::supplier::PaymentTerm::new(
+                input
+                    .payment_term_type
+                    .map(to_domain_payment_term_type)
+                    .unwrap_or(self.payment_term.term_type),
+                input
+                    .payment_term_months
+                    .unwrap_or(self.payment_term.months as i32),
+                input
+                    .payment_term_day
+                    .unwrap_or(self.payment_term.day as i32),
+            )?,
+            business_day_adjustment: input
+                .business_day_adjustment
+                .map(to_domain_business_day_adjustment)
+                .unwrap_or(self.business_day_adjustment),
+            settlement_range: domain::supplier::SettlementRange::new(
+                input
+                    .settlement_lower_hours
+                    .unwrap_or(self.settlement_range.lower_hours),
+                input
+                    .settlement_upper_hours
+                    .unwrap_or(self.settlement_range.upper_hours),
+                input
+                    .excess_hourly_rate
+                    .unwrap_or(self.settlement_range.excess_rate),
+                input
+                    .deduction_hourly_rate
+                    .unwrap_or(self.settlement_range.deduction_rate),
+            )?,
+            retainer: domain::supplier::RetainerAllowance::new(
+                input
+                    .retainer_included_quantity
+                    .unwrap_or(self.retainer.included_quantity),
+                input.retainer_unit.unwrap_or(self.retainer.unit),
+                input
+                    .retainer_overage_rate
+                    .unwrap_or(self.retainer.overage_rate),
+            )?,
+            recurrence: to_domain_recurrence(
+                input
+                    .interval_months
+                    .unwrap_or(self.recurrence.interval_months as i32),
+                input
+                    .start_ym
+                    .unwrap_or(self.recurrence.start_ym.to_string()),
+                input
+                    .contract_end_ym
+                    .unwrap_or(self.recurrence.end_ym.to_string()),
+            )?,
+            proration: domain::supplier::Proration::new(
+                input
+                    .proration_basis
+                    .map(to_domain_proration_basis)
+                    .unwrap_or(self.proration.basis),
+                match input.contract_start_date {
+                    Some(v) => domain::YMD::from_str(v.as_str())?,
+                    None => self.proration.start_date,
+                },
+                match input.contract_end_date {
+                    Some(v) => domain::YMD::from_str(v.as_str())?,
+                    None => self.proration.end_date,
+                },
+            )?,
+            tax_setting: domain::invoice::TaxSetting {
+                tax_type: input
+                    .tax_type
+                    .map(to_domain_tax_type)
+                    .unwrap_or(self.tax_setting.tax_type),
+                inclusive: input.tax_inclusive.unwrap_or(self.tax_setting.inclusive),
+                rounding: input
+                    .tax_rounding
+                    .map(to_domain_tax_rounding)
+                    .unwrap_or(self.tax_setting.rounding),
+            },
+            withholding_tax: input.withholding_tax.unwrap_or(self.withholding_tax),
+        })
+    }
+}
+
+fn to_domain_recurrence(
+    interval_months: i32,
+    start_ym: String,
+    contract_end_ym: String,
+) -> Result<domain::supplier::Recurrence, String> {
+    domain::supplier::Recurrence::new(
+        interval_months,
+        domain::YM::from_str(start_ym.as_str())?,
+        domain::YM::from_str(contract_end_ym.as_str())?,
+    )
+}
+
+fn to_domain_proration_basis(v: GraphQLProrationBasis) -> domain::supplier::ProrationBasis {
+    match v {
+        GraphQLProrationBasis::CalendarDays => domain::supplier::ProrationBasis::CalendarDays,
+        GraphQLProrationBasis::BusinessDays => domain::supplier::ProrationBasis::BusinessDays,
+    }
+}
+
+fn to_domain_milestone_amount_type(
+    v: GraphQLMilestoneAmountType,
+) -> domain::supplier_milestone::MilestoneAmountType {
+    match v {
+        GraphQLMilestoneAmountType::Fixed => domain::supplier_milestone::MilestoneAmountType::Fixed,
+        GraphQLMilestoneAmountType::Percentage => {
+            domain::supplier_milestone::MilestoneAmountType::Percentage
+        }
+    }
+}
+
+fn to_domain_payment_method(v: GraphQLPaymentMethod) -> domain::payment::PaymentMethod {
+    match v {
+        GraphQLPaymentMethod::BankTransfer => domain::payment::PaymentMethod::BankTransfer,
+        GraphQLPaymentMethod::Cash => domain::payment::PaymentMethod::Cash,
+        GraphQLPaymentMethod::CreditCard => domain::payment::PaymentMethod::CreditCard,
+        GraphQLPaymentMethod::Other => domain::payment::PaymentMethod::Other,
+    }
 }
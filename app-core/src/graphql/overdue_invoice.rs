@@ -0,0 +1,44 @@
+use crate::domain;
+use crate::graphql::*;
+use juniper_from_schema::{QueryTrail, Walked};
+
+#[derive(Debug, Clone)]
+pub struct OverdueInvoice {
+    pub invoice: domain::invoice::Invoice,
+    pub supplier: domain::supplier::Supplier,
+    pub days_overdue: i64,
+}
+#[async_trait]
+impl OverdueInvoiceFields for OverdueInvoice {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.invoice.id.clone()))
+    }
+
+    fn field_invoice<'s, 'r>(
+        &'s self,
+        _: &Executor<Context>,
+        _: &QueryTrail<'r, Invoice, Walked>,
+    ) -> FieldResult<Invoice> {
+        Ok(Invoice {
+            invoice: self.invoice.to_owned(),
+        })
+    }
+
+    fn field_supplier<'s, 'r>(
+        &'s self,
+        _: &Executor<Context>,
+        _: &QueryTrail<'r, Supplier, Walked>,
+    ) -> FieldResult<Supplier> {
+        Ok(Supplier {
+            supplier: self.supplier.to_owned(),
+        })
+    }
+
+    fn field_days_overdue(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.days_overdue as i32)
+    }
+
+    fn field_overdue_at(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        Ok(self.invoice.overdue_at.map(|v| v.to_string()))
+    }
+}
@@ -0,0 +1,38 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct Payment {
+    pub payment: domain::payment::Payment,
+}
+#[async_trait]
+impl PaymentFields for Payment {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.payment.id.clone()))
+    }
+
+    fn field_invoice_id(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.payment.invoice_id.clone())
+    }
+
+    fn field_received_ymd(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.payment.received_ymd.to_string())
+    }
+
+    fn field_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.payment.amount.clone())
+    }
+
+    fn field_method(&self, _: &Executor<Context>) -> FieldResult<GraphQLPaymentMethod> {
+        Ok(match self.payment.method {
+            domain::payment::PaymentMethod::BankTransfer => GraphQLPaymentMethod::BankTransfer,
+            domain::payment::PaymentMethod::Cash => GraphQLPaymentMethod::Cash,
+            domain::payment::PaymentMethod::CreditCard => GraphQLPaymentMethod::CreditCard,
+            domain::payment::PaymentMethod::Other => GraphQLPaymentMethod::Other,
+        })
+    }
+
+    fn field_memo(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.payment.memo.clone())
+    }
+}
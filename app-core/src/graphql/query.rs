@@ -5,8 +5,10 @@ use crate::graphql::Context;
 use crate::graphql::*;
 use crate::{domain, CoreError, FieldErrorWithCode};
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use juniper::{Executor, FieldResult};
 use juniper_from_schema::{QueryTrail, Walked};
+use std::str::FromStr;
 
 pub struct Query;
 #[async_trait]
@@ -133,4 +135,215 @@ impl QueryFields for Query {
             has_next,
         })
     }
+
+    async fn field_holiday_list<'s, 'r, 'a>(
+        &'s self,
+        _exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Holiday, Walked>,
+        year: i32,
+    ) -> FieldResult<Vec<Holiday>> {
+        if year < 2007 || year > 2099 {
+            return Err(FieldErrorWithCode::from(CoreError::BadRequest(
+                "2007〜2099年の範囲で指定してください".to_string(),
+            ))
+            .into());
+        }
+
+        Ok(domain::calendar::holidays(year)
+            .into_iter()
+            .map(|v| Holiday { holiday: v })
+            .collect())
+    }
+
+    async fn field_exchange_rate_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, ExchangeRate, Walked>,
+        currency: GraphQLCurrency,
+    ) -> FieldResult<Vec<ExchangeRate>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let exchange_rate_dao: Dao<domain::currency::ExchangeRate> = Dao::new();
+        ctx.authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let currency = match currency {
+            GraphQLCurrency::Jpy => domain::currency::Currency::JPY,
+            GraphQLCurrency::Usd => domain::currency::Currency::USD,
+            GraphQLCurrency::Eur => domain::currency::Currency::EUR,
+        };
+
+        let exchange_rates = exchange_rate_dao
+            .get_all_by_currency(&conn, &currency)
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(exchange_rates
+            .into_iter()
+            .map(|v| ExchangeRate { exchange_rate: v })
+            .collect())
+    }
+
+    /// 支払期限を過ぎても入金が揃っていない請求書を期限の古い順に返す
+    async fn field_overdue_invoice_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, OverdueInvoice, Walked>,
+    ) -> FieldResult<Vec<OverdueInvoice>> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let invoices = invoice_dao
+            .get_all_unpaid_by_user(&conn, authenticated_user_id)
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(invoices
+            .into_iter()
+            .filter(|v| v.0.is_overdue(now))
+            .map(|v| OverdueInvoice {
+                days_overdue: v.0.days_overdue(now),
+                invoice: v.0,
+                supplier: v.1,
+            })
+            .collect())
+    }
+
+    /// 未回収の請求書がある請求先ごとの残高
+    async fn field_supplier_outstanding_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierOutstanding, Walked>,
+    ) -> FieldResult<Vec<SupplierOutstanding>> {
+        let now: DateTime<Utc> = Utc::now();
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let exchange_rate_dao: Dao<domain::currency::ExchangeRate> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let rates = exchange_rate_dao
+            .get_all_until(&conn, &domain::YMD::from(now.naive_utc().date()))
+            .map_err(FieldErrorWithCode::from)?;
+        let suppliers = supplier_dao
+            .get_all_by_user(&conn, authenticated_user_id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        let invoices = invoice_dao
+            .get_all_unpaid_by_user(&conn, authenticated_user_id)
+            .map_err(FieldErrorWithCode::from)?
+            .into_iter()
+            .map(|v| v.0)
+            .collect::<Vec<_>>();
+
+        Ok(suppliers
+            .into_iter()
+            .filter_map(|supplier| {
+                let invoices = invoices
+                    .iter()
+                    .filter(|v| v.supplier_id == supplier.id)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if invoices.is_empty() {
+                    return None;
+                }
+                Some(SupplierOutstanding {
+                    summary: domain::invoice::OutstandingSummary::of(&invoices, &rates, now),
+                    supplier,
+                })
+            })
+            .collect())
+    }
+
+    /// 発行月・通貨ごとの請求額。取り消した請求書と赤伝を差し引く。円換算は請求書ごとに発行日の仲値で行う
+    async fn field_billing_summary_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, BillingSummary, Walked>,
+        year: i32,
+    ) -> FieldResult<Vec<BillingSummary>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let invoice_dao: Dao<domain::invoice::Invoice> = Dao::new();
+        let exchange_rate_dao: Dao<domain::currency::ExchangeRate> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        if year < 2000 || year > 2099 {
+            return Err(FieldErrorWithCode::from(CoreError::BadRequest(
+                "2000〜2099年の範囲で指定してください".to_string(),
+            ))
+            .into());
+        }
+
+        let to = domain::YMD::from(NaiveDate::from_ymd(year, 12, 31));
+        let invoices = invoice_dao
+            .get_all_issued_by_user(
+                &conn,
+                authenticated_user_id,
+                &domain::YMD::from(NaiveDate::from_ymd(year, 1, 1)),
+                &to,
+            )
+            .map_err(FieldErrorWithCode::from)?;
+        let rates = exchange_rate_dao
+            .get_all_until(&conn, &to)
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(domain::invoice::BillingSummary::of(&invoices, &rates)
+            .into_iter()
+            .map(|summary| BillingSummary { summary })
+            .collect())
+    }
+
+    /// subjectTemplate を指定した場合は保存前のテンプレートで件名を作る
+    async fn field_subject_preview<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        supplier_id: String,
+        ym: String,
+        subject_template: Option<String>,
+    ) -> FieldResult<String> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_dao: Dao<domain::supplier::Supplier> = Dao::new();
+        let authenticated_user_id = ctx
+            .authenticated_user_id
+            .clone()
+            .ok_or(FieldErrorWithCode::from(CoreError::UnAuthenticate))?;
+
+        let ym = domain::YM::from_str(ym.as_str())
+            .ok()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| {
+                FieldErrorWithCode::from(CoreError::BadRequest(
+                    "対象月を正しく入力してください".to_string(),
+                ))
+            })?;
+
+        let mut supplier = supplier_dao
+            .get(&conn, supplier_id)
+            .map_err(FieldErrorWithCode::from)?;
+        if supplier.user_id != authenticated_user_id {
+            return Err(FieldErrorWithCode::from(CoreError::Forbidden).into());
+        }
+
+        if let Some(subject_template) = subject_template {
+            domain::subject_template::SubjectTemplate::from_str(subject_template.as_str())
+                .map_err(|e| FieldErrorWithCode::from(CoreError::BadRequest(e)))?;
+            supplier.subject_template = subject_template;
+        }
+
+        // プレビューでは採番しないため、請求書番号は空で描画する
+        Ok(supplier.subject_at(&ym, ""))
+    }
 }
@@ -0,0 +1,21 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct RetainerUsage {
+    pub usage: domain::retainer_usage::RetainerUsage,
+}
+#[async_trait]
+impl RetainerUsageFields for RetainerUsage {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.usage.id.clone()))
+    }
+
+    fn field_ym(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.usage.ym.to_string())
+    }
+
+    fn field_quantity(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.usage.quantity)
+    }
+}
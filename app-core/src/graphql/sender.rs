@@ -30,4 +30,12 @@ impl SenderFields for Sender {
     fn field_address(&self, _: &Executor<Context>) -> FieldResult<String> {
         Ok(self.sender.address.clone())
     }
+
+    fn field_registration_number(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.sender.registration_number.clone())
+    }
+
+    fn field_remarks_template(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.sender.remarks_template.clone())
+    }
 }
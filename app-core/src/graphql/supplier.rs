@@ -1,5 +1,7 @@
-use crate::domain;
+use crate::ddb::Dao;
 use crate::graphql::*;
+use crate::{domain, FieldErrorWithCode};
+use chrono::Utc;
 use juniper_from_schema::{QueryTrail, Walked};
 
 #[derive(Debug, Clone)]
@@ -16,18 +18,45 @@ impl SupplierFields for Supplier {
         Ok(self.supplier.name.clone())
     }
 
-    fn field_billing_amount_include_tax(&self, _: &Executor<Context>) -> FieldResult<i32> {
-        Ok(self.supplier.billing_amount_include_tax().clone())
+    async fn field_billing_amount_include_tax<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+    ) -> FieldResult<Money> {
+        let prices = self.prices(exec.context())?;
+
+        Ok(self
+            .supplier
+            .with_current_price(&prices, Utc::now())
+            .billing_amount_include_tax())
+    }
+
+    async fn field_billing_amount_exclude_tax<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+    ) -> FieldResult<Money> {
+        let prices = self.prices(exec.context())?;
+
+        Ok(self
+            .supplier
+            .with_current_price(&prices, Utc::now())
+            .billing_amount_exclude_tax())
     }
 
-    fn field_billing_amount_exclude_tax(&self, _: &Executor<Context>) -> FieldResult<i32> {
-        Ok(self.supplier.billing_amount.clone())
+    fn field_currency(&self, _: &Executor<Context>) -> FieldResult<GraphQLCurrency> {
+        Ok(match self.supplier.currency {
+            domain::currency::Currency::JPY => GraphQLCurrency::Jpy,
+            domain::currency::Currency::USD => GraphQLCurrency::Usd,
+            domain::currency::Currency::EUR => GraphQLCurrency::Eur,
+        })
     }
 
     fn field_billing_type(&self, _: &Executor<Context>) -> FieldResult<GraphQLBillingType> {
         Ok(match self.supplier.billing_type {
             domain::supplier::BillingType::Monthly => GraphQLBillingType::Monthly,
             domain::supplier::BillingType::OneTime => GraphQLBillingType::OneTime,
+            domain::supplier::BillingType::Hourly => GraphQLBillingType::Hourly,
+            domain::supplier::BillingType::Milestone => GraphQLBillingType::Milestone,
+            domain::supplier::BillingType::Retainer => GraphQLBillingType::Retainer,
         })
     }
 
@@ -46,6 +75,193 @@ impl SupplierFields for Supplier {
         Ok(self.supplier.subject_template.clone())
     }
 
+    fn field_remarks_template(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.supplier.remarks_template.clone())
+    }
+
+    fn field_invoice_number_prefix(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.supplier.invoice_number_prefix.clone())
+    }
+
+    fn field_billing_email(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.supplier.billing_email.clone())
+    }
+
+    fn field_reminder_schedule(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.supplier.reminder_schedule.clone())
+    }
+
+    fn field_billing_cc(&self, _: &Executor<Context>) -> FieldResult<Vec<String>> {
+        Ok(self.supplier.delivery.cc.clone())
+    }
+
+    fn field_billing_bcc(&self, _: &Executor<Context>) -> FieldResult<Vec<String>> {
+        Ok(self.supplier.delivery.bcc.clone())
+    }
+
+    fn field_auto_send_invoice(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.supplier.delivery.auto_send)
+    }
+
+    fn field_closing_day(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.supplier.closing_day.int())
+    }
+
+    fn field_payment_term_type(
+        &self,
+        _: &Executor<Context>,
+    ) -> FieldResult<GraphQLPaymentTermType> {
+        Ok(match self.supplier.payment_term.term_type {
+            domain::supplier::PaymentTermType::MonthEnd => GraphQLPaymentTermType::MonthEnd,
+            domain::supplier::PaymentTermType::MonthDay => GraphQLPaymentTermType::MonthDay,
+            domain::supplier::PaymentTermType::DaysAfterIssue => {
+                GraphQLPaymentTermType::DaysAfterIssue
+            }
+        })
+    }
+
+    fn field_payment_term_months(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.supplier.payment_term.months as i32)
+    }
+
+    fn field_payment_term_day(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.supplier.payment_term.day as i32)
+    }
+
+    fn field_business_day_adjustment(
+        &self,
+        _: &Executor<Context>,
+    ) -> FieldResult<GraphQLBusinessDayAdjustment> {
+        Ok(match self.supplier.business_day_adjustment {
+            domain::calendar::BusinessDayAdjustment::Unadjusted => {
+                GraphQLBusinessDayAdjustment::Unadjusted
+            }
+            domain::calendar::BusinessDayAdjustment::Previous => {
+                GraphQLBusinessDayAdjustment::Previous
+            }
+            domain::calendar::BusinessDayAdjustment::Next => GraphQLBusinessDayAdjustment::Next,
+        })
+    }
+
+    fn field_settlement_lower_hours(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.supplier.settlement_range.lower_hours)
+    }
+
+    fn field_settlement_upper_hours(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.supplier.settlement_range.upper_hours)
+    }
+
+    fn field_excess_hourly_rate(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.supplier.settlement_range.excess_rate.clone())
+    }
+
+    fn field_deduction_hourly_rate(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.supplier.settlement_range.deduction_rate.clone())
+    }
+
+    fn field_retainer_included_quantity(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.supplier.retainer.included_quantity)
+    }
+
+    fn field_retainer_unit(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.supplier.retainer.unit.clone())
+    }
+
+    fn field_retainer_overage_rate(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.supplier.retainer.overage_rate.clone())
+    }
+
+    fn field_interval_months(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.supplier.recurrence.interval_months as i32)
+    }
+
+    fn field_start_ym(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        if !self.supplier.recurrence.start_ym.is_empty() {
+            return Ok(Some(self.supplier.recurrence.start_ym.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn field_contract_end_ym(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        if !self.supplier.recurrence.end_ym.is_empty() {
+            return Ok(Some(self.supplier.recurrence.end_ym.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn field_contract_start_date(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        if !self.supplier.proration.start_date.is_empty() {
+            return Ok(Some(self.supplier.proration.start_date.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn field_contract_end_date(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        if !self.supplier.proration.end_date.is_empty() {
+            return Ok(Some(self.supplier.proration.end_date.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn field_proration_basis(&self, _: &Executor<Context>) -> FieldResult<GraphQLProrationBasis> {
+        Ok(match self.supplier.proration.basis {
+            domain::supplier::ProrationBasis::CalendarDays => GraphQLProrationBasis::CalendarDays,
+            domain::supplier::ProrationBasis::BusinessDays => GraphQLProrationBasis::BusinessDays,
+        })
+    }
+
+    fn field_tax_type(&self, _: &Executor<Context>) -> FieldResult<GraphQLTaxType> {
+        Ok(match self.supplier.tax_setting.tax_type {
+            domain::invoice::TaxType::Standard10 => GraphQLTaxType::Standard10,
+            domain::invoice::TaxType::Reduced8 => GraphQLTaxType::Reduced8,
+            domain::invoice::TaxType::NonTaxable => GraphQLTaxType::NonTaxable,
+        })
+    }
+
+    fn field_tax_inclusive(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.supplier.tax_setting.inclusive)
+    }
+
+    fn field_tax_rounding(&self, _: &Executor<Context>) -> FieldResult<GraphQLTaxRounding> {
+        Ok(match self.supplier.tax_setting.rounding {
+            domain::invoice::TaxRounding::Floor => GraphQLTaxRounding::Floor,
+            domain::invoice::TaxRounding::Round => GraphQLTaxRounding::Round,
+            domain::invoice::TaxRounding::Ceil => GraphQLTaxRounding::Ceil,
+        })
+    }
+
+    fn field_withholding_tax(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.supplier.withholding_tax)
+    }
+
+    fn field_archived(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.supplier.archived)
+    }
+
+    async fn field_next_issue_ymd<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+    ) -> FieldResult<Option<String>> {
+        let suspensions = self.suspensions(exec.context())?;
+
+        Ok(self
+            .supplier
+            .next_payment_date(Utc::now(), &suspensions)
+            .map(|(issue_date, _)| issue_date))
+    }
+
+    async fn field_next_payment_due_on_ymd<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+    ) -> FieldResult<Option<String>> {
+        let suspensions = self.suspensions(exec.context())?;
+
+        Ok(self
+            .supplier
+            .next_payment_date(Utc::now(), &suspensions)
+            .map(|(_, payment_due_on)| payment_due_on))
+    }
+
     async fn field_latest_invoice_list<'s, 'r, 'a>(
         &'s self,
         exec: &Executor<'r, 'a, Context>,
@@ -65,4 +281,201 @@ impl SupplierFields for Supplier {
             })
             .collect())
     }
+
+    async fn field_working_hours_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, WorkingHours, Walked>,
+    ) -> FieldResult<Vec<WorkingHours>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let working_hours_dao: Dao<domain::working_hours::WorkingHours> = Dao::new();
+
+        let items = working_hours_dao
+            .get_all_by_supplier(&conn, self.supplier.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(items
+            .iter()
+            .map(|v| WorkingHours {
+                working_hours: v.to_owned(),
+            })
+            .collect())
+    }
+
+    async fn field_retainer_usage_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, RetainerUsage, Walked>,
+    ) -> FieldResult<Vec<RetainerUsage>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let retainer_usage_dao: Dao<domain::retainer_usage::RetainerUsage> = Dao::new();
+
+        let items = retainer_usage_dao
+            .get_all_by_supplier(&conn, self.supplier.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(items
+            .iter()
+            .map(|v| RetainerUsage {
+                usage: v.to_owned(),
+            })
+            .collect())
+    }
+
+    async fn field_item_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierItem, Walked>,
+    ) -> FieldResult<Vec<SupplierItem>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_item_dao: Dao<domain::supplier_item::SupplierItem> = Dao::new();
+
+        let items = supplier_item_dao
+            .get_all_by_supplier(&conn, self.supplier.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(items
+            .iter()
+            .map(|v| SupplierItem { item: v.to_owned() })
+            .collect())
+    }
+
+    async fn field_milestone_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierMilestone, Walked>,
+    ) -> FieldResult<Vec<SupplierMilestone>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_milestone_dao: Dao<domain::supplier_milestone::SupplierMilestone> = Dao::new();
+
+        let milestones = supplier_milestone_dao
+            .get_all_by_supplier(&conn, self.supplier.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(milestones
+            .iter()
+            .map(|v| SupplierMilestone {
+                milestone: v.to_owned(),
+                amount: self.supplier.milestone_amount(v),
+            })
+            .collect())
+    }
+
+    async fn field_suspension_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierSuspension, Walked>,
+    ) -> FieldResult<Vec<SupplierSuspension>> {
+        let suspensions = self.suspensions(exec.context())?;
+
+        Ok(suspensions
+            .iter()
+            .map(|v| SupplierSuspension {
+                suspension: v.to_owned(),
+            })
+            .collect())
+    }
+
+    async fn field_price_history_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierPrice, Walked>,
+    ) -> FieldResult<Vec<SupplierPrice>> {
+        let prices = self.prices(exec.context())?;
+
+        Ok(prices
+            .iter()
+            .map(|v| SupplierPrice {
+                price: v.to_owned(),
+            })
+            .collect())
+    }
+
+    async fn field_upcoming_price<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierPrice, Walked>,
+    ) -> FieldResult<Option<SupplierPrice>> {
+        let prices = self.prices(exec.context())?;
+        let current = self.supplier.next_billing_period(Utc::now()).ym();
+
+        Ok(
+            domain::supplier_price::upcoming_price(&prices, &current).map(|v| SupplierPrice {
+                price: v.to_owned(),
+            }),
+        )
+    }
+
+    async fn field_expense_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, Expense, Walked>,
+    ) -> FieldResult<Vec<Expense>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let expense_dao: Dao<domain::expense::Expense> = Dao::new();
+
+        let expenses = expense_dao
+            .get_all_by_supplier(&conn, self.supplier.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(expenses
+            .iter()
+            .map(|v| Expense {
+                expense: v.to_owned(),
+            })
+            .collect())
+    }
+
+    async fn field_adjustment_list<'s, 'r, 'a>(
+        &'s self,
+        exec: &Executor<'r, 'a, Context>,
+        _: &QueryTrail<'r, SupplierAdjustment, Walked>,
+    ) -> FieldResult<Vec<SupplierAdjustment>> {
+        let ctx = exec.context();
+        let conn = ctx.get_mutex_connection();
+        let supplier_adjustment_dao: Dao<domain::supplier_adjustment::SupplierAdjustment> =
+            Dao::new();
+
+        let adjustments = supplier_adjustment_dao
+            .get_all_by_supplier(&conn, self.supplier.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+
+        Ok(adjustments
+            .iter()
+            .map(|v| SupplierAdjustment {
+                adjustment: v.to_owned(),
+            })
+            .collect())
+    }
+}
+
+impl Supplier {
+    fn suspensions(
+        &self,
+        ctx: &Context,
+    ) -> FieldResult<Vec<domain::supplier_suspension::SupplierSuspension>> {
+        let conn = ctx.get_mutex_connection();
+        let supplier_suspension_dao: Dao<domain::supplier_suspension::SupplierSuspension> =
+            Dao::new();
+
+        let suspensions = supplier_suspension_dao
+            .get_all_by_supplier(&conn, self.supplier.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        Ok(suspensions)
+    }
+
+    fn prices(&self, ctx: &Context) -> FieldResult<Vec<domain::supplier_price::SupplierPrice>> {
+        let conn = ctx.get_mutex_connection();
+        let supplier_price_dao: Dao<domain::supplier_price::SupplierPrice> = Dao::new();
+
+        let prices = supplier_price_dao
+            .get_all_by_supplier(&conn, self.supplier.id.clone())
+            .map_err(FieldErrorWithCode::from)?;
+        Ok(prices)
+    }
 }
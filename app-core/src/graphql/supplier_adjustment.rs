@@ -0,0 +1,48 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct SupplierAdjustment {
+    pub adjustment: domain::supplier_adjustment::SupplierAdjustment,
+}
+#[async_trait]
+impl SupplierAdjustmentFields for SupplierAdjustment {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.adjustment.id.clone()))
+    }
+
+    fn field_description(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.adjustment.description.clone())
+    }
+
+    fn field_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.adjustment.amount.clone())
+    }
+
+    fn field_tax_type(&self, _: &Executor<Context>) -> FieldResult<GraphQLTaxType> {
+        Ok(match self.adjustment.tax_type {
+            domain::invoice::TaxType::Standard10 => GraphQLTaxType::Standard10,
+            domain::invoice::TaxType::Reduced8 => GraphQLTaxType::Reduced8,
+            domain::invoice::TaxType::NonTaxable => GraphQLTaxType::NonTaxable,
+        })
+    }
+
+    fn field_target_ym(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        if !self.adjustment.target_ym.is_empty() {
+            return Ok(Some(self.adjustment.target_ym.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn field_applied(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.adjustment.is_applied())
+    }
+
+    fn field_cancelled(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.adjustment.cancelled)
+    }
+
+    fn field_invoice_id(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        Ok(self.adjustment.invoice_id.clone())
+    }
+}
@@ -0,0 +1,45 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct SupplierItem {
+    pub item: domain::supplier_item::SupplierItem,
+}
+#[async_trait]
+impl SupplierItemFields for SupplierItem {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.item.id.clone()))
+    }
+
+    fn field_name(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.item.name.clone())
+    }
+
+    fn field_quantity(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.item.quantity)
+    }
+
+    fn field_unit(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.item.unit.clone())
+    }
+
+    fn field_unit_price(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.item.unit_price.clone())
+    }
+
+    fn field_tax_type(&self, _: &Executor<Context>) -> FieldResult<GraphQLTaxType> {
+        Ok(match self.item.tax_type {
+            domain::invoice::TaxType::Standard10 => GraphQLTaxType::Standard10,
+            domain::invoice::TaxType::Reduced8 => GraphQLTaxType::Reduced8,
+            domain::invoice::TaxType::NonTaxable => GraphQLTaxType::NonTaxable,
+        })
+    }
+
+    fn field_withholding(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.item.withholding)
+    }
+
+    fn field_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.item.amount())
+    }
+}
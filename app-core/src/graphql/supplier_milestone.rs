@@ -0,0 +1,57 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct SupplierMilestone {
+    pub milestone: domain::supplier_milestone::SupplierMilestone,
+    /// 契約金額から求めた請求金額
+    pub amount: Money,
+}
+#[async_trait]
+impl SupplierMilestoneFields for SupplierMilestone {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.milestone.id.clone()))
+    }
+
+    fn field_name(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.milestone.name.clone())
+    }
+
+    fn field_position(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.milestone.position)
+    }
+
+    fn field_amount_type(&self, _: &Executor<Context>) -> FieldResult<GraphQLMilestoneAmountType> {
+        Ok(match self.milestone.amount_type {
+            domain::supplier_milestone::MilestoneAmountType::Fixed => {
+                GraphQLMilestoneAmountType::Fixed
+            }
+            domain::supplier_milestone::MilestoneAmountType::Percentage => {
+                GraphQLMilestoneAmountType::Percentage
+            }
+        })
+    }
+
+    fn field_amount_value(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.milestone.amount_value.clone())
+    }
+
+    fn field_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.amount.clone())
+    }
+
+    fn field_target_ym(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        if !self.milestone.target_ym.is_empty() {
+            return Ok(Some(self.milestone.target_ym.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn field_billed(&self, _: &Executor<Context>) -> FieldResult<bool> {
+        Ok(self.milestone.is_billed())
+    }
+
+    fn field_invoice_id(&self, _: &Executor<Context>) -> FieldResult<Option<String>> {
+        Ok(self.milestone.invoice_id.clone())
+    }
+}
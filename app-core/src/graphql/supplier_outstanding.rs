@@ -0,0 +1,49 @@
+use crate::domain;
+use crate::graphql::*;
+use juniper_from_schema::{QueryTrail, Walked};
+
+#[derive(Debug, Clone)]
+pub struct SupplierOutstanding {
+    pub supplier: domain::supplier::Supplier,
+    pub summary: domain::invoice::OutstandingSummary,
+}
+#[async_trait]
+impl SupplierOutstandingFields for SupplierOutstanding {
+    fn field_supplier<'s, 'r>(
+        &'s self,
+        _: &Executor<Context>,
+        _: &QueryTrail<'r, Supplier, Walked>,
+    ) -> FieldResult<Supplier> {
+        Ok(Supplier {
+            supplier: self.supplier.to_owned(),
+        })
+    }
+
+    fn field_currency(&self, _: &Executor<Context>) -> FieldResult<GraphQLCurrency> {
+        Ok(match self.supplier.currency {
+            domain::currency::Currency::JPY => GraphQLCurrency::Jpy,
+            domain::currency::Currency::USD => GraphQLCurrency::Usd,
+            domain::currency::Currency::EUR => GraphQLCurrency::Eur,
+        })
+    }
+
+    fn field_outstanding_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.summary.outstanding_amount.clone())
+    }
+
+    fn field_overdue_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.summary.overdue_amount.clone())
+    }
+
+    fn field_overdue_count(&self, _: &Executor<Context>) -> FieldResult<i32> {
+        Ok(self.summary.overdue_count)
+    }
+
+    fn field_outstanding_amount_jpy(&self, _: &Executor<Context>) -> FieldResult<Option<Money>> {
+        Ok(self.summary.outstanding_amount_jpy.clone())
+    }
+
+    fn field_overdue_amount_jpy(&self, _: &Executor<Context>) -> FieldResult<Option<Money>> {
+        Ok(self.summary.overdue_amount_jpy.clone())
+    }
+}
@@ -0,0 +1,21 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct SupplierPrice {
+    pub price: domain::supplier_price::SupplierPrice,
+}
+#[async_trait]
+impl SupplierPriceFields for SupplierPrice {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.price.id.clone()))
+    }
+
+    fn field_effective_from(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.price.effective_from.to_string())
+    }
+
+    fn field_billing_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.price.billing_amount.clone())
+    }
+}
@@ -0,0 +1,21 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct SupplierSuspension {
+    pub suspension: domain::supplier_suspension::SupplierSuspension,
+}
+#[async_trait]
+impl SupplierSuspensionFields for SupplierSuspension {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.suspension.id.clone()))
+    }
+
+    fn field_from_ym(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.suspension.from_ym.to_string())
+    }
+
+    fn field_to_ym(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.suspension.to_ym.to_string())
+    }
+}
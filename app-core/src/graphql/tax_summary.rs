@@ -0,0 +1,25 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct TaxSummary {
+    pub summary: domain::invoice::TaxSummary,
+}
+#[async_trait]
+impl TaxSummaryFields for TaxSummary {
+    fn field_tax_type(&self, _: &Executor<Context>) -> FieldResult<GraphQLTaxType> {
+        Ok(match self.summary.tax_type {
+            domain::invoice::TaxType::Standard10 => GraphQLTaxType::Standard10,
+            domain::invoice::TaxType::Reduced8 => GraphQLTaxType::Reduced8,
+            domain::invoice::TaxType::NonTaxable => GraphQLTaxType::NonTaxable,
+        })
+    }
+
+    fn field_taxable_amount(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.summary.taxable_amount.clone())
+    }
+
+    fn field_tax(&self, _: &Executor<Context>) -> FieldResult<Money> {
+        Ok(self.summary.tax.clone())
+    }
+}
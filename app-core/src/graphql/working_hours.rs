@@ -0,0 +1,21 @@
+use crate::domain;
+use crate::graphql::*;
+
+#[derive(Debug, Clone)]
+pub struct WorkingHours {
+    pub working_hours: domain::working_hours::WorkingHours,
+}
+#[async_trait]
+impl WorkingHoursFields for WorkingHours {
+    fn field_id(&self, _: &Executor<Context>) -> FieldResult<ID> {
+        Ok(Into::into(self.working_hours.id.clone()))
+    }
+
+    fn field_ym(&self, _: &Executor<Context>) -> FieldResult<String> {
+        Ok(self.working_hours.ym.to_string())
+    }
+
+    fn field_hours(&self, _: &Executor<Context>) -> FieldResult<f64> {
+        Ok(self.working_hours.hours())
+    }
+}
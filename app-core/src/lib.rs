@@ -2,9 +2,9 @@ pub mod ddb;
 pub mod domain;
 pub mod firebase;
 pub mod graphql;
+pub mod mail;
 pub mod misoca;
 pub mod task;
-pub mod util;
 
 #[macro_use]
 extern crate diesel;
@@ -56,12 +56,42 @@ impl From<jsonwebtoken::errors::Error> for CoreError {
     }
 }
 
+impl From<std::io::Error> for CoreError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
 impl From<reqwest::Error> for CoreError {
     fn from(e: reqwest::Error) -> Self {
         Self::Internal(e.to_string())
     }
 }
 
+impl From<cloud_storage::Error> for CoreError {
+    fn from(_e: cloud_storage::Error) -> Self {
+        Self::Internal("GCSでエラーが発生しました".to_string())
+    }
+}
+
+impl From<lettre::error::Error> for CoreError {
+    fn from(e: lettre::error::Error) -> Self {
+        Self::Internal(format!("メールアドレスが不正です: {}", e))
+    }
+}
+
+impl From<lettre::smtp::error::Error> for CoreError {
+    fn from(e: lettre::smtp::error::Error) -> Self {
+        Self::Internal(format!("SMTPエラー: {}", e))
+    }
+}
+
+impl From<native_tls::Error> for CoreError {
+    fn from(e: native_tls::Error) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
 #[derive(StrumDisplay, Debug)]
 pub enum FieldErrorCode {
     BadRequest,
@@ -0,0 +1,323 @@
+use crate::{CoreError, CoreResult};
+use actix_web::error::BlockingError;
+use actix_web::web;
+use chrono::Utc;
+use lettre::smtp::authentication::Credentials;
+use lettre::{
+    ClientSecurity, ClientTlsParameters, EmailAddress, Envelope, SendableEmail, SmtpClient,
+    Transport,
+};
+use native_tls::TlsConnector;
+use std::env;
+use std::time::Duration;
+use uuid::Uuid;
+
+const TIMEOUT_SECS: u64 = 30;
+const LINE_LENGTH: usize = 76;
+/// エンコード後の1語が75文字に収まるよう、エンコード前のバイト数で区切る
+const ENCODED_WORD_BYTES: usize = 45;
+
+/// SMTPでメールを送るクライアント
+#[derive(Clone)]
+pub struct Client {
+    host: String,
+    port: u16,
+    security: Security,
+    /// 空の場合は認証しない
+    username: String,
+    password: String,
+    from: String,
+}
+
+/// SMTPサーバーとの接続の暗号化
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Security {
+    /// 平文で接続してSTARTTLSで暗号化する。STARTTLSに対応していないサーバーには送らない
+    StartTls,
+    /// 最初からTLSで接続する（SMTPS）
+    Tls,
+    /// 暗号化しない。同じネットワーク内のリレーサーバー（開発環境ではMailHog）向けで、認証はできない
+    None,
+}
+
+impl Security {
+    fn default_port(&self) -> u16 {
+        match self {
+            Self::StartTls => 587,
+            Self::Tls => 465,
+            Self::None => 25,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Message {
+    /// 差出人の表示名。アドレスはクライアントの from を使う
+    pub from_name: String,
+    /// 返信先。空の場合は付けない
+    pub reply_to: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    /// ヘッダーには載せず、宛先としてだけ送る
+    pub bcc: Vec<String>,
+    pub subject: String,
+    pub body: String,
+    pub attachments: Vec<Attachment>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Attachment {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl Client {
+    pub fn new(
+        host: String,
+        port: u16,
+        security: Security,
+        username: String,
+        password: String,
+        from: String,
+    ) -> Self {
+        Client {
+            host,
+            port,
+            security,
+            username,
+            password,
+            from,
+        }
+    }
+
+    /// SMTP_HOST が設定されていなければメールを送らない。SMTP_TLS は starttls（省略時）、tls、none のいずれか
+    pub fn from_env() -> Option<Self> {
+        let host = env::var("SMTP_HOST").ok().filter(|v| !v.is_empty())?;
+        let security = match env::var("SMTP_TLS").unwrap_or_default().as_str() {
+            "" | "starttls" => Security::StartTls,
+            "tls" => Security::Tls,
+            "none" => Security::None,
+            v => panic!("SMTP_TLS must be starttls, tls or none: {}", v),
+        };
+        let username = env::var("SMTP_USERNAME").unwrap_or_default();
+        if security == Security::None && !username.is_empty() {
+            panic!("SMTP_USERNAME cannot be used with SMTP_TLS=none");
+        }
+        Some(Client::new(
+            host,
+            env::var("SMTP_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(|| security.default_port()),
+            security,
+            username,
+            env::var("SMTP_PASSWORD").unwrap_or_default(),
+            env::var("SMTP_FROM").expect("SMTP_FROM must be set"),
+        ))
+    }
+
+    pub fn send(&self, message: &Message) -> CoreResult<()> {
+        if message.to.is_empty() {
+            return Err(CoreError::BadRequest("宛先がありません".to_string()));
+        }
+        // アドレスはヘッダーにそのまま書くため、改行で別のヘッダーを差し込めないようにする
+        for address in message
+            .to
+            .iter()
+            .chain(message.cc.iter())
+            .chain(message.bcc.iter())
+            .chain(std::iter::once(&message.reply_to).filter(|v| !v.is_empty()))
+        {
+            if address.contains(|c| c == '\r' || c == '\n') {
+                return Err(CoreError::BadRequest(format!(
+                    "メールアドレスに改行が含まれています: {}",
+                    address.escape_debug()
+                )));
+            }
+        }
+        if !message.reply_to.is_empty() {
+            EmailAddress::new(message.reply_to.clone())?;
+        }
+        println!("send mail: {} {}", message.to.join(","), message.subject);
+
+        let recipients = message
+            .to
+            .iter()
+            .chain(message.cc.iter())
+            .chain(message.bcc.iter())
+            .map(|v| EmailAddress::new(v.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let envelope = Envelope::new(Some(EmailAddress::new(self.from.clone())?), recipients)?;
+        let email = SendableEmail::new(
+            envelope,
+            Uuid::new_v4().to_string(),
+            self.format(message).into_bytes(),
+        );
+
+        // DATAの終わりで250が返れば送信済み。その後のQUITの失敗はlettreが無視する
+        self.transport()?.send(email)?;
+        Ok(())
+    }
+
+    /// 送信はブロックするため、非同期の処理からは別スレッドで送る
+    pub async fn send_async(&self, message: Message) -> CoreResult<()> {
+        let client = self.clone();
+        web::block(move || client.send(&message))
+            .await
+            .map_err(|e| match e {
+                BlockingError::Error(e) => e,
+                BlockingError::Canceled => {
+                    CoreError::Internal("メールの送信が中断されました".to_string())
+                }
+            })
+    }
+
+    fn transport(&self) -> CoreResult<lettre::SmtpTransport> {
+        let tls = || -> CoreResult<ClientTlsParameters> {
+            Ok(ClientTlsParameters::new(
+                self.host.clone(),
+                TlsConnector::new()?,
+            ))
+        };
+        let security = match self.security {
+            Security::StartTls => ClientSecurity::Required(tls()?),
+            Security::Tls => ClientSecurity::Wrapper(tls()?),
+            Security::None => ClientSecurity::None,
+        };
+        let mut client = SmtpClient::new((self.host.as_str(), self.port), security)?
+            .timeout(Some(Duration::from_secs(TIMEOUT_SECS)));
+        if !self.username.is_empty() {
+            client = client.credentials(Credentials::new(
+                self.username.clone(),
+                self.password.clone(),
+            ));
+        }
+        Ok(client.transport())
+    }
+
+    fn format(&self, message: &Message) -> String {
+        let mut headers = vec![
+            format!(
+                "From: {} <{}>",
+                encode_display_name(message.from_name.as_str()),
+                self.from
+            ),
+            format!("To: {}", message.to.join(", ")),
+            format!("Subject: {}", encode_header(message.subject.as_str())),
+            format!("Date: {}", Utc::now().to_rfc2822()),
+            format!("Message-ID: <{}@{}>", Uuid::new_v4(), domain_of(&self.from)),
+            "MIME-Version: 1.0".to_string(),
+        ];
+        if !message.cc.is_empty() {
+            headers.push(format!("Cc: {}", message.cc.join(", ")));
+        }
+        if !message.reply_to.is_empty() {
+            headers.push(format!("Reply-To: {}", message.reply_to));
+        }
+
+        let text = text_part(message.body.as_str());
+        if message.attachments.is_empty() {
+            return format!("{}\r\n{}", headers.join("\r\n"), text);
+        }
+
+        let boundary = format!("----=_Part_{}", Uuid::new_v4().to_simple());
+        headers.push(format!(
+            "Content-Type: multipart/mixed; boundary=\"{}\"",
+            boundary
+        ));
+        let parts = std::iter::once(text)
+            .chain(message.attachments.iter().map(attachment_part))
+            .map(|v| format!("--{}\r\n{}", boundary, v))
+            .collect::<Vec<_>>();
+
+        format!(
+            "{}\r\n\r\n{}\r\n--{}--",
+            headers.join("\r\n"),
+            parts.join("\r\n"),
+            boundary
+        )
+    }
+}
+
+/// 本文。ヘッダーの終わりの空行を含む
+fn text_part(body: &str) -> String {
+    format!(
+        "Content-Type: text/plain; charset=UTF-8\r\nContent-Transfer-Encoding: base64\r\n\r\n{}",
+        wrap(&base64::encode(body.replace('\n', "\r\n")))
+    )
+}
+
+/// 日本語のファイル名は RFC 2231 の形式と、古いメールソフト向けのBエンコードの両方で付ける
+fn attachment_part(attachment: &Attachment) -> String {
+    format!(
+        "Content-Type: {}; name=\"{}\"\r\nContent-Transfer-Encoding: base64\r\nContent-Disposition: attachment; filename*=UTF-8''{}\r\n\r\n{}",
+        attachment.content_type,
+        encode_header(attachment.filename.as_str()),
+        percent_encode(attachment.filename.as_str()),
+        wrap(&base64::encode(&attachment.data))
+    )
+}
+
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 差出人の表示名。ASCIIのみで区切り文字などを含む場合は引用符で囲む
+fn encode_display_name(s: &str) -> String {
+    let s = strip_newlines(s);
+    if !s.is_ascii() || !s.contains(|c| "()<>[]:;@\\,.\"".contains(c)) {
+        return encode_header(s.as_str());
+    }
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// ヘッダーの値に改行があると別のヘッダーを差し込めるため、空白に置き換える
+fn strip_newlines(s: &str) -> String {
+    s.replace("\r\n", " ")
+        .replace(|c| c == '\r' || c == '\n', " ")
+}
+
+/// ASCII以外を含むヘッダーはUTF-8のBエンコードにする
+fn encode_header(s: &str) -> String {
+    let s = strip_newlines(s);
+    if s.is_ascii() {
+        return s;
+    }
+
+    let mut words = vec![];
+    let mut chunk = String::new();
+    for c in s.chars() {
+        if chunk.len() + c.len_utf8() > ENCODED_WORD_BYTES {
+            words.push(chunk.clone());
+            chunk.clear();
+        }
+        chunk.push(c);
+    }
+    words.push(chunk);
+
+    words
+        .iter()
+        .map(|v| format!("=?UTF-8?B?{}?=", base64::encode(v)))
+        .collect::<Vec<_>>()
+        .join("\r\n ")
+}
+
+fn wrap(s: &str) -> String {
+    s.as_bytes()
+        .chunks(LINE_LENGTH)
+        .map(|v| String::from_utf8_lossy(v).to_string())
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+fn domain_of(address: &str) -> &str {
+    address.rsplit('@').next().unwrap_or("localhost")
+}
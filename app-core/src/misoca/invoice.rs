@@ -1,7 +1,6 @@
 use crate::domain;
 use crate::domain::YMD;
 use crate::misoca::{CallInput, Client};
-use crate::util;
 use crate::{CoreError, CoreResult};
 use actix_web::web::Bytes;
 use reqwest::{Method, Response};
@@ -9,10 +8,11 @@ use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 impl Client {
+    /// 金額は請求書の通貨で読む必要があるため、変換せずに返す
     pub async fn get_invoices(
         &self,
         input: get_invoices::Input,
-    ) -> CoreResult<Vec<domain::invoice::Invoice>> {
+    ) -> CoreResult<get_invoices::Output> {
         #[derive(Debug, Serialize)]
         struct Body {}
 
@@ -47,11 +47,6 @@ impl Client {
         .json::<get_invoices::Output>()
         .await
         .map_err(CoreError::from)
-        .map(|item| {
-            item.into_iter()
-                .map(|v| v.to_domain(input.supplier_id.clone()).unwrap())
-                .collect::<Vec<_>>()
-        })
     }
 
     pub async fn get_pdf(&self, input: get_pdf::Input) -> CoreResult<get_pdf::Output> {
@@ -76,7 +71,9 @@ impl Client {
         struct ItemBody {
             pub name: String,
             pub quantity: i32,
-            pub unit_price: i32,
+            pub unit: String,
+            /// 端数が丸められないよう通貨単位の10進数の文字列で送る
+            pub unit_price: String,
             pub tax_type: String,
             pub excluding_withholding_tax: bool,
         }
@@ -88,7 +85,9 @@ impl Client {
             pub sender_address1: String,
             pub sender_tel: String,
             pub sender_email: String,
+            pub sender_registration_number: String,
             pub bank_accounts: Vec<Bank>,
+            pub notes: String,
         }
 
         #[derive(Debug, Serialize)]
@@ -102,6 +101,10 @@ impl Client {
             pub subject: String,
             pub payment_due_on: String,
             pub contact_id: i32,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub invoice_number: Option<String>,
+            pub tax_option: String,
+            pub tax_rounding_policy: String,
             pub items: Vec<ItemBody>,
             pub body: Sender,
         }
@@ -127,7 +130,9 @@ impl Client {
                 sender_address1: sender.address,
                 sender_tel: sender.tel,
                 sender_email: sender.email,
+                sender_registration_number: sender.registration_number,
                 bank_accounts: banks,
+                notes: input.remarks.clone(),
             }
         } else {
             Sender {
@@ -136,7 +141,9 @@ impl Client {
                 sender_address1: "".to_string(),
                 sender_tel: "".to_string(),
                 sender_email: "".to_string(),
+                sender_registration_number: "".to_string(),
                 bank_accounts: banks,
+                notes: input.remarks.clone(),
             }
         };
 
@@ -144,14 +151,29 @@ impl Client {
             issue_date: input.issue_date.clone(),
             subject: input.subject.clone(),
             payment_due_on: input.payment_due_on.clone(),
-            contact_id: input.contact_id.parse().unwrap(),
-            items: vec![ItemBody {
-                name: input.subject.clone(),
-                quantity: 1,
-                unit_price: input.billing_amount.clone(),
-                tax_type: "STANDARD_TAX_10".to_string(),
-                excluding_withholding_tax: false,
-            }],
+            contact_id: input
+                .contact_id
+                .parse()
+                .map_err(|_e| CoreError::Internal("cannot parse contact_id".to_string()))?,
+            invoice_number: input.invoice_number.clone(),
+            tax_option: if input.tax_setting.inclusive {
+                "INCLUDE".to_string()
+            } else {
+                "EXCLUDE".to_string()
+            },
+            tax_rounding_policy: tax_rounding_policy(&input.tax_setting.rounding),
+            items: input
+                .items
+                .iter()
+                .map(|v| ItemBody {
+                    name: v.name.clone(),
+                    quantity: v.quantity,
+                    unit: v.unit.clone(),
+                    unit_price: v.unit_price.to_major_string(&input.currency),
+                    tax_type: tax_type(&v.tax_type),
+                    excluding_withholding_tax: !v.withholding,
+                })
+                .collect::<Vec<_>>(),
             body: sender,
         };
 
@@ -176,8 +198,31 @@ impl Client {
         .error_for_status()?
         .json::<create_invoice::Output>()
         .await
-        .map_err(CoreError::from)
-        .map(|item| item.to_domain(input.supplier_id.clone()).unwrap())
+        .map_err(CoreError::from)?
+        .to_domain(input.supplier_id.clone(), input.currency.clone())
+        .map(|mut invoice| {
+            invoice.remarks = input.remarks.clone();
+            if let Some(invoice_number) = input.invoice_number.clone() {
+                invoice.invoice_number = invoice_number;
+            }
+            invoice
+        })
+    }
+}
+
+fn tax_type(v: &domain::invoice::TaxType) -> String {
+    match v {
+        domain::invoice::TaxType::Standard10 => "STANDARD_TAX_10".to_string(),
+        domain::invoice::TaxType::Reduced8 => "REDUCED_TAX_8".to_string(),
+        domain::invoice::TaxType::NonTaxable => "NO_TAX".to_string(),
+    }
+}
+
+fn tax_rounding_policy(v: &domain::invoice::TaxRounding) -> String {
+    match v {
+        domain::invoice::TaxRounding::Floor => "FLOOR".to_string(),
+        domain::invoice::TaxRounding::Round => "ROUND".to_string(),
+        domain::invoice::TaxRounding::Ceil => "CEIL".to_string(),
     }
 }
 
@@ -188,7 +233,6 @@ pub mod get_invoices {
         pub access_token: String,
         pub page: i32,
         pub per_page: i32,
-        pub supplier_id: String,
         pub contact_group_id: String,
     }
 
@@ -217,7 +261,13 @@ pub mod create_invoice {
         pub subject: String,
         pub issue_date: String,
         pub payment_due_on: String,
-        pub billing_amount: i32,
+        /// Noneの場合はmisocaで採番する
+        pub invoice_number: Option<String>,
+        /// 明細の金額はこの通貨の最小単位で渡し、misocaには通貨単位で送る
+        pub currency: domain::currency::Currency,
+        pub items: Vec<domain::invoice::InvoiceItem>,
+        pub remarks: String,
+        pub tax_setting: domain::invoice::TaxSetting,
         pub bank: Option<domain::bank::Bank>,
         pub sender: Option<domain::sender::Sender>,
         pub now: DateTime<Utc>,
@@ -245,19 +295,43 @@ pub struct Invoice {
 pub struct InvoiceBody {
     pub total_amount: Option<String>,
     pub tax: Option<String>,
+    pub notes: Option<String>,
 }
 
 impl Invoice {
-    fn to_domain(&self, supplier_id: String) -> CoreResult<domain::invoice::Invoice> {
-        let body = self.body.as_ref().unwrap();
+    pub fn invoice_id(&self) -> CoreResult<String> {
+        self.id
+            .map(|v| v.to_string())
+            .ok_or(CoreError::Internal("id is missing".to_string()))
+    }
 
-        let total_amount: f64 = body
-            .total_amount
-            .clone()
-            .unwrap_or("0".to_string())
-            .parse()
-            .unwrap();
-        let tax: f64 = body.tax.clone().unwrap_or("0".to_string()).parse().unwrap();
+    /// 金額はcurrencyの通貨単位の文字列として読む
+    pub fn to_domain(
+        &self,
+        supplier_id: String,
+        currency: domain::currency::Currency,
+    ) -> CoreResult<domain::invoice::Invoice> {
+        let id = self
+            .id
+            .ok_or(CoreError::Internal("id is missing".to_string()))?;
+        let body = self
+            .body
+            .as_ref()
+            .ok_or(CoreError::Internal("body is missing".to_string()))?;
+
+        let total_amount = domain::money::Money::from_major(
+            body.total_amount
+                .clone()
+                .unwrap_or("0".to_string())
+                .as_str(),
+            &currency,
+        )
+        .map_err(|e| CoreError::Internal(format!("cannot parse total_amount: {}", e)))?;
+        let tax = domain::money::Money::from_major(
+            body.tax.clone().unwrap_or("0".to_string()).as_str(),
+            &currency,
+        )
+        .map_err(|e| CoreError::Internal(format!("cannot parse tax: {}", e)))?;
 
         let issue_ymd = YMD::from_str(self.issue_date.clone().unwrap_or("".to_string()).as_str())
             .map_err(|_e| CoreError::Internal("cannot parse issue_date".to_string()))?;
@@ -269,15 +343,17 @@ impl Invoice {
         )
         .map_err(|_e| CoreError::Internal("cannot parse payment_due_on".to_string()))?;
 
-        let created_at =
-            chrono::DateTime::parse_from_rfc3339(self.created_at.clone().unwrap().as_str())
-                .map_err(|_e| CoreError::Internal("cannot parse created_at".to_string()))?;
-        let updated_at =
-            chrono::DateTime::parse_from_rfc3339(self.updated_at.clone().unwrap().as_str())
-                .map_err(|_e| CoreError::Internal("cannot parse updated_at".to_string()))?;
+        let created_at = chrono::DateTime::parse_from_rfc3339(
+            self.created_at.clone().unwrap_or("".to_string()).as_str(),
+        )
+        .map_err(|_e| CoreError::Internal("cannot parse created_at".to_string()))?;
+        let updated_at = chrono::DateTime::parse_from_rfc3339(
+            self.updated_at.clone().unwrap_or("".to_string()).as_str(),
+        )
+        .map_err(|_e| CoreError::Internal("cannot parse updated_at".to_string()))?;
 
         Ok(domain::invoice::Invoice {
-            id: String::from(self.id.unwrap().to_string()),
+            id: id.to_string(),
             supplier_id,
             issue_ymd,
             payment_due_on_ymd,
@@ -285,14 +361,31 @@ impl Invoice {
             payment_status: domain::invoice::PaymentStatus::from(
                 self.payment_status.clone().unwrap_or(0),
             ),
-            invoice_status: domain::invoice::InvoiceStatus::from(
-                self.invoice_status.clone().unwrap_or(0),
+            remote_payment_status: domain::invoice::PaymentStatus::from(
+                self.payment_status.clone().unwrap_or(0),
             ),
+            // 取消はこちらでのみ管理するため、misocaの値は送付済みかどうかだけを見る
+            invoice_status: match self.invoice_status {
+                Some(1) => domain::invoice::InvoiceStatus::Submitted,
+                _ => domain::invoice::InvoiceStatus::UnSubmitted,
+            },
             recipient_name: self.recipient_name.clone().unwrap_or("".to_string()),
             subject: self.subject.clone().unwrap_or("".to_string()),
-            total_amount: util::f64_to_i32(total_amount),
-            tax: util::f64_to_i32(tax),
+            billing_ym: domain::YM { year: 0, month: 0 },
+            currency,
+            total_amount: total_amount.clone(),
+            tax,
+            withholding_tax: domain::money::Money::zero(),
+            payment_amount: total_amount,
+            paid_amount: domain::money::Money::zero(),
+            tax_summaries: vec![],
+            remarks: body.notes.clone().unwrap_or("".to_string()),
             pdf_path: None,
+            overdue_at: None,
+            cancelled_at: None,
+            cancel_reason: "".to_string(),
+            credit_note_id: None,
+            original_invoice_id: None,
             created_at: created_at.naive_utc(),
             updated_at: updated_at.naive_utc(),
         })
@@ -1,3 +1,8 @@
+pub mod allocate_invoice_number;
 pub mod create_invoice;
+pub mod detect_overdue;
 pub mod get_misoca_token;
+pub mod import_exchange_rates;
+pub mod send_invoice;
+pub mod send_reminders;
 pub mod sync_invoice;
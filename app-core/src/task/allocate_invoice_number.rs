@@ -0,0 +1,71 @@
+use crate::ddb;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use chrono::{DateTime, Utc};
+use diesel::MysqlConnection;
+use std::str::FromStr;
+
+/// ユーザーの書式で請求書番号を採番する。書式が未設定の場合はmisocaの採番に任せるためNoneを返す。
+/// 請求書の登録とは別のトランザクションで呼び出し、misocaで発行できなかった場合は `void` で欠番として記録する
+pub fn exec(
+    conn: &MysqlConnection,
+    user: &domain::user::User,
+    supplier: &domain::supplier::Supplier,
+    issue_date: &str,
+    now: DateTime<Utc>,
+) -> CoreResult<Option<String>> {
+    let sequence_dao: ddb::Dao<domain::invoice_number::InvoiceNumberSequence> = ddb::Dao::new();
+
+    let format =
+        domain::invoice_number::InvoiceNumberFormat::from_str(user.invoice_number_format.as_str())
+            .map_err(CoreError::Internal)?;
+    if format.is_empty() {
+        return Ok(None);
+    }
+
+    let issue_ymd = domain::YMD::from_str(issue_date).map_err(CoreError::Internal)?;
+    let seq = sequence_dao.next(conn, user.id.clone(), issue_ymd.year, now)?;
+
+    Ok(Some(format.render(
+        &issue_ymd,
+        supplier.invoice_number_prefix.as_str(),
+        seq,
+    )))
+}
+
+/// 採番した番号でmisocaが請求書を発行しなかった場合に、番号を再利用せず欠番として記録する
+pub fn void(
+    conn: &MysqlConnection,
+    user: &domain::user::User,
+    invoice_number: Option<String>,
+    reason: &CoreError,
+    now: DateTime<Utc>,
+) -> CoreResult<()> {
+    let voided_dao: ddb::Dao<domain::invoice_number::VoidedInvoiceNumber> = ddb::Dao::new();
+
+    let invoice_number = match invoice_number {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+    println!("請求書番号[{}]を欠番にします: {}", invoice_number, reason);
+    voided_dao.insert(
+        conn,
+        &domain::invoice_number::VoidedInvoiceNumber::new(
+            user.id.clone(),
+            invoice_number,
+            reason.to_string(),
+            now,
+        ),
+    )
+}
+
+/// misocaで発行した請求書を登録できなかった場合のエラー。番号はmisocaで使われているため欠番にせず、
+/// misocaと突き合わせられるよう請求書のIDと番号を残す
+pub fn unrecorded(invoice: &domain::invoice::Invoice, reason: CoreError) -> CoreError {
+    let message = format!(
+        "misocaで発行した請求書[{}]（請求書番号: {}）を登録できませんでした。misocaと突き合わせてください: {}",
+        invoice.id, invoice.invoice_number, reason
+    );
+    println!("{}", message);
+    CoreError::Internal(message)
+}
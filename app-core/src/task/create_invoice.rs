@@ -1,17 +1,34 @@
 use crate::ddb;
+use crate::ddb::Tx;
 use crate::domain;
+use crate::mail;
 use crate::misoca;
+use crate::task::allocate_invoice_number;
 use crate::task::get_misoca_token;
+use crate::task::send_invoice;
 use crate::{CoreError, CoreResult};
-use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use chrono::{DateTime, Utc};
+use diesel::MysqlConnection;
 use std::sync::Mutex;
 
-pub async fn exec(misoca_cli: misoca::Client, now: DateTime<Utc>) -> CoreResult<()> {
+pub async fn exec(
+    misoca_cli: misoca::Client,
+    mail_cli: Option<mail::Client>,
+    now: DateTime<Utc>,
+) -> CoreResult<()> {
     let conn = ddb::establish_connection();
     let user_dao: ddb::Dao<domain::user::User> = ddb::Dao::new();
     let invoice_dao: ddb::Dao<domain::invoice::Invoice> = ddb::Dao::new();
     let bank_dao: ddb::Dao<domain::bank::Bank> = ddb::Dao::new();
     let sender_dao: ddb::Dao<domain::sender::Sender> = ddb::Dao::new();
+    let working_hours_dao: ddb::Dao<domain::working_hours::WorkingHours> = ddb::Dao::new();
+    let retainer_usage_dao: ddb::Dao<domain::retainer_usage::RetainerUsage> = ddb::Dao::new();
+    let supplier_item_dao: ddb::Dao<domain::supplier_item::SupplierItem> = ddb::Dao::new();
+    let supplier_price_dao: ddb::Dao<domain::supplier_price::SupplierPrice> = ddb::Dao::new();
+    let supplier_milestone_dao: ddb::Dao<domain::supplier_milestone::SupplierMilestone> =
+        ddb::Dao::new();
+    let supplier_suspension_dao: ddb::Dao<domain::supplier_suspension::SupplierSuspension> =
+        ddb::Dao::new();
 
     let users = user_dao
         .get_all_with_suppliers(&conn)
@@ -33,21 +50,58 @@ pub async fn exec(misoca_cli: misoca::Client, now: DateTime<Utc>) -> CoreResult<
         let banks = bank_dao.get_all_by_user(&conn, only_user.id.clone())?;
         let senders = sender_dao.get_all_by_user(&conn, only_user.id.clone())?;
 
+        let sender = match senders.first() {
+            Some(sender) => sender.clone(),
+            None => {
+                println!("ユーザー[{}]: 差出人が登録されていません", only_user.id);
+                continue;
+            }
+        };
+        if let Err(e) = sender.validate_for_qualified_invoice() {
+            println!("ユーザー[{}]: {}", only_user.id, e);
+            continue;
+        }
+
+        let issuer = Issuer {
+            conn: &conn,
+            misoca_cli: &misoca_cli,
+            mail_cli: mail_cli.as_ref(),
+            access_token,
+            user: &only_user,
+            bank: banks.first().cloned(),
+            sender: &sender,
+            now,
+        };
+
         for supplier in suppliers
             .iter()
-            .filter(|v| v.billing_type == domain::supplier::BillingType::Monthly)
+            .filter(|v| {
+                v.billing_type == domain::supplier::BillingType::Monthly
+                    || v.billing_type == domain::supplier::BillingType::Hourly
+                    || v.billing_type == domain::supplier::BillingType::Retainer
+            })
+            .filter(|v| v.is_issue_due(now))
             .collect::<Vec<_>>()
         {
-            let subject = supplier.subject_in_this_month(now).clone();
-            let (issue_date, payment_due_on) = supplier.payment_date_in_this_month(now);
+            let suspensions =
+                supplier_suspension_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
+            if !supplier.is_billing_month(now, &suspensions) {
+                continue;
+            }
+
+            // 明細の検証が済むまで採番しないよう、請求書番号を入れない件名で明細を作っておく
+            let draft_subject = supplier.subject_in_this_month(now, "");
+            let period = supplier.billing_period(now);
 
             println!("月々の請求");
             println!("請求先: {}", supplier.name.clone());
-            println!("発行日: {}", issue_date);
-            println!("支払い期日: {}", payment_due_on);
 
-            let exist =
-                invoice_dao.exist_by_subject(&conn, supplier.id.clone(), subject.clone())?;
+            let exist = invoice_dao.exist_by_period(
+                &conn,
+                supplier.id.clone(),
+                &period.ym(),
+                draft_subject.clone(),
+            )?;
             if exist {
                 println!(
                     "請求先[{}]の請求書はすでに存在します",
@@ -56,51 +110,91 @@ pub async fn exec(misoca_cli: misoca::Client, now: DateTime<Utc>) -> CoreResult<
                 continue;
             }
 
-            let invoice = misoca_cli
-                .create_invoice(misoca::invoice::create_invoice::Input {
-                    access_token: access_token.clone(),
-                    supplier_id: supplier.id.clone(),
-                    contact_id: supplier.contact_id.clone(),
-                    subject,
-                    issue_date,
-                    payment_due_on,
-                    billing_amount: supplier.billing_amount.clone(),
-                    bank: banks.first().cloned(),
-                    sender: senders.first().cloned(),
-                    now,
-                })
-                .await?;
+            let working_hours = match working_hours_dao.get_by_supplier_and_ym(
+                &conn,
+                supplier.id.clone(),
+                &period.ym(),
+            ) {
+                Ok(v) => Some(v),
+                Err(CoreError::NotFound) => None,
+                Err(e) => return Err(e),
+            };
 
-            invoice_dao.insert(&conn, &invoice)?;
-        }
+            let usage = match retainer_usage_dao.get_by_supplier_and_ym(
+                &conn,
+                supplier.id.clone(),
+                &period.ym(),
+            ) {
+                Ok(v) => Some(v),
+                Err(CoreError::NotFound) => None,
+                Err(e) => return Err(e),
+            };
 
-        let first_day_in_last_month = NaiveDate::from_ymd(now.year(), now.month() - 1, 1);
-        let last_month = domain::YM {
-            year: first_day_in_last_month.year() as u32,
-            month: first_day_in_last_month.month(),
-        };
+            let prices = supplier_price_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
+            let supplier = supplier.with_price_at(&period.ym(), &prices);
+
+            let supplier_items =
+                supplier_item_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
 
-        println!("先月: {:?}", last_month);
+            let items = match supplier.invoice_items(
+                draft_subject.clone(),
+                supplier_items,
+                working_hours,
+                usage,
+                now,
+            ) {
+                Ok(items) => items,
+                Err(e) => {
+                    println!("請求先[{}]: {}", supplier.name.clone(), e);
+                    continue;
+                }
+            };
+
+            issuer
+                .issue(
+                    &supplier,
+                    Some(period.ym()),
+                    draft_subject,
+                    items,
+                    |number| {
+                        (
+                            supplier.subject_in_this_month(now, number),
+                            supplier.remarks_at(
+                                &period.ym(),
+                                sender.remarks_template.as_str(),
+                                number,
+                            ),
+                        )
+                    },
+                    |_, _| Ok(()),
+                )
+                .await?;
+        }
 
         for supplier in suppliers
             .iter()
             .filter(|v| v.billing_type == domain::supplier::BillingType::OneTime)
+            .filter(|v| !v.archived)
+            .filter(|v| v.is_issue_due(now))
             .collect::<Vec<_>>()
         {
-            if supplier.end_ym != last_month {
+            let period = supplier.billing_period(now);
+            if supplier.end_ym != period.ym() {
                 continue;
             }
 
-            let subject = supplier.subject_in_this_month(now).clone();
-            let (issue_date, payment_due_on) = supplier.payment_date_in_this_month(now);
+            // 明細の検証が済むまで採番しないよう、請求書番号を入れない件名で明細を作っておく
+            let draft_subject = supplier.subject_in_this_month(now, "");
 
             println!("1回のみの請求");
             println!("請求先: {}", supplier.name.clone());
-            println!("発行日: {}", issue_date);
-            println!("支払い期日: {}", payment_due_on);
 
-            let exist =
-                invoice_dao.exist_by_subject(&conn, supplier.id.clone(), subject.clone())?;
+            let exist = invoice_dao.exist_by_period(
+                &conn,
+                supplier.id.clone(),
+                &period.ym(),
+                draft_subject.clone(),
+            )?;
             if exist {
                 println!(
                     "請求先[{}]の請求書はすでに存在します",
@@ -109,24 +203,315 @@ pub async fn exec(misoca_cli: misoca::Client, now: DateTime<Utc>) -> CoreResult<
                 continue;
             }
 
-            let invoice = misoca_cli
-                .create_invoice(misoca::invoice::create_invoice::Input {
-                    access_token: access_token.clone(),
-                    supplier_id: supplier.id.clone(),
-                    contact_id: supplier.contact_id.clone(),
-                    subject,
-                    issue_date,
-                    payment_due_on,
-                    billing_amount: supplier.billing_amount.clone(),
-                    bank: banks.first().cloned(),
-                    sender: senders.first().cloned(),
-                    now,
-                })
+            let prices = supplier_price_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
+            let supplier = supplier.with_price_at(&period.ym(), &prices);
+
+            let supplier_items =
+                supplier_item_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
+
+            let items = match supplier.invoice_items(
+                draft_subject.clone(),
+                supplier_items,
+                None,
+                None,
+                now,
+            ) {
+                Ok(items) => items,
+                Err(e) => {
+                    println!("請求先[{}]: {}", supplier.name.clone(), e);
+                    continue;
+                }
+            };
+
+            issuer
+                .issue(
+                    &supplier,
+                    Some(period.ym()),
+                    draft_subject,
+                    items,
+                    |number| {
+                        (
+                            supplier.subject_in_this_month(now, number),
+                            supplier.remarks_at(
+                                &period.ym(),
+                                sender.remarks_template.as_str(),
+                                number,
+                            ),
+                        )
+                    },
+                    |_, _| Ok(()),
+                )
                 .await?;
+        }
+
+        for supplier in suppliers
+            .iter()
+            .filter(|v| v.billing_type == domain::supplier::BillingType::Milestone)
+            .filter(|v| !v.archived)
+            .filter(|v| v.is_issue_due(now))
+            .collect::<Vec<_>>()
+        {
+            let period = supplier.billing_period(now);
+            let milestones =
+                supplier_milestone_dao.get_all_by_supplier(&conn, supplier.id.clone())?;
+
+            // 請求済みのマイルストーンは請求書のIDを持つので、件名が同じでも別のマイルストーンとして請求する
+            for milestone in milestones.iter().filter(|v| v.is_due(&period.ym())) {
+                let draft_subject = supplier.subject_for_milestone(milestone, &period, "");
 
-            invoice_dao.insert(&conn, &invoice)?;
+                println!("マイルストーンの請求");
+                println!("請求先: {}", supplier.name.clone());
+                println!("マイルストーン: {}", milestone.name.clone());
+
+                let items = supplier.milestone_items(draft_subject.clone(), milestone);
+
+                issuer
+                    .issue(
+                        supplier,
+                        None,
+                        draft_subject,
+                        items,
+                        |number| {
+                            (
+                                supplier.subject_for_milestone(milestone, &period, number),
+                                supplier.remarks_for_milestone(
+                                    milestone,
+                                    &period,
+                                    sender.remarks_template.as_str(),
+                                    number,
+                                ),
+                            )
+                        },
+                        |conn, invoice| {
+                            // 画面から同時に請求されていないか、行をロックして確かめ直す
+                            let mut milestone = supplier_milestone_dao
+                                .get_for_update(conn, milestone.id.clone())?;
+                            if milestone.is_billed() {
+                                return Err(CoreError::BadRequest(
+                                    "このマイルストーンは請求済みです".to_string(),
+                                ));
+                            }
+                            milestone.mark_billed(invoice.id.clone(), now);
+                            supplier_milestone_dao.update(conn, &milestone)
+                        },
+                    )
+                    .await?;
+            }
         }
     }
 
     Ok(())
 }
+
+/// ユーザーごとに共通する、請求書の発行に使うもの
+struct Issuer<'a> {
+    conn: &'a MysqlConnection,
+    misoca_cli: &'a misoca::Client,
+    mail_cli: Option<&'a mail::Client>,
+    access_token: String,
+    user: &'a domain::user::User,
+    bank: Option<domain::bank::Bank>,
+    sender: &'a domain::sender::Sender,
+    now: DateTime<Utc>,
+}
+
+impl Issuer<'_> {
+    /// 明細に未請求の調整と立替金を加えて検証し、採番してmisocaで発行する。明細が検証を通らない請求先は発行せずに読み飛ばす。
+    /// render は採番した番号から件名と備考を作り、extra_writes は登録した請求書に紐づけて記録するものを書き込む
+    async fn issue<R, W>(
+        &self,
+        supplier: &domain::supplier::Supplier,
+        billing_ym: Option<domain::YM>,
+        draft_subject: String,
+        mut items: Vec<domain::invoice::InvoiceItem>,
+        render: R,
+        extra_writes: W,
+    ) -> CoreResult<()>
+    where
+        R: FnOnce(&str) -> (String, String),
+        W: FnOnce(&MysqlConnection, &domain::invoice::Invoice) -> CoreResult<()>,
+    {
+        let conn = self.conn;
+        let now = self.now;
+        let invoice_dao: ddb::Dao<domain::invoice::Invoice> = ddb::Dao::new();
+
+        let (issue_date, payment_due_on) = supplier.payment_date_in_this_month(now);
+        println!("発行日: {}", issue_date);
+        println!("支払い期日: {}", payment_due_on);
+
+        let mut extras =
+            attach_pending_extras(conn, supplier, &supplier.billing_period(now), &mut items)?;
+        if let Err(e) = domain::invoice::validate_items(&items) {
+            println!("請求先[{}]: {}", supplier.name.clone(), e);
+            return Ok(());
+        }
+
+        let withholding_tax = domain::invoice::withholding_tax(&items, &supplier.currency);
+        let tax_summaries = supplier.tax_setting.summaries(&items);
+
+        let invoice_number = Tx::run(conn, || {
+            allocate_invoice_number::exec(conn, self.user, supplier, issue_date.as_str(), now)
+        })?;
+        let number = invoice_number.clone().unwrap_or_default();
+        let (subject, remarks) = render(number.as_str());
+        apply_subject(&mut items, draft_subject.as_str(), subject.as_str());
+
+        let mut invoice = match self
+            .misoca_cli
+            .create_invoice(misoca::invoice::create_invoice::Input {
+                access_token: self.access_token.clone(),
+                supplier_id: supplier.id.clone(),
+                contact_id: supplier.contact_id.clone(),
+                subject,
+                issue_date,
+                payment_due_on,
+                invoice_number: invoice_number.clone(),
+                currency: supplier.currency.clone(),
+                items,
+                remarks,
+                tax_setting: supplier.tax_setting.clone(),
+                bank: self.bank.clone(),
+                sender: Some(self.sender.clone()),
+                now,
+            })
+            .await
+        {
+            Ok(invoice) => invoice,
+            Err(e) => {
+                allocate_invoice_number::void(conn, self.user, invoice_number, &e, now)?;
+                return Err(e);
+            }
+        };
+        invoice.apply_withholding_tax(withholding_tax);
+        invoice.apply_tax_summaries(tax_summaries);
+        if let Some(billing_ym) = billing_ym {
+            invoice.apply_billing_ym(billing_ym);
+        }
+
+        // misocaの呼び出し中はトランザクションを持たず、発行できてから登録する。登録に失敗しても番号はmisocaで使われているため欠番にしない
+        Tx::run(conn, || {
+            invoice_dao.insert(conn, &invoice)?;
+            extra_writes(conn, &invoice)?;
+            extras.mark_billed(conn, &invoice, now)
+        })
+        .map_err(|e| allocate_invoice_number::unrecorded(&invoice, e))?;
+
+        auto_send(
+            conn,
+            self.misoca_cli,
+            self.mail_cli,
+            self.access_token.clone(),
+            supplier,
+            invoice,
+            now,
+        )
+        .await;
+        Ok(())
+    }
+}
+
+/// 請求書番号を入れて描画し直した件名を、件名をそのまま使った明細行にも反映する
+fn apply_subject(items: &mut [domain::invoice::InvoiceItem], draft_subject: &str, subject: &str) {
+    for item in items.iter_mut().filter(|v| v.name == draft_subject) {
+        item.name = subject.to_string();
+    }
+}
+
+/// 請求書に載せる未請求の調整と立替金
+struct PendingExtras {
+    adjustments: Vec<domain::supplier_adjustment::SupplierAdjustment>,
+    expenses: Vec<domain::expense::Expense>,
+}
+
+impl PendingExtras {
+    /// 発行した請求書に載せたものとして記録する
+    fn mark_billed(
+        &mut self,
+        conn: &MysqlConnection,
+        invoice: &domain::invoice::Invoice,
+        now: DateTime<Utc>,
+    ) -> CoreResult<()> {
+        let supplier_adjustment_dao: ddb::Dao<domain::supplier_adjustment::SupplierAdjustment> =
+            ddb::Dao::new();
+        let expense_dao: ddb::Dao<domain::expense::Expense> = ddb::Dao::new();
+
+        for adjustment in self.adjustments.iter_mut() {
+            adjustment.mark_applied(invoice.id.clone(), now);
+            supplier_adjustment_dao.update(conn, adjustment)?;
+        }
+        for expense in self.expenses.iter_mut() {
+            expense.mark_billed(invoice.id.clone(), now);
+            expense_dao.update(conn, expense)?;
+        }
+        Ok(())
+    }
+}
+
+/// 請求期間までに発生した未請求の調整と立替金を明細に加える。期間後の立替金や翌月以降の調整は次の請求に回す
+fn attach_pending_extras(
+    conn: &MysqlConnection,
+    supplier: &domain::supplier::Supplier,
+    period: &domain::supplier::BillingPeriod,
+    items: &mut Vec<domain::invoice::InvoiceItem>,
+) -> CoreResult<PendingExtras> {
+    let supplier_adjustment_dao: ddb::Dao<domain::supplier_adjustment::SupplierAdjustment> =
+        ddb::Dao::new();
+    let expense_dao: ddb::Dao<domain::expense::Expense> = ddb::Dao::new();
+
+    let adjustments =
+        supplier_adjustment_dao.get_pending_by_supplier(conn, supplier.id.clone(), &period.ym())?;
+    items.extend(
+        adjustments
+            .iter()
+            .map(|v| v.invoice_item(supplier.withholding_tax)),
+    );
+
+    let expenses = expense_dao.get_pending_by_supplier(
+        conn,
+        supplier.id.clone(),
+        &domain::YMD::from(period.end),
+    )?;
+    items.extend(expenses.iter().map(|v| v.invoice_item()));
+
+    Ok(PendingExtras {
+        adjustments,
+        expenses,
+    })
+}
+
+/// 請求先で自動送付が有効なら、発行した請求書をメールで送る。送付に失敗しても発行は取り消さず、画面から送り直せるようにする
+async fn auto_send(
+    conn: &MysqlConnection,
+    misoca_cli: &misoca::Client,
+    mail_cli: Option<&mail::Client>,
+    access_token: String,
+    supplier: &domain::supplier::Supplier,
+    invoice: domain::invoice::Invoice,
+    now: DateTime<Utc>,
+) {
+    if !supplier.delivery.auto_send {
+        return;
+    }
+    let mail_cli = match mail_cli {
+        Some(mail_cli) => mail_cli,
+        None => {
+            println!("メールの送信が設定されていないため、請求書を送付しません");
+            return;
+        }
+    };
+
+    match send_invoice::exec(
+        conn,
+        misoca_cli,
+        mail_cli,
+        access_token,
+        supplier,
+        invoice,
+        now,
+    )
+    .await
+    {
+        Ok((invoice, _)) => println!("請求書[{}]を送付しました", invoice.id),
+        Err(e) => println!("請求先[{}]: 請求書を送付できません: {}", supplier.name, e),
+    }
+}
@@ -0,0 +1,28 @@
+use crate::ddb;
+use crate::ddb::Tx;
+use crate::domain;
+use crate::CoreResult;
+use chrono::{DateTime, Utc};
+
+/// 支払期限を過ぎても入金が揃っていない請求書に、期限超過を検知した日時を記録する
+pub async fn exec(now: DateTime<Utc>) -> CoreResult<()> {
+    let conn = ddb::establish_connection();
+    let invoice_dao: ddb::Dao<domain::invoice::Invoice> = ddb::Dao::new();
+
+    let today = domain::YMD::from(now.naive_utc().date());
+
+    let count = Tx::run(&conn, || {
+        let mut count = 0;
+        for mut invoice in invoice_dao.get_all_overdue_candidates(&conn, &today)? {
+            if invoice.mark_overdue(now) {
+                invoice_dao.update(&conn, &invoice)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    })?;
+
+    println!("期限超過の請求書を{}件記録しました", count);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::ddb;
+use crate::ddb::Tx;
+use crate::domain;
+use crate::{CoreError, CoreResult};
+use chrono::{DateTime, Utc};
+use std::fs;
+
+/// 仲値（TTM）のCSVを読み込んで為替レートを登録する
+pub async fn exec(path: String, now: DateTime<Utc>) -> CoreResult<()> {
+    let conn = ddb::establish_connection();
+    let exchange_rate_dao: ddb::Dao<domain::currency::ExchangeRate> = ddb::Dao::new();
+
+    let csv = fs::read_to_string(path.as_str())
+        .map_err(|e| CoreError::Internal(format!("{}: {}", path, e)))?;
+    let rates = domain::currency::parse_ttm_csv(csv.as_str(), now).map_err(CoreError::Internal)?;
+
+    Tx::run(&conn, || {
+        for rate in rates.iter() {
+            exchange_rate_dao.upsert(&conn, rate)?;
+        }
+        Ok(())
+    })?;
+
+    println!("為替レートを{}件登録しました", rates.len());
+
+    Ok(())
+}
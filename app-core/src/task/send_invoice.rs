@@ -0,0 +1,160 @@
+use crate::ddb;
+use crate::ddb::Tx;
+use crate::domain;
+use crate::domain::invoice_delivery::{
+    InvoiceDelivery, DEFAULT_BODY_TEMPLATE, DEFAULT_SUBJECT_TEMPLATE,
+};
+use crate::domain::mail_template::{MailContext, MailTemplate};
+use crate::mail;
+use crate::misoca;
+use crate::INVOICE_BUCKET;
+use crate::{CoreError, CoreResult};
+use actix_web::web::Buf;
+use chrono::{DateTime, Utc};
+use cloud_storage::Object;
+use diesel::MysqlConnection;
+
+/// 請求書のPDFを添付して請求先の請求用アドレスに送り、送付済みにする。
+/// 送付の記録と送付済みへの更新はサーバーがメッセージを受け付けてから書き込み、送信に失敗した場合はどちらも残さない
+pub async fn exec(
+    conn: &MysqlConnection,
+    misoca_cli: &misoca::Client,
+    mail_cli: &mail::Client,
+    access_token: String,
+    supplier: &domain::supplier::Supplier,
+    mut invoice: domain::invoice::Invoice,
+    now: DateTime<Utc>,
+) -> CoreResult<(domain::invoice::Invoice, InvoiceDelivery)> {
+    let user_dao: ddb::Dao<domain::user::User> = ddb::Dao::new();
+    let sender_dao: ddb::Dao<domain::sender::Sender> = ddb::Dao::new();
+    let invoice_dao: ddb::Dao<domain::invoice::Invoice> = ddb::Dao::new();
+    let invoice_delivery_dao: ddb::Dao<InvoiceDelivery> = ddb::Dao::new();
+
+    invoice
+        .validate_for_delivery()
+        .map_err(CoreError::BadRequest)?;
+    if supplier.billing_email.is_empty() {
+        return Err(CoreError::BadRequest(format!(
+            "請求先[{}]の請求書送付先メールアドレスが登録されていません",
+            supplier.name
+        )));
+    }
+    let due = invoice
+        .payment_due_on_ymd
+        .to_date()
+        .ok_or_else(|| CoreError::BadRequest("支払期限のない請求書は送付できません".to_string()))?;
+
+    let user = user_dao.get(conn, supplier.user_id.clone())?;
+    let subject_template = MailTemplate::or_default(
+        user.invoice_mail_subject_template.as_str(),
+        DEFAULT_SUBJECT_TEMPLATE,
+    )
+    .map_err(CoreError::BadRequest)?;
+    let body_template = MailTemplate::or_default(
+        user.invoice_mail_body_template.as_str(),
+        DEFAULT_BODY_TEMPLATE,
+    )
+    .map_err(CoreError::BadRequest)?;
+
+    let senders = sender_dao.get_all_by_user(conn, user.id.clone())?;
+    let sender = senders.first();
+
+    let pdf = fetch_pdf(conn, misoca_cli, access_token, &mut invoice).await?;
+
+    let ctx = MailContext {
+        supplier_name: supplier.name.clone(),
+        sender_name: sender.map(|v| v.name.clone()).unwrap_or_default(),
+        subject: invoice.subject.clone(),
+        invoice_number: invoice.invoice_number.clone(),
+        issue: invoice.issue_ymd.to_date(),
+        due,
+        amount: invoice.payment_amount.clone(),
+        currency: invoice.currency.clone(),
+        days_overdue: 0,
+    };
+    let message = mail::Message {
+        from_name: ctx.sender_name.clone(),
+        reply_to: sender.map(|v| v.email.clone()).unwrap_or_default(),
+        to: vec![supplier.billing_email.clone()],
+        cc: supplier.delivery.cc.clone(),
+        bcc: supplier.delivery.bcc.clone(),
+        subject: subject_template.render(&ctx),
+        body: body_template.render(&ctx),
+        attachments: vec![mail::Attachment {
+            filename: pdf_filename(&invoice),
+            content_type: "application/pdf".to_string(),
+            data: pdf,
+        }],
+    };
+    let delivery = InvoiceDelivery::new(
+        invoice.id.clone(),
+        supplier.billing_email.clone(),
+        &supplier.delivery,
+        message.subject.clone(),
+        invoice.pdf_path.clone().unwrap_or_default(),
+        now,
+    );
+
+    mail_cli.send_async(message).await?;
+
+    invoice.mark_submitted();
+    Tx::run(conn, || {
+        invoice_dao.update(conn, &invoice)?;
+        invoice_delivery_dao.insert(conn, &delivery)
+    })?;
+
+    Ok((invoice, delivery))
+}
+
+/// 請求書の最新のPDFを取得する。保存済みのものがあればそれを使い、なければmisocaから取得して保存する
+async fn fetch_pdf(
+    conn: &MysqlConnection,
+    misoca_cli: &misoca::Client,
+    access_token: String,
+    invoice: &mut domain::invoice::Invoice,
+) -> CoreResult<Vec<u8>> {
+    let invoice_dao: ddb::Dao<domain::invoice::Invoice> = ddb::Dao::new();
+
+    let path = invoice.pdf_object_path();
+    if invoice.has_current_pdf() {
+        let data = Object::download(INVOICE_BUCKET, path.as_str()).await?;
+        return Ok(data);
+    }
+
+    let data = misoca_cli
+        .get_pdf(misoca::invoice::get_pdf::Input {
+            access_token,
+            invoice_id: invoice.id.clone(),
+        })
+        .await?
+        .bytes()
+        .to_vec();
+    Object::create(
+        INVOICE_BUCKET,
+        data.clone(),
+        path.as_str(),
+        "application/pdf",
+    )
+    .await?;
+
+    invoice.update_pdf_path(path);
+    Tx::run(conn, || {
+        invoice_dao.update(conn, invoice)?;
+        Ok(())
+    })?;
+
+    Ok(data)
+}
+
+fn pdf_filename(invoice: &domain::invoice::Invoice) -> String {
+    let kind = if invoice.is_credit_note() {
+        "赤伝"
+    } else {
+        "請求書"
+    };
+    if invoice.invoice_number.is_empty() {
+        format!("{}_{}.pdf", kind, invoice.subject)
+    } else {
+        format!("{}_{}.pdf", kind, invoice.invoice_number)
+    }
+}
@@ -0,0 +1,122 @@
+use crate::ddb;
+use crate::ddb::Tx;
+use crate::domain;
+use crate::domain::mail_template::{MailContext, MailTemplate};
+use crate::domain::reminder::{
+    InvoiceReminder, ReminderSchedule, DEFAULT_BODY_TEMPLATE, DEFAULT_SUBJECT_TEMPLATE,
+};
+use crate::mail;
+use crate::{CoreError, CoreResult};
+use chrono::{DateTime, Utc};
+
+/// 督促スケジュールに従って、未入金の請求書の督促メールを請求先に送る。
+/// 送信記録を送信と同じトランザクションで書き込み、同じ段階の督促を二度送らない
+pub async fn exec(mail_cli: mail::Client, now: DateTime<Utc>) -> CoreResult<()> {
+    let conn = ddb::establish_connection();
+    let user_dao: ddb::Dao<domain::user::User> = ddb::Dao::new();
+    let sender_dao: ddb::Dao<domain::sender::Sender> = ddb::Dao::new();
+    let invoice_dao: ddb::Dao<domain::invoice::Invoice> = ddb::Dao::new();
+    let invoice_reminder_dao: ddb::Dao<InvoiceReminder> = ddb::Dao::new();
+
+    let today = now.naive_utc().date();
+    let mut count = 0;
+
+    let users = user_dao
+        .get_all_with_suppliers(&conn)
+        .map_err(CoreError::from)?;
+
+    for (user, _) in users {
+        let templates = MailTemplate::or_default(
+            user.reminder_subject_template.as_str(),
+            DEFAULT_SUBJECT_TEMPLATE,
+        )
+        .and_then(|subject| {
+            let body = MailTemplate::or_default(
+                user.reminder_body_template.as_str(),
+                DEFAULT_BODY_TEMPLATE,
+            )?;
+            Ok((subject, body))
+        });
+        let (subject_template, body_template) = match templates {
+            Ok(templates) => templates,
+            Err(e) => {
+                println!("ユーザー[{}]: {}", user.id, e);
+                continue;
+            }
+        };
+
+        let senders = sender_dao.get_all_by_user(&conn, user.id.clone())?;
+        let sender = senders.first();
+
+        for (invoice, supplier) in invoice_dao.get_all_unpaid_by_user(&conn, user.id.clone())? {
+            if supplier.billing_email.is_empty() {
+                continue;
+            }
+            let schedule = match ReminderSchedule::of(
+                user.reminder_schedule.as_str(),
+                supplier.reminder_schedule.as_str(),
+            ) {
+                Ok(schedule) => schedule,
+                Err(e) => {
+                    println!("請求先[{}]: {}", supplier.name, e);
+                    continue;
+                }
+            };
+            let due = match invoice.payment_due_on_ymd.to_date() {
+                Some(due) => due,
+                None => continue,
+            };
+            let offset_days = match schedule.due_offset(due, today) {
+                Some(offset_days) => offset_days,
+                None => continue,
+            };
+            if invoice_reminder_dao.exists(&conn, invoice.id.clone(), offset_days)? {
+                continue;
+            }
+
+            let ctx = MailContext {
+                supplier_name: supplier.name.clone(),
+                sender_name: sender.map(|v| v.name.clone()).unwrap_or_default(),
+                subject: invoice.subject.clone(),
+                invoice_number: invoice.invoice_number.clone(),
+                issue: invoice.issue_ymd.to_date(),
+                due,
+                amount: invoice.outstanding_amount(),
+                currency: invoice.currency.clone(),
+                days_overdue: invoice.days_overdue(now),
+            };
+            let message = mail::Message {
+                from_name: ctx.sender_name.clone(),
+                reply_to: sender.map(|v| v.email.clone()).unwrap_or_default(),
+                to: vec![supplier.billing_email.clone()],
+                cc: supplier.delivery.cc.clone(),
+                bcc: vec![],
+                subject: subject_template.render(&ctx),
+                body: body_template.render(&ctx),
+                attachments: vec![],
+            };
+            let reminder = InvoiceReminder::new(
+                invoice.id.clone(),
+                offset_days,
+                supplier.billing_email.clone(),
+                message.subject.clone(),
+                now,
+            );
+
+            // サーバーがメッセージを受け付けなかった場合だけ記録をロールバックし、次回のバッチで送り直す。
+            // 受け付けた後の切断（QUITの失敗）は送信済みとして扱う
+            let result = Tx::run(&conn, || {
+                invoice_reminder_dao.insert(&conn, &reminder)?;
+                mail_cli.send(&message)
+            });
+            match result {
+                Ok(_) => count += 1,
+                Err(e) => println!("請求書[{}]: {}", invoice.id, e),
+            }
+        }
+    }
+
+    println!("督促メールを{}件送信しました", count);
+
+    Ok(())
+}
@@ -5,12 +5,12 @@ use crate::misoca;
 use crate::task::get_misoca_token;
 use crate::{CoreError, CoreResult};
 use chrono::{DateTime, Utc};
+use diesel::MysqlConnection;
 use std::sync::Mutex;
 
 pub async fn exec(misoca_cli: misoca::Client, now: DateTime<Utc>) -> CoreResult<()> {
     let conn = ddb::establish_connection();
     let user_dao: ddb::Dao<domain::user::User> = ddb::Dao::new();
-    let invoice_dao: ddb::Dao<domain::invoice::Invoice> = ddb::Dao::new();
 
     let users = user_dao
         .get_all_with_suppliers(&conn)
@@ -35,29 +35,44 @@ pub async fn exec(misoca_cli: misoca::Client, now: DateTime<Utc>) -> CoreResult<
                     access_token: access_token.clone(),
                     page: 1,
                     per_page: 100,
-                    supplier_id: supplier.id.clone(),
                     contact_group_id: supplier.contact_group_id.clone(),
                 })
                 .await?;
 
-            Tx::run(&conn, || {
-                for invoice in invoices {
-                    match invoice_dao.get(&conn, invoice.id.clone()) {
-                        Ok(current) => {
-                            if current.should_update(&invoice) {
-                                invoice_dao.update(&conn, &invoice)?;
-                            }
-                        }
-                        Err(CoreError::NotFound) => {
-                            invoice_dao.insert(&conn, &invoice)?;
-                        }
-                        Err(_) => {}
-                    }
-                }
-                Ok(())
-            })?;
+            save_remote(&conn, &supplier, invoices)?;
         }
     }
 
     Ok(())
 }
+
+/// misocaから取得した請求書を保存する。登録済みの請求書はその請求書の通貨で金額を読み、
+/// 請求先の通貨は新しく取り込む請求書にだけ使う
+pub fn save_remote(
+    conn: &MysqlConnection,
+    supplier: &domain::supplier::Supplier,
+    invoices: Vec<misoca::invoice::Invoice>,
+) -> CoreResult<()> {
+    let invoice_dao: ddb::Dao<domain::invoice::Invoice> = ddb::Dao::new();
+
+    Tx::run(conn, || {
+        for remote in invoices {
+            match invoice_dao.get(conn, remote.invoice_id()?) {
+                Ok(current) => {
+                    let invoice =
+                        remote.to_domain(supplier.id.clone(), current.currency.clone())?;
+                    if current.should_update(&invoice) {
+                        invoice_dao.update(conn, &current.merge_remote(invoice))?;
+                    }
+                }
+                Err(CoreError::NotFound) => {
+                    let invoice =
+                        remote.to_domain(supplier.id.clone(), supplier.currency.clone())?;
+                    invoice_dao.insert(conn, &invoice)?;
+                }
+                Err(_) => {}
+            }
+        }
+        Ok(())
+    })
+}
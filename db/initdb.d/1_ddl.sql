@@ -4,6 +4,12 @@ USE `works`;
 CREATE TABLE IF NOT EXISTS `users` (
     `id` VARCHAR(255) NOT NULL,
     `misoca_refresh_token` VARCHAR(255) NOT NULL,
+    `invoice_number_format` VARCHAR(255) NOT NULL DEFAULT '',
+    `reminder_schedule` VARCHAR(255) NOT NULL DEFAULT '',
+    `reminder_subject_template` VARCHAR(255) NOT NULL DEFAULT '',
+    `reminder_body_template` TEXT NOT NULL,
+    `invoice_mail_subject_template` VARCHAR(255) NOT NULL DEFAULT '',
+    `invoice_mail_body_template` TEXT NOT NULL,
     `created_at` DATETIME NOT NULL,
     `updated_at` DATETIME NOT NULL,
     PRIMARY KEY (`id`))
@@ -15,11 +21,42 @@ CREATE TABLE IF NOT EXISTS `suppliers` (
     `user_id` VARCHAR(255) NOT NULL,
     `contact_id` VARCHAR(255) NOT NULL,
     `name` VARCHAR(255) NOT NULL,
-    `billing_amount` INT(11) NOT NULL,
+    `billing_amount` DECIMAL(20,0) NOT NULL,
+    `currency` INT(11) NOT NULL DEFAULT 0,
     `billing_type` INT(11) NOT NULL,
     `end_ym` VARCHAR(255) NOT NULL,
     `subject` VARCHAR(255) NOT NULL,
     `subject_template` VARCHAR(255) NOT NULL,
+    `remarks_template` TEXT NOT NULL,
+    `invoice_number_prefix` VARCHAR(255) NOT NULL DEFAULT '',
+    `billing_email` VARCHAR(255) NOT NULL DEFAULT '',
+    `reminder_schedule` VARCHAR(255) NOT NULL DEFAULT '',
+    `billing_cc` VARCHAR(1024) NOT NULL DEFAULT '',
+    `billing_bcc` VARCHAR(1024) NOT NULL DEFAULT '',
+    `auto_send_invoice` BOOLEAN NOT NULL DEFAULT FALSE,
+    `closing_day` INT(11) NOT NULL DEFAULT 0,
+    `payment_term_type` INT(11) NOT NULL DEFAULT 0,
+    `payment_term_months` INT(11) NOT NULL DEFAULT 1,
+    `payment_term_day` INT(11) NOT NULL DEFAULT 0,
+    `business_day_adjustment` INT(11) NOT NULL DEFAULT 0,
+    `settlement_lower_hours` INT(11) NOT NULL DEFAULT 0,
+    `settlement_upper_hours` INT(11) NOT NULL DEFAULT 0,
+    `excess_hourly_rate` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `deduction_hourly_rate` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `retainer_included_quantity` INT(11) NOT NULL DEFAULT 0,
+    `retainer_unit` VARCHAR(255) NOT NULL DEFAULT '',
+    `retainer_overage_rate` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `interval_months` INT(11) NOT NULL DEFAULT 1,
+    `start_ym` VARCHAR(255) NOT NULL DEFAULT '',
+    `contract_end_ym` VARCHAR(255) NOT NULL DEFAULT '',
+    `contract_start_date` VARCHAR(255) NOT NULL DEFAULT '',
+    `contract_end_date` VARCHAR(255) NOT NULL DEFAULT '',
+    `proration_basis` INT(11) NOT NULL DEFAULT 0,
+    `tax_type` INT(11) NOT NULL DEFAULT 0,
+    `tax_inclusive` BOOLEAN NOT NULL DEFAULT FALSE,
+    `tax_rounding` INT(11) NOT NULL DEFAULT 0,
+    `withholding_tax` BOOLEAN NOT NULL DEFAULT FALSE,
+    `archived` BOOLEAN NOT NULL DEFAULT FALSE,
     `created_at` DATETIME NOT NULL,
     `updated_at` DATETIME NOT NULL,
     PRIMARY KEY (`id`),
@@ -41,16 +78,36 @@ CREATE TABLE IF NOT EXISTS `invoices` (
     `payment_due_on_at` DATETIME NULL,
     `invoice_number` VARCHAR (255) NOT NULL,
     `payment_status` INT(11) NOT NULL,
+    `remote_payment_status` INT(11) NOT NULL DEFAULT 0,
     `invoice_status` INT(11) NOT NULL,
     `recipient_name` VARCHAR(255) NOT NULL,
     `subject` VARCHAR(255) NOT NULL,
-    `total_amount` INT(11) NOT NULL,
-    `tax` INT(11) NOT NULL,
+    `billing_ym` VARCHAR(255) NOT NULL DEFAULT '',
+    `currency` INT(11) NOT NULL DEFAULT 0,
+    `total_amount` DECIMAL(20,0) NOT NULL,
+    `tax` DECIMAL(20,0) NOT NULL,
+    `withholding_tax` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `payment_amount` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `paid_amount` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `standard_taxable_amount` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `standard_tax` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `reduced_taxable_amount` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `reduced_tax` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `non_taxable_amount` DECIMAL(20,0) NOT NULL DEFAULT 0,
+    `remarks` TEXT NOT NULL,
     `pdf_path` VARCHAR(255) NOT NULL,
+    `overdue_at` DATETIME NULL,
+    `cancelled_at` DATETIME NULL,
+    `cancel_reason` VARCHAR(255) NOT NULL DEFAULT '',
+    `credit_note_id` VARCHAR(255) NULL,
+    `original_invoice_id` VARCHAR(255) NULL,
     `created_at` DATETIME NOT NULL,
     `updated_at` DATETIME NOT NULL,
+    `invoice_number_key` VARCHAR(255) AS (NULLIF(`invoice_number`, '')) STORED,
     PRIMARY KEY (`id`),
     INDEX `fk_invoices_suppliers_idx` (`supplier_id` ASC),
+    INDEX `invoices_billing_ym_idx` (`supplier_id` ASC, `billing_ym` ASC),
+    UNIQUE INDEX `invoices_invoice_number_uq` (`supplier_id` ASC, `invoice_number_key` ASC),
     CONSTRAINT `fk_invoices_suppliers`
     FOREIGN KEY (`supplier_id`)
     REFERENCES `suppliers` (`id`)
@@ -67,6 +124,8 @@ CREATE TABLE IF NOT EXISTS `senders` (
     `tel` VARCHAR(255) NOT NULL,
     `postal_code` VARCHAR(255) NOT NULL,
     `address` VARCHAR(255) NOT NULL,
+    `registration_number` VARCHAR(255) NOT NULL DEFAULT '',
+    `remarks_template` TEXT NOT NULL,
     `created_at` DATETIME NOT NULL,
     `updated_at` DATETIME NOT NULL,
     PRIMARY KEY (`id`),
@@ -96,4 +155,250 @@ CREATE TABLE IF NOT EXISTS `banks` (
     ON DELETE NO ACTION
     ON UPDATE NO ACTION)
 ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
-COMMENT = '';
\ No newline at end of file
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `working_hours` (
+    `id` VARCHAR(255) NOT NULL,
+    `supplier_id` VARCHAR(255) NOT NULL,
+    `ym` VARCHAR(255) NOT NULL,
+    `minutes` INT(11) NOT NULL,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE INDEX `uq_working_hours_supplier_ym` (`supplier_id`, `ym`),
+    CONSTRAINT `fk_working_hours_suppliers`
+    FOREIGN KEY (`supplier_id`)
+    REFERENCES `suppliers` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `retainer_usages` (
+    `id` VARCHAR(255) NOT NULL,
+    `supplier_id` VARCHAR(255) NOT NULL,
+    `ym` VARCHAR(255) NOT NULL,
+    `quantity` INT(11) NOT NULL,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE INDEX `uq_retainer_usages_supplier_ym` (`supplier_id`, `ym`),
+    CONSTRAINT `fk_retainer_usages_suppliers`
+    FOREIGN KEY (`supplier_id`)
+    REFERENCES `suppliers` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `supplier_items` (
+    `id` VARCHAR(255) NOT NULL,
+    `supplier_id` VARCHAR(255) NOT NULL,
+    `name` VARCHAR(255) NOT NULL,
+    `quantity` INT(11) NOT NULL,
+    `unit` VARCHAR(255) NOT NULL,
+    `unit_price` DECIMAL(20,0) NOT NULL,
+    `tax_type` INT(11) NOT NULL,
+    `withholding` BOOLEAN NOT NULL DEFAULT FALSE,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    INDEX `fk_supplier_items_suppliers_idx` (`supplier_id` ASC),
+    CONSTRAINT `fk_supplier_items_suppliers`
+    FOREIGN KEY (`supplier_id`)
+    REFERENCES `suppliers` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `supplier_suspensions` (
+    `id` VARCHAR(255) NOT NULL,
+    `supplier_id` VARCHAR(255) NOT NULL,
+    `from_ym` VARCHAR(255) NOT NULL,
+    `to_ym` VARCHAR(255) NOT NULL,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    INDEX `fk_supplier_suspensions_suppliers_idx` (`supplier_id` ASC),
+    CONSTRAINT `fk_supplier_suspensions_suppliers`
+    FOREIGN KEY (`supplier_id`)
+    REFERENCES `suppliers` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `supplier_prices` (
+    `id` VARCHAR(255) NOT NULL,
+    `supplier_id` VARCHAR(255) NOT NULL,
+    `effective_from` VARCHAR(255) NOT NULL,
+    `billing_amount` DECIMAL(20,0) NOT NULL,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE INDEX `uq_supplier_prices_supplier_effective_from` (`supplier_id`, `effective_from`),
+    CONSTRAINT `fk_supplier_prices_suppliers`
+    FOREIGN KEY (`supplier_id`)
+    REFERENCES `suppliers` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `supplier_milestones` (
+    `id` VARCHAR(255) NOT NULL,
+    `supplier_id` VARCHAR(255) NOT NULL,
+    `name` VARCHAR(255) NOT NULL,
+    `position` INT(11) NOT NULL DEFAULT 0,
+    `amount_type` INT(11) NOT NULL DEFAULT 0,
+    `amount_value` DECIMAL(20,0) NOT NULL,
+    `target_ym` VARCHAR(255) NOT NULL DEFAULT '',
+    `invoice_id` VARCHAR(255) NULL,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    INDEX `fk_supplier_milestones_suppliers_idx` (`supplier_id` ASC),
+    CONSTRAINT `fk_supplier_milestones_suppliers`
+    FOREIGN KEY (`supplier_id`)
+    REFERENCES `suppliers` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `expenses` (
+    `id` VARCHAR(255) NOT NULL,
+    `supplier_id` VARCHAR(255) NOT NULL,
+    `ymd` VARCHAR(255) NOT NULL,
+    `description` VARCHAR(255) NOT NULL,
+    `amount` DECIMAL(20,0) NOT NULL,
+    `tax_type` INT(11) NOT NULL DEFAULT 2,
+    `receipt_path` VARCHAR(255) NULL,
+    `invoice_id` VARCHAR(255) NULL,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    INDEX `fk_expenses_suppliers_idx` (`supplier_id` ASC),
+    CONSTRAINT `fk_expenses_suppliers`
+    FOREIGN KEY (`supplier_id`)
+    REFERENCES `suppliers` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `supplier_adjustments` (
+    `id` VARCHAR(255) NOT NULL,
+    `supplier_id` VARCHAR(255) NOT NULL,
+    `description` VARCHAR(255) NOT NULL,
+    `amount` DECIMAL(20,0) NOT NULL,
+    `tax_type` INT(11) NOT NULL DEFAULT 0,
+    `target_ym` VARCHAR(255) NOT NULL DEFAULT '',
+    `invoice_id` VARCHAR(255) NULL,
+    `cancelled` BOOLEAN NOT NULL DEFAULT FALSE,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    INDEX `fk_supplier_adjustments_suppliers_idx` (`supplier_id` ASC),
+    CONSTRAINT `fk_supplier_adjustments_suppliers`
+    FOREIGN KEY (`supplier_id`)
+    REFERENCES `suppliers` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `invoice_number_sequences` (
+    `user_id` VARCHAR(255) NOT NULL,
+    `year` INT(11) NOT NULL,
+    `last_number` INT(11) NOT NULL DEFAULT 0,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`user_id`, `year`),
+    CONSTRAINT `fk_invoice_number_sequences_users`
+    FOREIGN KEY (`user_id`)
+    REFERENCES `users` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `voided_invoice_numbers` (
+    `user_id` VARCHAR(255) NOT NULL,
+    `invoice_number` VARCHAR(255) NOT NULL,
+    `reason` TEXT NOT NULL,
+    `created_at` DATETIME NOT NULL,
+    PRIMARY KEY (`user_id`, `invoice_number`),
+    CONSTRAINT `fk_voided_invoice_numbers_users`
+    FOREIGN KEY (`user_id`)
+    REFERENCES `users` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `exchange_rates` (
+    `currency` INT(11) NOT NULL,
+    `ymd` VARCHAR(255) NOT NULL,
+    `rate` INT(11) NOT NULL,
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`currency`, `ymd`))
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `payments` (
+    `id` VARCHAR(255) NOT NULL,
+    `invoice_id` VARCHAR(255) NOT NULL,
+    `received_ymd` VARCHAR(255) NOT NULL,
+    `amount` DECIMAL(20,0) NOT NULL,
+    `method` INT(11) NOT NULL DEFAULT 0,
+    `memo` VARCHAR(255) NOT NULL DEFAULT '',
+    `created_at` DATETIME NOT NULL,
+    `updated_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    INDEX `fk_payments_invoices_idx` (`invoice_id` ASC),
+    CONSTRAINT `fk_payments_invoices`
+    FOREIGN KEY (`invoice_id`)
+    REFERENCES `invoices` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `invoice_reminders` (
+    `id` VARCHAR(255) NOT NULL,
+    `invoice_id` VARCHAR(255) NOT NULL,
+    `offset_days` INT(11) NOT NULL,
+    `sent_to` VARCHAR(255) NOT NULL,
+    `subject` VARCHAR(255) NOT NULL,
+    `sent_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE INDEX `uq_invoice_reminders_invoice_offset_days` (`invoice_id`, `offset_days`),
+    CONSTRAINT `fk_invoice_reminders_invoices`
+    FOREIGN KEY (`invoice_id`)
+    REFERENCES `invoices` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';
+
+CREATE TABLE IF NOT EXISTS `invoice_deliveries` (
+    `id` VARCHAR(255) NOT NULL,
+    `invoice_id` VARCHAR(255) NOT NULL,
+    `sent_to` VARCHAR(255) NOT NULL,
+    `cc` VARCHAR(1024) NOT NULL DEFAULT '',
+    `bcc` VARCHAR(1024) NOT NULL DEFAULT '',
+    `subject` VARCHAR(255) NOT NULL,
+    `pdf_path` VARCHAR(255) NOT NULL,
+    `sent_at` DATETIME NOT NULL,
+    PRIMARY KEY (`id`),
+    INDEX `fk_invoice_deliveries_invoices_idx` (`invoice_id` ASC),
+    CONSTRAINT `fk_invoice_deliveries_invoices`
+    FOREIGN KEY (`invoice_id`)
+    REFERENCES `invoices` (`id`)
+    ON DELETE NO ACTION
+    ON UPDATE NO ACTION)
+ENGINE = InnoDB DEFAULT CHARSET=utf8mb4
+COMMENT = '';